@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// The namespace an identifier belongs to in the audit trail file, matching
+/// the keyed table the request describes: `{"uids": {...}, "patient_ids":
+/// {...}, ...}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum IdentifierCategory {
+    Uids,
+    PatientIds,
+    Names,
+    AccessionNumbers,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AuditTrailData {
+    #[serde(default)]
+    uids: HashMap<String, String>,
+    #[serde(default)]
+    patient_ids: HashMap<String, String>,
+    #[serde(default)]
+    names: HashMap<String, String>,
+    #[serde(default)]
+    accession_numbers: HashMap<String, String>,
+}
+
+impl AuditTrailData {
+    fn table_mut(&mut self, category: IdentifierCategory) -> &mut HashMap<String, String> {
+        match category {
+            IdentifierCategory::Uids => &mut self.uids,
+            IdentifierCategory::PatientIds => &mut self.patient_ids,
+            IdentifierCategory::Names => &mut self.names,
+            IdentifierCategory::AccessionNumbers => &mut self.accession_numbers,
+        }
+    }
+}
+
+/// A JSON-backed table of original identifier values to the anonymized
+/// replacement each was given, namespaced by [`IdentifierCategory`] and
+/// persisted to `path` after every new mapping.
+///
+/// Loaded once when the `Anonymizer` is constructed with `audit_trail=...`,
+/// then consulted on every `anonymize` call so the same patient, study, and
+/// series identifiers always get the same replacement - something
+/// deterministic hashing alone can't guarantee once the secret changes, or
+/// across separate processes sharing the same trail file.
+pub(crate) struct AuditTrail {
+    path: PathBuf,
+    data: Mutex<AuditTrailData>,
+}
+
+impl AuditTrail {
+    pub(crate) fn load(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        let data = if path.exists() {
+            let contents = fs::read_to_string(&path)?;
+            serde_json::from_str(&contents).unwrap_or_default()
+        } else {
+            AuditTrailData::default()
+        };
+
+        Ok(Self {
+            path,
+            data: Mutex::new(data),
+        })
+    }
+
+    /// Returns the replacement already on file for `original`, or records
+    /// `fresh_replacement` (the value just generated by the normal hash/UID
+    /// logic for this run) as the replacement to reuse from now on.
+    pub(crate) fn replacement_for(
+        &self,
+        category: IdentifierCategory,
+        original: &str,
+        fresh_replacement: &str,
+    ) -> std::io::Result<String> {
+        let mut data = self.data.lock().unwrap();
+
+        if let Some(existing) = data.table_mut(category).get(original) {
+            return Ok(existing.clone());
+        }
+
+        data.table_mut(category)
+            .insert(original.to_string(), fresh_replacement.to_string());
+
+        let contents = serde_json::to_string_pretty(&*data)?;
+        fs::write(&self.path, contents)?;
+
+        Ok(fresh_replacement.to_string())
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::audit_trail::IdentifierCategory;
+
+/// Length in bytes of the AES-256-GCM key `reident_key` must supply.
+pub(crate) const REIDENT_KEY_LEN: usize = 32;
+
+/// Length in bytes of the random nonce prefixed to every encrypted keyfile.
+const NONCE_LEN: usize = 12;
+
+/// The inverse of an audit trail: pseudonym (the anonymized replacement) to
+/// the original identifier value it stands in for, namespaced by
+/// [`IdentifierCategory`]. Persisted to a `.keyfile` encrypted with
+/// `reident_key`, so - unlike the plaintext audit trail - the mapping is
+/// useless to anyone without the key, while still letting
+/// `Anonymizer::reidentify` recover the original values.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ReidentificationMap {
+    #[serde(default)]
+    uids: HashMap<String, String>,
+    #[serde(default)]
+    patient_ids: HashMap<String, String>,
+    #[serde(default)]
+    names: HashMap<String, String>,
+    #[serde(default)]
+    accession_numbers: HashMap<String, String>,
+}
+
+impl ReidentificationMap {
+    fn table_mut(&mut self, category: IdentifierCategory) -> &mut HashMap<String, String> {
+        match category {
+            IdentifierCategory::Uids => &mut self.uids,
+            IdentifierCategory::PatientIds => &mut self.patient_ids,
+            IdentifierCategory::Names => &mut self.names,
+            IdentifierCategory::AccessionNumbers => &mut self.accession_numbers,
+        }
+    }
+
+    pub(crate) fn table(&self, category: IdentifierCategory) -> &HashMap<String, String> {
+        match category {
+            IdentifierCategory::Uids => &self.uids,
+            IdentifierCategory::PatientIds => &self.patient_ids,
+            IdentifierCategory::Names => &self.names,
+            IdentifierCategory::AccessionNumbers => &self.accession_numbers,
+        }
+    }
+
+    pub(crate) fn record(&mut self, category: IdentifierCategory, pseudonym: &str, original: &str) {
+        self.table_mut(category)
+            .insert(pseudonym.to_string(), original.to_string());
+    }
+
+    /// Decrypts and loads a mapping previously saved with [`Self::save_encrypted`].
+    pub(crate) fn load_encrypted(path: &Path, key: &[u8; REIDENT_KEY_LEN]) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| format!("failed to read keyfile: {e}"))?;
+        if bytes.len() < NONCE_LEN {
+            return Err("keyfile is too short to contain a valid nonce".to_string());
+        }
+
+        let (nonce_bytes, ciphertext) = bytes.split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| "failed to decrypt keyfile: wrong key or corrupted file".to_string())?;
+
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| format!("keyfile did not contain a valid re-identification map: {e}"))
+    }
+
+    /// Merges any mapping already on disk at `path` with `self`, then
+    /// encrypts and writes the result back - so repeated `anonymize_to`
+    /// calls against the same keyfile accumulate mappings rather than
+    /// overwriting earlier ones.
+    pub(crate) fn save_encrypted(
+        &self,
+        path: &Path,
+        key: &[u8; REIDENT_KEY_LEN],
+    ) -> Result<(), String> {
+        let mut merged = if path.exists() {
+            Self::load_encrypted(path, key).unwrap_or_default()
+        } else {
+            Self::default()
+        };
+
+        for category in [
+            IdentifierCategory::Uids,
+            IdentifierCategory::PatientIds,
+            IdentifierCategory::Names,
+            IdentifierCategory::AccessionNumbers,
+        ] {
+            for (pseudonym, original) in self.table(category) {
+                merged.record(category, pseudonym, original);
+            }
+        }
+
+        let json = serde_json::to_vec(&merged).map_err(|e| e.to_string())?;
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), json.as_ref())
+            .map_err(|e| format!("failed to encrypt keyfile: {e}"))?;
+
+        let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(&nonce_bytes);
+        out.extend_from_slice(&ciphertext);
+
+        fs::write(path, out).map_err(|e| format!("failed to write keyfile: {e}"))
+    }
+}
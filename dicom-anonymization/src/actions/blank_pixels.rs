@@ -0,0 +1,476 @@
+use dicom_core::header::Header;
+use dicom_core::value::{PrimitiveValue, Value};
+use dicom_core::VR;
+use dicom_dictionary_std::tags;
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+
+/// A rectangular region of a frame to blank out, in one of two coordinate
+/// systems.
+///
+/// [`Rect::Fractional`] expresses the region as a fraction (`0.0..=1.0`) of
+/// the image's width/height, so the same region (e.g. "bottom strip of the
+/// frame") reuses across a series whose frames aren't all the same size.
+/// [`Rect::Absolute`] pins the region to exact pixel coordinates, for a
+/// known, fixed geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rect {
+    Fractional {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    Absolute {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl Rect {
+    /// Resolves this region to pixel coordinates `(x, y, width, height)`
+    /// against a frame of `columns` x `rows`, clamped so it never runs past
+    /// the frame's edge.
+    fn to_absolute(self, columns: u32, rows: u32) -> (u32, u32, u32, u32) {
+        let (x, y, width, height) = match self {
+            Rect::Absolute {
+                x,
+                y,
+                width,
+                height,
+            } => (x, y, width, height),
+            Rect::Fractional {
+                x,
+                y,
+                width,
+                height,
+            } => (
+                (x.clamp(0.0, 1.0) * columns as f64).round() as u32,
+                (y.clamp(0.0, 1.0) * rows as f64).round() as u32,
+                (width.clamp(0.0, 1.0) * columns as f64).round() as u32,
+                (height.clamp(0.0, 1.0) * rows as f64).round() as u32,
+            ),
+        };
+
+        let x = x.min(columns);
+        let y = y.min(rows);
+        let width = width.min(columns.saturating_sub(x));
+        let height = height.min(rows.saturating_sub(y));
+        (x, y, width, height)
+    }
+}
+
+/// The subset of the Image Pixel module (PS3.3 C.7.6.3) needed to locate
+/// pixel samples within `PixelData`.
+struct PixelGeometry {
+    rows: u32,
+    columns: u32,
+    samples_per_pixel: u32,
+    planar_configuration: u32,
+    number_of_frames: u32,
+    is_monochrome1: bool,
+}
+
+/// Reads [`PixelGeometry`] from `obj`'s Image Pixel module elements.
+///
+/// Returns [`ActionError::InvalidInput`] naming the first element required
+/// to locate pixel samples (`Rows`, `Columns`, `SamplesPerPixel`) that's
+/// missing; `PlanarConfiguration` and `NumberOfFrames` are optional per
+/// PS3.3 and default to `0` (color-by-pixel) and `1` respectively when
+/// absent.
+fn read_pixel_geometry(obj: &DefaultDicomObject) -> Result<PixelGeometry, ActionError> {
+    let required_u32 = |tag, name: &str| {
+        obj.element(tag)
+            .ok()
+            .and_then(|elem| elem.value().to_int::<u32>().ok())
+            .ok_or_else(|| ActionError::InvalidInput(format!("missing required tag: {name}")))
+    };
+
+    let optional_u32 = |tag, default: u32| {
+        obj.element(tag)
+            .ok()
+            .and_then(|elem| elem.value().to_int::<u32>().ok())
+            .unwrap_or(default)
+    };
+
+    let photometric_interpretation = obj
+        .element(tags::PHOTOMETRIC_INTERPRETATION)
+        .ok()
+        .and_then(|elem| elem.value().to_str().ok())
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default();
+
+    Ok(PixelGeometry {
+        rows: required_u32(tags::ROWS, "Rows")?,
+        columns: required_u32(tags::COLUMNS, "Columns")?,
+        samples_per_pixel: required_u32(tags::SAMPLES_PER_PIXEL, "SamplesPerPixel")?,
+        planar_configuration: optional_u32(tags::PLANAR_CONFIGURATION, 0),
+        number_of_frames: optional_u32(tags::NUMBER_OF_FRAMES, 1).max(1),
+        is_monochrome1: photometric_interpretation == "MONOCHROME1",
+    })
+}
+
+/// Blanks `rects` (or, if empty, every sample) in every frame of `samples`,
+/// writing `background` into each blanked sample.
+///
+/// Returns [`ActionError::InvalidInput`] if `samples` is shorter than
+/// `geometry` says it must be, rather than indexing past its end.
+fn blank_samples<T: Copy>(
+    samples: &mut [T],
+    geometry: &PixelGeometry,
+    rects: &[Rect],
+    background: T,
+) -> Result<(), ActionError> {
+    let frame_stride = (geometry.rows * geometry.columns * geometry.samples_per_pixel) as usize;
+    let plane_stride = (geometry.rows * geometry.columns) as usize;
+    let required_len = frame_stride * geometry.number_of_frames as usize;
+
+    if samples.len() < required_len {
+        return Err(ActionError::InvalidInput(format!(
+            "pixel data holds {} samples, but Rows/Columns/SamplesPerPixel/NumberOfFrames require at least {required_len}",
+            samples.len()
+        )));
+    }
+
+    if rects.is_empty() {
+        samples.fill(background);
+        return Ok(());
+    }
+
+    for frame in 0..geometry.number_of_frames as usize {
+        let frame_offset = frame * frame_stride;
+
+        for rect in rects {
+            let (x, y, width, height) = rect.to_absolute(geometry.columns, geometry.rows);
+
+            for row in y..y + height {
+                for col in x..x + width {
+                    let pixel_offset = (row * geometry.columns + col) as usize;
+
+                    for sample in 0..geometry.samples_per_pixel as usize {
+                        let index = if geometry.planar_configuration == 1
+                            && geometry.samples_per_pixel > 1
+                        {
+                            frame_offset + sample * plane_stride + pixel_offset
+                        } else {
+                            frame_offset
+                                + pixel_offset * geometry.samples_per_pixel as usize
+                                + sample
+                        };
+                        samples[index] = background;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Action implementing burned-in pixel annotation redaction (PS3.15 Annex E
+/// "Clean Pixel Data Option"): zeroes out the given regions - or, via
+/// [`BlankPixels::all`], the entire frame - of native (uncompressed) pixel
+/// data in every frame of `PixelData`.
+///
+/// Blanked samples are set to the background value for the image's
+/// `PhotometricInterpretation`: the maximum representable sample value for
+/// `MONOCHROME1` (whose sample values are inverted, so the maximum displays
+/// as black), `0` otherwise.
+///
+/// Only native pixel data (`PrimitiveValue::U8`/`PrimitiveValue::U16`) is
+/// supported. Encapsulated (compressed) pixel data is refused with
+/// [`ActionError::InvalidInput`] rather than transcoded, since doing so
+/// would require a codec dependency this crate doesn't otherwise need;
+/// callers working with compressed transfer syntaxes must decompress first.
+///
+/// This action only ever touches the `PixelData` element it's applied to -
+/// it has no way to also force `BurnedInAnnotation` to `"NO"`. Configure
+/// that tag's own action (e.g. `Action::Replace { value: "NO".into() }`)
+/// alongside this one if that attribute should be kept consistent with the
+/// redacted pixel data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlankPixels {
+    regions: Vec<Rect>,
+}
+
+impl BlankPixels {
+    /// Blanks only the given regions of every frame.
+    pub fn regions(regions: Vec<Rect>) -> Self {
+        Self { regions }
+    }
+
+    /// Blanks every pixel of every frame.
+    pub fn all() -> Self {
+        Self {
+            regions: Vec::new(),
+        }
+    }
+}
+
+impl DataElementAction for BlankPixels {
+    fn process<'a>(
+        &'a self,
+        _config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let geometry = read_pixel_geometry(obj)?;
+
+        let new_value = match elem.value() {
+            Value::Primitive(PrimitiveValue::U8(data)) => {
+                let mut data: Vec<u8> = data.iter().copied().collect();
+                let background = if geometry.is_monochrome1 { u8::MAX } else { 0 };
+                blank_samples(&mut data, &geometry, &self.regions, background)?;
+                PrimitiveValue::U8(data.into())
+            }
+            Value::Primitive(PrimitiveValue::U16(data)) => {
+                let mut data: Vec<u16> = data.iter().copied().collect();
+                let background = if geometry.is_monochrome1 { u16::MAX } else { 0 };
+                blank_samples(&mut data, &geometry, &self.regions, background)?;
+                PrimitiveValue::U16(data.into())
+            }
+            _ => {
+                return Err(ActionError::InvalidInput(
+                    "blanking pixel regions requires native (uncompressed) pixel data".to_string(),
+                ))
+            }
+        };
+
+        let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::Primitive(new_value));
+        Ok(Some(Cow::Owned(new_elem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_object::FileDicomObject;
+
+    use crate::config::Config;
+    use crate::test_utils::make_file_meta;
+
+    fn image_obj(
+        photometric_interpretation: &str,
+        samples_per_pixel: u16,
+        planar_configuration: u16,
+        number_of_frames: Option<i32>,
+    ) -> FileDicomObject<InMemElement> {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(tags::ROWS, VR::US, Value::from(2u16)));
+        obj.put(InMemElement::new(tags::COLUMNS, VR::US, Value::from(2u16)));
+        obj.put(InMemElement::new(
+            tags::SAMPLES_PER_PIXEL,
+            VR::US,
+            Value::from(samples_per_pixel),
+        ));
+        obj.put(InMemElement::new(
+            tags::PLANAR_CONFIGURATION,
+            VR::US,
+            Value::from(planar_configuration),
+        ));
+        obj.put(InMemElement::new(
+            tags::PHOTOMETRIC_INTERPRETATION,
+            VR::CS,
+            Value::from(photometric_interpretation),
+        ));
+        if let Some(number_of_frames) = number_of_frames {
+            obj.put(InMemElement::new(
+                tags::NUMBER_OF_FRAMES,
+                VR::IS,
+                Value::from(number_of_frames.to_string()),
+            ));
+        }
+        obj
+    }
+
+    #[test]
+    fn test_blank_all_pixels_zeroes_monochrome2_image() {
+        let obj = image_obj("MONOCHROME2", 1, 0, None);
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![1, 2, 3, 4].into())),
+        );
+
+        let result = BlankPixels::all()
+            .process(&Config::default(), &obj, &elem)
+            .unwrap()
+            .unwrap();
+        match result.into_owned().value() {
+            Value::Primitive(PrimitiveValue::U8(data)) => assert_eq!(data.as_ref(), &[0, 0, 0, 0]),
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_blank_all_pixels_uses_max_value_for_monochrome1() {
+        let obj = image_obj("MONOCHROME1", 1, 0, None);
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OW,
+            Value::Primitive(PrimitiveValue::U16(vec![1, 2, 3, 4].into())),
+        );
+
+        let result = BlankPixels::all()
+            .process(&Config::default(), &obj, &elem)
+            .unwrap()
+            .unwrap();
+        match result.into_owned().value() {
+            Value::Primitive(PrimitiveValue::U16(data)) => {
+                assert_eq!(data.as_ref(), &[u16::MAX; 4])
+            }
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_blank_region_leaves_rest_of_frame_untouched() {
+        let obj = image_obj("MONOCHROME2", 1, 0, None);
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![9, 9, 9, 9].into())),
+        );
+
+        // top-left single pixel of the 2x2 frame only
+        let regions = vec![Rect::Absolute {
+            x: 0,
+            y: 0,
+            width: 1,
+            height: 1,
+        }];
+        let result = BlankPixels::regions(regions)
+            .process(&Config::default(), &obj, &elem)
+            .unwrap()
+            .unwrap();
+        match result.into_owned().value() {
+            Value::Primitive(PrimitiveValue::U8(data)) => assert_eq!(data.as_ref(), &[0, 9, 9, 9]),
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_blank_fractional_region_covers_whole_frame() {
+        let obj = image_obj("MONOCHROME2", 1, 0, None);
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![9, 9, 9, 9].into())),
+        );
+
+        let regions = vec![Rect::Fractional {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        }];
+        let result = BlankPixels::regions(regions)
+            .process(&Config::default(), &obj, &elem)
+            .unwrap()
+            .unwrap();
+        match result.into_owned().value() {
+            Value::Primitive(PrimitiveValue::U8(data)) => assert_eq!(data.as_ref(), &[0, 0, 0, 0]),
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_blank_respects_multiple_frames() {
+        let obj = image_obj("MONOCHROME2", 1, 0, Some(2));
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![9, 9, 9, 9, 9, 9, 9, 9].into())),
+        );
+
+        let result = BlankPixels::all()
+            .process(&Config::default(), &obj, &elem)
+            .unwrap()
+            .unwrap();
+        match result.into_owned().value() {
+            Value::Primitive(PrimitiveValue::U8(data)) => assert_eq!(data.as_ref(), &[0u8; 8]),
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_blank_respects_planar_configuration() {
+        // 2x2 image, 2 samples per pixel, planar (plane 0 then plane 1)
+        let obj = image_obj("RGB", 2, 1, None);
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![9, 9, 9, 9, 9, 9, 9, 9].into())),
+        );
+
+        let regions = vec![Rect::Absolute {
+            x: 0,
+            y: 0,
+            width: 1,
+            height: 1,
+        }];
+        let result = BlankPixels::regions(regions)
+            .process(&Config::default(), &obj, &elem)
+            .unwrap()
+            .unwrap();
+        match result.into_owned().value() {
+            // plane 0's first sample and plane 1's first sample both blanked
+            Value::Primitive(PrimitiveValue::U8(data)) => {
+                assert_eq!(data.as_ref(), &[0, 9, 9, 9, 0, 9, 9, 9])
+            }
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_process_errors_on_missing_geometry() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![1, 2].into())),
+        );
+
+        let err = BlankPixels::all()
+            .process(&Config::default(), &obj, &elem)
+            .unwrap_err();
+        assert!(matches!(err, ActionError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_process_errors_when_pixel_data_is_shorter_than_geometry_requires() {
+        // geometry declares a 2x2 frame (4 samples) but the element only holds 2
+        let obj = image_obj("MONOCHROME2", 1, 0, None);
+        let elem = InMemElement::new(
+            tags::PIXEL_DATA,
+            VR::OB,
+            Value::Primitive(PrimitiveValue::U8(vec![1, 2].into())),
+        );
+
+        let err = BlankPixels::all()
+            .process(&Config::default(), &obj, &elem)
+            .unwrap_err();
+        assert!(matches!(err, ActionError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_process_errors_on_encapsulated_pixel_data() {
+        let obj = image_obj("MONOCHROME2", 1, 0, None);
+        let elem = InMemElement::new(tags::PIXEL_DATA, VR::OB, Value::from("not pixel samples"));
+
+        let err = BlankPixels::all()
+            .process(&Config::default(), &obj, &elem)
+            .unwrap_err();
+        assert!(matches!(err, ActionError::InvalidInput(_)));
+    }
+}
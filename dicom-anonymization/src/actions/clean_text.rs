@@ -0,0 +1,292 @@
+use dicom_core::value::Value;
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+
+use crate::actions::errors::ActionError;
+#[cfg(feature = "serde")]
+use crate::actions::tag_string_wrapper;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+use crate::Tag;
+
+/// A regex pattern compiled once, when the owning [`CleanTextRule`] is
+/// deserialized, so a malformed pattern is rejected at config-load time
+/// rather than the first time the rule runs.
+#[derive(Debug, Clone)]
+struct CompiledPattern(Regex);
+
+impl CompiledPattern {
+    fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl PartialEq for CompiledPattern {
+    // Two patterns are equal iff their source text is, not their compiled
+    // representation - `Regex` itself has no `PartialEq`.
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CompiledPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CompiledPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+
+        Regex::new(&pattern).map(CompiledPattern).map_err(|e| {
+            serde::de::Error::custom(format!("invalid regex pattern {pattern:?}: {e}"))
+        })
+    }
+}
+
+/// One named redaction rule within an [`crate::actions::Action::CleanText`]:
+/// a required regex `pattern`, plus an optional list of `literal_tags` whose
+/// *runtime* value (e.g. the patient's own name or ID, pulled from
+/// `(0010,0010)`/`(0010,0020)`) is also redacted wherever it appears
+/// verbatim in the text, in addition to whatever the pattern matches.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CleanTextRule {
+    pub name: String,
+    pattern: CompiledPattern,
+    #[cfg_attr(feature = "serde", serde(default, with = "literal_tags_wrapper"))]
+    literal_tags: Vec<Tag>,
+}
+
+impl CleanTextRule {
+    /// Builds a rule from an already-valid regex pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying [`regex::Error`] as a string if `pattern`
+    /// doesn't compile.
+    pub fn new(name: impl Into<String>, pattern: &str) -> Result<Self, String> {
+        Ok(Self {
+            name: name.into(),
+            pattern: CompiledPattern(Regex::new(pattern).map_err(|e| e.to_string())?),
+            literal_tags: Vec::new(),
+        })
+    }
+
+    /// Adds a tag whose runtime value should also be redacted literally
+    /// wherever it appears in the cleaned text.
+    pub fn literal_tag(mut self, tag: Tag) -> Self {
+        self.literal_tags.push(tag);
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+mod literal_tags_wrapper {
+    use super::tag_string_wrapper;
+    use crate::Tag;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(tags: &[Tag], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(tags.len()))?;
+        for tag in tags {
+            seq.serialize_element(&TagWrapper(*tag))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Tag>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let wrappers = Vec::<TagWrapper>::deserialize(deserializer)?;
+        Ok(wrappers.into_iter().map(|wrapper| wrapper.0).collect())
+    }
+
+    struct TagWrapper(Tag);
+
+    impl serde::Serialize for TagWrapper {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            tag_string_wrapper::serialize(&self.0, serializer)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for TagWrapper {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            tag_string_wrapper::deserialize(deserializer).map(TagWrapper)
+        }
+    }
+}
+
+/// Action implementing PS3.15 Annex E-style scrubbing of free-text VRs
+/// (Study/Series/Image Description, Image Comments, and similar): every
+/// [`CleanTextRule`] is applied in order, replacing each regex match - and
+/// each literal occurrence of a `literal_tags` value - with `replacement`,
+/// leaving the rest of the text untouched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanText {
+    rules: Vec<CleanTextRule>,
+    replacement: String,
+}
+
+impl CleanText {
+    pub fn new(rules: Vec<CleanTextRule>, replacement: impl Into<String>) -> Self {
+        Self {
+            rules,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+impl DataElementAction for CleanText {
+    fn process<'a>(
+        &'a self,
+        _config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let original = elem.value().to_str().map_err(|e| {
+            ActionError::InvalidInput(format!("clean_text requires a string value: {e}"))
+        })?;
+
+        let mut cleaned = original.into_owned();
+
+        for rule in &self.rules {
+            cleaned = rule
+                .pattern
+                .regex()
+                .replace_all(&cleaned, self.replacement.as_str())
+                .into_owned();
+
+            for &tag in &rule.literal_tags {
+                let literal = obj
+                    .element(tag)
+                    .ok()
+                    .and_then(|elem| elem.value().to_str().ok())
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default();
+
+                if !literal.is_empty() {
+                    cleaned = cleaned.replace(&literal, &self.replacement);
+                }
+            }
+        }
+
+        let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(cleaned));
+        Ok(Some(Cow::Owned(new_elem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+    use dicom_dictionary_std::tags;
+    use dicom_object::FileDicomObject;
+
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_invalid_pattern_fails_to_deserialize() {
+        let json = r#"{"name": "bad", "pattern": "(unclosed"}"#;
+        let result: Result<CleanTextRule, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_pattern_round_trips_through_json() {
+        let rule = CleanTextRule::new("mrn", r"\d{6,}").unwrap();
+        let json = serde_json::to_string(&rule).unwrap();
+        let deserialized: CleanTextRule = serde_json::from_str(&json).unwrap();
+        assert_eq!(rule, deserialized);
+    }
+
+    #[test]
+    fn test_regex_rule_redacts_match_and_keeps_rest_of_text() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().build();
+
+        let rule = CleanTextRule::new("mrn", r"\d{6,}").unwrap();
+        let action = CleanText::new(vec![rule], "[REDACTED]");
+
+        let elem = InMemElement::new(
+            tags::STUDY_DESCRIPTION,
+            VR::LO,
+            Value::from("patient MRN 203087 seen for follow-up"),
+        );
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(
+            processed.value(),
+            &Value::from("patient MRN [REDACTED] seen for follow-up")
+        );
+    }
+
+    #[test]
+    fn test_literal_tag_redacts_patient_name_wherever_it_appears() {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("Doe^John"),
+        ));
+        let config = ConfigBuilder::new().build();
+
+        let rule = CleanTextRule::new("patient_name", r"(?!)")
+            .unwrap()
+            .literal_tag(tags::PATIENT_NAME);
+        let action = CleanText::new(vec![rule], "[REDACTED]");
+
+        let elem = InMemElement::new(
+            tags::IMAGE_COMMENTS,
+            VR::LT,
+            Value::from("seen by Doe^John today"),
+        );
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("seen by [REDACTED] today"));
+    }
+
+    #[test]
+    fn test_non_matching_text_is_left_untouched() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().build();
+
+        let rule = CleanTextRule::new("mrn", r"\d{6,}").unwrap();
+        let action = CleanText::new(vec![rule], "[REDACTED]");
+
+        let elem = InMemElement::new(
+            tags::STUDY_DESCRIPTION,
+            VR::LO,
+            Value::from("routine follow-up"),
+        );
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("routine follow-up"));
+    }
+}
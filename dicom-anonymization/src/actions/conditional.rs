@@ -0,0 +1,192 @@
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+
+use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+
+/// A regex pattern compiled once, when the owning [`crate::actions::Action::OnlyIfMatches`]
+/// is deserialized, so a malformed pattern is rejected at config-load time
+/// rather than the first time the action runs.
+#[derive(Debug, Clone)]
+pub struct CompiledPattern(Regex);
+
+impl CompiledPattern {
+    pub(crate) fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl PartialEq for CompiledPattern {
+    // Two patterns are equal iff their source text is, not their compiled
+    // representation - `Regex` itself has no `PartialEq`.
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CompiledPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CompiledPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+
+        Regex::new(&pattern).map(CompiledPattern).map_err(|e| {
+            serde::de::Error::custom(format!("invalid regex pattern {pattern:?}: {e}"))
+        })
+    }
+}
+
+fn is_empty_value(elem: &InMemElement) -> bool {
+    elem.value()
+        .primitive()
+        .map(|value| value.is_empty())
+        .unwrap_or(false)
+}
+
+/// Wraps an `action` so it only runs when the element currently has a
+/// non-empty value, otherwise leaving the element untouched.
+///
+/// This mirrors Orthanc's `Clear(tag, onlyIfExists)`: it avoids, for example,
+/// emptying `PatientComments` when it was never populated in the first place.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnlyIfNonEmpty<A> {
+    action: A,
+}
+
+impl<A> OnlyIfNonEmpty<A> {
+    pub fn new(action: A) -> Self {
+        Self { action }
+    }
+}
+
+impl<A: DataElementAction> DataElementAction for OnlyIfNonEmpty<A> {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        if is_empty_value(elem) {
+            return Ok(Some(Cow::Borrowed(elem)));
+        }
+        self.action.process(config, obj, elem)
+    }
+}
+
+/// Wraps an `action` so it only runs when the element's value matches `pattern`,
+/// otherwise leaving the element untouched.
+///
+/// This lets callers write rules like "replace `AccessionNumber` only when it
+/// matches a given regex" instead of applying the action indiscriminately.
+#[derive(Debug, Clone)]
+pub struct OnlyIfMatches<A> {
+    action: A,
+    pattern: Regex,
+}
+
+impl<A> OnlyIfMatches<A> {
+    pub fn new(action: A, pattern: Regex) -> Self {
+        Self { action, pattern }
+    }
+}
+
+impl<A: DataElementAction> DataElementAction for OnlyIfMatches<A> {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let matches = elem
+            .value()
+            .to_str()
+            .map(|value| self.pattern.is_match(&value))
+            .unwrap_or(false);
+
+        if !matches {
+            return Ok(Some(Cow::Borrowed(elem)));
+        }
+        self.action.process(config, obj, elem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::value::{PrimitiveValue, Value};
+    use dicom_core::VR;
+    use dicom_object::FileDicomObject;
+
+    use crate::actions::Empty;
+    use crate::config::Config;
+    use crate::tags;
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_only_if_non_empty_skips_empty_element() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(
+            tags::PATIENT_COMMENTS,
+            VR::LT,
+            Value::Primitive(PrimitiveValue::Empty),
+        );
+
+        let action = OnlyIfNonEmpty::new(Empty);
+        let result = action.process(&Config::default(), &obj, &elem).unwrap();
+        assert_eq!(result.unwrap().into_owned(), elem);
+    }
+
+    #[test]
+    fn test_only_if_non_empty_runs_on_populated_element() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(tags::PATIENT_COMMENTS, VR::LT, Value::from("some notes"));
+
+        let action = OnlyIfNonEmpty::new(Empty);
+        let result = action.process(&Config::default(), &obj, &elem).unwrap();
+        assert_eq!(
+            result.unwrap().into_owned().value(),
+            &Value::Primitive(PrimitiveValue::Empty)
+        );
+    }
+
+    #[test]
+    fn test_only_if_matches_skips_non_matching_element() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(tags::ACCESSION_NUMBER, VR::SH, Value::from("not-a-number"));
+
+        let action = OnlyIfMatches::new(Empty, Regex::new(r"^\d+$").unwrap());
+        let result = action.process(&Config::default(), &obj, &elem).unwrap();
+        assert_eq!(result.unwrap().into_owned(), elem);
+    }
+
+    #[test]
+    fn test_only_if_matches_runs_on_matching_element() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(tags::ACCESSION_NUMBER, VR::SH, Value::from("0123456789"));
+
+        let action = OnlyIfMatches::new(Empty, Regex::new(r"^\d+$").unwrap());
+        let result = action.process(&Config::default(), &obj, &elem).unwrap();
+        assert_eq!(
+            result.unwrap().into_owned().value(),
+            &Value::Primitive(PrimitiveValue::Empty)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{Days, NaiveDate};
+use dicom_core::header::Header;
+use dicom_core::value::Value;
+use dicom_core::{Tag, VR};
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use std::borrow::Cow;
+
+use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+
+/// Upper bound accepted for [`crate::actions::Action::DateShift`]'s
+/// `max_offset_days`, enforced via `garde` at config deserialization time (see
+/// [`crate::actions::Action`]). 10 years comfortably covers any clinically
+/// meaningful longitudinal offset while catching a typo'd extra digit (e.g.
+/// `36500` instead of `3650`) before it ever reaches [`day_offset`].
+pub(crate) const MAX_OFFSET_DAYS: u32 = 3650;
+
+/// Action implementing the PS3.15 "Retain Longitudinal Temporal Information
+/// with Modified Dates" option: shifts every DA value, and the date portion
+/// of every DT value, by a signed day offset that is deterministic per
+/// subject and bounded to `max_offset_days`.
+///
+/// Because the offset is derived once per subject (from `subject_tag`,
+/// typically `PatientID`) and reused for every date belonging to that
+/// subject, intervals between dates (e.g. days since a prior study) survive
+/// de-identification even though the absolute dates don't. Time-of-day is
+/// left untouched, and `VR::TM` elements - which carry only a time of day
+/// with no date portion to shift - are passed through unchanged rather than
+/// having their leading digits misread as a date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateShift {
+    subject_tag: Tag,
+    max_offset_days: u32,
+}
+
+impl DateShift {
+    pub fn new(subject_tag: Tag, max_offset_days: u32) -> Self {
+        Self {
+            subject_tag,
+            max_offset_days,
+        }
+    }
+}
+
+impl DataElementAction for DateShift {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        if elem.vr() == VR::TM {
+            // A pure time-of-day has no date portion to shift.
+            return Ok(Some(Cow::Borrowed(elem)));
+        }
+
+        let subject_id = obj
+            .element(self.subject_tag)
+            .ok()
+            .and_then(|subject_elem| subject_elem.value().to_str().ok());
+
+        let Some(subject_id) = subject_id else {
+            // Nothing to key the offset off of: leave the date untouched rather
+            // than fail the whole object over one missing identifier.
+            return Ok(Some(Cow::Borrowed(elem)));
+        };
+
+        let offset_days = day_offset(
+            config.get_date_shift_key(),
+            &subject_id,
+            self.max_offset_days,
+        );
+
+        let value = match elem.value().to_str() {
+            Ok(value) => value,
+            Err(_) => return Ok(Some(Cow::Borrowed(elem))),
+        };
+
+        match shift_date_str(&value, offset_days) {
+            Some(shifted) => {
+                let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(shifted));
+                Ok(Some(Cow::Owned(new_elem)))
+            }
+            // empty or unrecognized date: pass through unchanged
+            None => Ok(Some(Cow::Borrowed(elem))),
+        }
+    }
+}
+
+/// Derives a deterministic signed offset in `-max_offset_days..=max_offset_days`
+/// for `subject_id`, keyed by `key`.
+///
+/// This stands in for HMAC(key, subject_id): BLAKE3's keyed hash mode serves
+/// the same purpose (a secret-dependent MAC) without pulling in a separate
+/// hmac/sha2 dependency, and the first 8 bytes of the digest are reduced
+/// modulo `2 * max_offset_days + 1` to land in the bounded range.
+///
+/// `pub(crate)` since [`super::hash_date::HashDate`] derives its offset the
+/// same way, keyed off another tag's value instead of `subject_tag`'s.
+pub(crate) fn day_offset(key: &str, subject_id: &str, max_offset_days: u32) -> i64 {
+    if max_offset_days == 0 {
+        return 0;
+    }
+
+    let derived_key = *blake3::hash(key.as_bytes()).as_bytes();
+    let digest = blake3::keyed_hash(&derived_key, subject_id.as_bytes());
+    let n = u64::from_le_bytes(digest.as_bytes()[..8].try_into().unwrap());
+
+    let range = 2 * max_offset_days as u64 + 1;
+    (n % range) as i64 - max_offset_days as i64
+}
+
+/// Shifts the date found at the start of `value` by `offset_days`, preserving
+/// whatever comes after it (a DT value's time-of-day and timezone) unchanged.
+///
+/// Returns `None` if `value` is empty or its leading digits don't form a
+/// recognized `YYYY`, `YYYYMM` or `YYYYMMDD` date, so the caller can leave the
+/// original value in place.
+///
+/// `pub(crate)` since [`super::hash_date::HashDate`] shifts dates the same
+/// way, just keyed off a hash of another tag's value instead of a bounded,
+/// subject-derived offset.
+pub(crate) fn shift_date_str(value: &str, offset_days: i64) -> Option<String> {
+    let digit_len = value
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .count()
+        .min(8);
+    let (date_part, remainder) = value.split_at(digit_len);
+
+    let (date, format) = match digit_len {
+        8 => (
+            NaiveDate::parse_from_str(date_part, "%Y%m%d").ok()?,
+            "%Y%m%d",
+        ),
+        6 => (
+            NaiveDate::parse_from_str(&format!("{date_part}01"), "%Y%m%d").ok()?,
+            "%Y%m",
+        ),
+        4 => (
+            NaiveDate::parse_from_str(&format!("{date_part}0101"), "%Y%m%d").ok()?,
+            "%Y",
+        ),
+        _ => return None,
+    };
+
+    let shifted = if offset_days >= 0 {
+        date + Days::new(offset_days as u64)
+    } else {
+        date - Days::new((-offset_days) as u64)
+    };
+
+    Some(format!("{}{remainder}", shifted.format(format)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+    use dicom_object::FileDicomObject;
+
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+    use dicom_dictionary_std::tags;
+
+    #[test]
+    fn test_day_offset_is_bounded() {
+        for subject_id in ["A", "B", "12345", ""] {
+            let offset = day_offset("secret", subject_id, 365);
+            assert!((-365..=365).contains(&offset));
+        }
+    }
+
+    #[test]
+    fn test_day_offset_is_stable_for_same_subject() {
+        let first = day_offset("secret", "203087", 365);
+        let second = day_offset("secret", "203087", 365);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_day_offset_differs_by_key() {
+        let a = day_offset("secret-a", "203087", 365);
+        let b = day_offset("secret-b", "203087", 365);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_shift_date_str_full_date() {
+        assert_eq!(shift_date_str("20200101", 5), Some("20200106".into()));
+        assert_eq!(shift_date_str("20200101", -5), Some("20191227".into()));
+    }
+
+    #[test]
+    fn test_shift_date_str_preserves_time_remainder() {
+        assert_eq!(
+            shift_date_str("20200101131110", 1),
+            Some("20200102131110".into())
+        );
+    }
+
+    #[test]
+    fn test_shift_date_str_partial_precision() {
+        assert_eq!(shift_date_str("202001", 45), Some("202002".into()));
+        assert_eq!(shift_date_str("2020", 400), Some("2021".into()));
+    }
+
+    #[test]
+    fn test_shift_date_str_empty_is_none() {
+        assert_eq!(shift_date_str("", 5), None);
+    }
+
+    #[test]
+    fn test_shift_date_str_invalid_is_none() {
+        assert_eq!(shift_date_str("not-a-date", 5), None);
+    }
+
+    #[test]
+    fn test_process_keeps_interval_between_two_dates_for_same_subject() {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("203087"),
+        ));
+        let config = ConfigBuilder::new().date_shift_key("secret").build();
+
+        let action = DateShift::new(tags::PATIENT_ID, 365);
+        let study_date = InMemElement::new(tags::STUDY_DATE, VR::DA, Value::from("20200101"));
+        let series_date = InMemElement::new(tags::SERIES_DATE, VR::DA, Value::from("20200103"));
+
+        let shifted_study = action
+            .process(&config, &obj, &study_date)
+            .unwrap()
+            .unwrap()
+            .into_owned();
+        let shifted_series = action
+            .process(&config, &obj, &series_date)
+            .unwrap()
+            .unwrap()
+            .into_owned();
+
+        let parse = |elem: &InMemElement| {
+            NaiveDate::parse_from_str(&elem.value().to_str().unwrap(), "%Y%m%d").unwrap()
+        };
+        assert_eq!(
+            (parse(&shifted_series) - parse(&shifted_study)).num_days(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_process_leaves_tm_value_unchanged() {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("203087"),
+        ));
+        let config = ConfigBuilder::new().date_shift_key("secret").build();
+
+        let action = DateShift::new(tags::PATIENT_ID, 365);
+        let elem = InMemElement::new(tags::STUDY_TIME, VR::TM, Value::from("131110"));
+
+        let result = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(result.into_owned(), elem);
+    }
+
+    #[test]
+    fn test_process_passes_through_when_subject_tag_missing() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().date_shift_key("secret").build();
+
+        let action = DateShift::new(tags::PATIENT_ID, 365);
+        let elem = InMemElement::new(tags::STUDY_DATE, VR::DA, Value::from("20200101"));
+
+        let result = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(result.into_owned(), elem);
+    }
+}
@@ -1,29 +1,87 @@
 use dicom_core::header::Header;
-use dicom_core::{DataElement, PrimitiveValue};
-use dicom_object::DefaultDicomObject;
+use dicom_core::value::{DataSetSequence, Value};
+use dicom_core::{DataElement, Length, PrimitiveValue, VR};
 use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
 use std::borrow::Cow;
 
-use crate::actions::ProcessElement;
 use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
 use crate::config::Config;
 
 /// Action that empties DICOM element values while preserving the element structure.
 ///
-/// This action replaces the value of a DICOM element with an empty primitive value,
+/// This action replaces the value of a DICOM element with an empty value,
 /// effectively removing the data content while keeping the element tag and VR intact.
+///
+/// `VR::SQ` elements are given an empty sequence (no items) rather than an empty
+/// primitive value, since a sequence-valued element cannot hold a primitive value.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Empty;
 
-impl ProcessElement for Empty {
+impl DataElementAction for Empty {
     fn process<'a>(
         &'a self,
         _config: &Config,
         _obj: &DefaultDicomObject,
         elem: &'a InMemElement,
     ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
-        let new_elem =
-            DataElement::new::<PrimitiveValue>(elem.tag(), elem.vr(), PrimitiveValue::Empty);
+        let new_elem = if elem.vr() == VR::SQ {
+            InMemElement::new(
+                elem.tag(),
+                VR::SQ,
+                Value::Sequence(DataSetSequence::new(Vec::new(), Length(0))),
+            )
+        } else {
+            DataElement::new::<PrimitiveValue>(elem.tag(), elem.vr(), PrimitiveValue::Empty)
+        };
+        Ok(Some(Cow::Owned(new_elem)))
+    }
+}
+
+/// Action that replaces DICOM element values with a VR-appropriate placeholder.
+///
+/// Unlike [`Empty`], this action never leaves a Type-1 element without a value:
+/// it substitutes a dummy value suitable for the element's VR (e.g. a dummy date
+/// for `DA`, a dummy time for `TM`, a placeholder string for textual VRs, or `0`
+/// for numeric VRs), so that readers requiring a present, non-empty value for
+/// Type-1 attributes do not break.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder;
+
+impl Placeholder {
+    /// Returns a dummy value appropriate for the given VR.
+    fn dummy_value(vr: VR) -> PrimitiveValue {
+        match vr {
+            VR::DA => PrimitiveValue::from("19000101"),
+            VR::TM => PrimitiveValue::from("000000.00"),
+            VR::DT => PrimitiveValue::from("19000101000000.00"),
+            VR::IS | VR::DS => PrimitiveValue::from("0"),
+            VR::US | VR::SS | VR::UL | VR::SL => PrimitiveValue::from(0i32),
+            VR::FL => PrimitiveValue::from(0f32),
+            VR::FD => PrimitiveValue::from(0f64),
+            VR::SQ => PrimitiveValue::Empty,
+            _ => PrimitiveValue::from("ANONYMIZED"),
+        }
+    }
+}
+
+impl DataElementAction for Placeholder {
+    fn process<'a>(
+        &'a self,
+        _config: &Config,
+        _obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let new_elem = if elem.vr() == VR::SQ {
+            InMemElement::new(
+                elem.tag(),
+                VR::SQ,
+                Value::Sequence(DataSetSequence::new(Vec::new(), Length(0))),
+            )
+        } else {
+            DataElement::new::<PrimitiveValue>(elem.tag(), elem.vr(), Self::dummy_value(elem.vr()))
+        };
         Ok(Some(Cow::Owned(new_elem)))
     }
 }
@@ -32,10 +90,10 @@ impl ProcessElement for Empty {
 mod tests {
     use super::*;
 
-    use dicom_core::VR;
     use dicom_core::value::Value;
-    use dicom_object::FileDicomObject;
+    use dicom_core::VR;
     use dicom_object::mem::InMemElement;
+    use dicom_object::FileDicomObject;
 
     use crate::config::Config;
     use crate::tags;
@@ -62,4 +120,77 @@ mod tests {
             _ => panic!("unexpected result"),
         }
     }
+
+    #[test]
+    fn test_process_sequence() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(
+            tags::REFERENCED_STUDY_SEQUENCE,
+            VR::SQ,
+            Value::Sequence(DataSetSequence::new(Vec::new(), Length(0))),
+        );
+
+        let result = Empty.process(&Config::default(), &obj, &elem);
+        match result {
+            Ok(Some(cow)) => {
+                let owned = cow.into_owned();
+                assert_eq!(owned.vr(), VR::SQ);
+                assert!(matches!(owned.value(), Value::Sequence(seq) if seq.items().is_empty()));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_placeholder_date() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(tags::STUDY_DATE, VR::DA, Value::from("20230101"));
+
+        let result = Placeholder.process(&Config::default(), &obj, &elem);
+        match result {
+            Ok(Some(cow)) => {
+                let owned = cow.into_owned();
+                assert_eq!(owned.value(), &Value::from("19000101"));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_placeholder_float_is_encoded_at_the_right_width() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+
+        let fl_elem = InMemElement::new(tags::ACCESSION_NUMBER, VR::FL, Value::from(1.5f32));
+        let fl_result = Placeholder.process(&Config::default(), &obj, &fl_elem);
+        match fl_result {
+            Ok(Some(cow)) => assert_eq!(cow.into_owned().value().length(), Length(4)),
+            _ => panic!("unexpected result"),
+        }
+
+        let fd_elem = InMemElement::new(tags::ACCESSION_NUMBER, VR::FD, Value::from(1.5f64));
+        let fd_result = Placeholder.process(&Config::default(), &obj, &fd_elem);
+        match fd_result {
+            Ok(Some(cow)) => assert_eq!(cow.into_owned().value().length(), Length(8)),
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_placeholder_string() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let elem = InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("0123456789ABCDEF"),
+        );
+
+        let result = Placeholder.process(&Config::default(), &obj, &elem);
+        match result {
+            Ok(Some(cow)) => {
+                let owned = cow.into_owned();
+                assert_eq!(owned.value(), &Value::from("ANONYMIZED"));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
 }
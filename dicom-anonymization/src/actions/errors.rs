@@ -0,0 +1,31 @@
+use dicom_core::value::CastValueError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub(crate) enum ActionError {
+    #[error("Internal error: {}", .0.to_lowercase())]
+    InternalError(String),
+
+    #[error("Invalid input: {}", .0.to_lowercase())]
+    InvalidInput(String),
+
+    #[error("Value error: {}", .0.to_lowercase())]
+    ValueError(String),
+
+    #[error("Value exceeds VR length: {}", .0.to_lowercase())]
+    ValueExceedsVrLength(String),
+
+    /// [`super::hash_date::HashDate`]'s `other_tag` was missing from the
+    /// object, or couldn't be read as a string - downgraded to a no-op by
+    /// [`crate::processor::DefaultProcessor`] rather than failing the whole
+    /// object, since one missing cross-reference tag shouldn't block every
+    /// other element from being anonymized.
+    #[error("invalid hash date tag: {0}")]
+    InvalidHashDateTag(String),
+}
+
+impl From<CastValueError> for ActionError {
+    fn from(err: CastValueError) -> Self {
+        ActionError::ValueError(format!("{err}"))
+    }
+}
@@ -0,0 +1,135 @@
+use dicom_core::header::Header;
+use dicom_core::value::Value;
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use std::borrow::Cow;
+
+use crate::actions::errors::ActionError;
+use crate::actions::utils::{check_hash_length_fits_vr, normalize_for_vr, resolve_element_vr};
+use crate::actions::DataElementAction;
+use crate::config::Config;
+
+/// The smallest `length` [`crate::actions::Action::Hash`] accepts, enforced
+/// by `garde` at config deserialization time (see
+/// [`crate::actions::Action::Hash`]). Below this, a truncated hash is short
+/// enough that brute-forcing it against the limited space of likely source
+/// values (e.g. a Patient ID) becomes practical even though the hash itself
+/// is keyed.
+pub const HASH_LENGTH_MINIMUM: usize = 8;
+
+/// A validated `length` for [`Hash`], at least [`HASH_LENGTH_MINIMUM`] bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashLength(pub usize);
+
+/// Action that replaces a data element's value with a keyed hash of itself,
+/// via [`Config::get_hash_fn_for`].
+///
+/// An optional `length` truncates the hash to that many characters, checked
+/// up front against the element's VR (see
+/// [`crate::actions::utils::check_hash_length_fits_vr`]) so a too-long
+/// request is rejected with [`ActionError::ValueExceedsVrLength`] instead of
+/// producing a value the VR doesn't allow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hash {
+    length: Option<HashLength>,
+}
+
+impl Hash {
+    pub fn new(length: Option<HashLength>) -> Self {
+        Self { length }
+    }
+}
+
+impl DataElementAction for Hash {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let value = match elem.value().to_str() {
+            Ok(value) => value,
+            Err(_) => return Ok(Some(Cow::Borrowed(elem))),
+        };
+
+        let vr = resolve_element_vr(obj, &elem.tag(), elem.vr());
+        let hashed = config.get_hash_fn_for(&elem.tag()).call(&value);
+
+        let truncated = match self.length {
+            Some(HashLength(length)) => {
+                check_hash_length_fits_vr(length, vr)?;
+                hashed.chars().take(length).collect()
+            }
+            None => hashed,
+        };
+
+        let (normalized, _truncated) = normalize_for_vr(&truncated, vr);
+        let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(normalized));
+        Ok(Some(Cow::Owned(new_elem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+    use dicom_dictionary_std::tags;
+    use dicom_object::FileDicomObject;
+
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_hash_matches_configured_hasher() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().keyed_hash_fn("secret").build();
+
+        let elem = InMemElement::new(tags::PATIENT_ID, VR::LO, Value::from("203087"));
+        let processed = Hash::new(None)
+            .process(&config, &obj, &elem)
+            .unwrap()
+            .unwrap();
+
+        let expected = config.get_hash_fn_for(&tags::PATIENT_ID).call("203087");
+        assert_eq!(processed.value(), &Value::from(expected));
+    }
+
+    #[test]
+    fn test_hash_is_deterministic() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().keyed_hash_fn("secret").build();
+
+        let elem = InMemElement::new(tags::PATIENT_ID, VR::LO, Value::from("203087"));
+        let first = Hash::new(None).process(&config, &obj, &elem).unwrap();
+        let second = Hash::new(None).process(&config, &obj, &elem).unwrap();
+        assert_eq!(first.unwrap().into_owned(), second.unwrap().into_owned());
+    }
+
+    #[test]
+    fn test_hash_truncates_to_requested_length() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().keyed_hash_fn("secret").build();
+
+        let elem = InMemElement::new(tags::PATIENT_ID, VR::LO, Value::from("203087"));
+        let processed = Hash::new(Some(HashLength(10)))
+            .process(&config, &obj, &elem)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(processed.value().to_str().unwrap().trim_end().len(), 10);
+    }
+
+    #[test]
+    fn test_hash_length_exceeding_vr_max_is_an_error() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().keyed_hash_fn("secret").build();
+
+        let elem = InMemElement::new(tags::ACCESSION_NUMBER, VR::SH, Value::from("203087"));
+        let err = Hash::new(Some(HashLength(17)))
+            .process(&config, &obj, &elem)
+            .unwrap_err();
+
+        assert!(matches!(err, ActionError::ValueExceedsVrLength(_)));
+    }
+}
@@ -0,0 +1,146 @@
+use dicom_core::header::Header;
+use dicom_core::value::Value;
+use dicom_core::{Tag, VR};
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use std::borrow::Cow;
+
+use crate::actions::date_shift::{day_offset, shift_date_str, MAX_OFFSET_DAYS};
+use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+
+/// Action implementing the PS3.15 "Retain Longitudinal Temporal Information
+/// with Modified Dates" option by deriving the day offset from another tag's
+/// value, rather than a caller-supplied `max_offset_days`-bounded subject key
+/// like [`crate::actions::Action::DateShift`] does.
+///
+/// This is useful when the dataset already carries a stable per-subject
+/// identifier in `other_tag` (typically `PatientID` or `StudyInstanceUID`)
+/// that every date-bearing element should shift consistently against,
+/// without configuring a separate `subject_tag`/`max_offset_days` pair per
+/// date action. The offset is bounded the same way `DateShift`'s is (see
+/// [`MAX_OFFSET_DAYS`]) and keyed by [`Config::get_date_shift_key`], so it's
+/// deterministic across a run but not recoverable without the key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashDate {
+    other_tag: Tag,
+}
+
+impl HashDate {
+    pub fn new(other_tag: Tag) -> Self {
+        Self { other_tag }
+    }
+}
+
+impl DataElementAction for HashDate {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        if elem.vr() == VR::TM {
+            // A pure time-of-day has no date portion to shift.
+            return Ok(Some(Cow::Borrowed(elem)));
+        }
+
+        let other_value = obj
+            .element(self.other_tag)
+            .ok()
+            .and_then(|other_elem| other_elem.value().to_str().ok());
+
+        let Some(other_value) = other_value else {
+            return Err(ActionError::InvalidHashDateTag(format!(
+                "tag {} not found or has no string value",
+                self.other_tag
+            )));
+        };
+
+        let offset_days = day_offset(config.get_date_shift_key(), &other_value, MAX_OFFSET_DAYS);
+
+        let value = match elem.value().to_str() {
+            Ok(value) => value,
+            Err(_) => return Ok(Some(Cow::Borrowed(elem))),
+        };
+
+        match shift_date_str(&value, offset_days) {
+            Some(shifted) => {
+                let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(shifted));
+                Ok(Some(Cow::Owned(new_elem)))
+            }
+            // empty or unrecognized date: pass through unchanged
+            None => Ok(Some(Cow::Borrowed(elem))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+    use dicom_dictionary_std::tags;
+    use dicom_object::FileDicomObject;
+
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_process_shifts_date_consistently_for_same_other_value() {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("203087"),
+        ));
+        let config = ConfigBuilder::new().date_shift_key("secret").build();
+
+        let action = HashDate::new(tags::PATIENT_ID);
+        let study_date = InMemElement::new(tags::STUDY_DATE, VR::DA, Value::from("20200101"));
+        let series_date = InMemElement::new(tags::SERIES_DATE, VR::DA, Value::from("20200103"));
+
+        let shifted_study = action
+            .process(&config, &obj, &study_date)
+            .unwrap()
+            .unwrap()
+            .into_owned();
+        let shifted_series = action
+            .process(&config, &obj, &series_date)
+            .unwrap()
+            .unwrap()
+            .into_owned();
+
+        assert_ne!(shifted_study.value(), &Value::from("20200101"));
+        assert_ne!(shifted_study, shifted_series);
+    }
+
+    #[test]
+    fn test_process_leaves_tm_value_unchanged() {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("203087"),
+        ));
+        let config = ConfigBuilder::new().date_shift_key("secret").build();
+
+        let action = HashDate::new(tags::PATIENT_ID);
+        let elem = InMemElement::new(tags::STUDY_TIME, VR::TM, Value::from("131110"));
+
+        let result = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(result.into_owned(), elem);
+    }
+
+    #[test]
+    fn test_process_errors_when_other_tag_missing() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().date_shift_key("secret").build();
+
+        let action = HashDate::new(tags::PATIENT_ID);
+        let elem = InMemElement::new(tags::STUDY_DATE, VR::DA, Value::from("20200101"));
+
+        let err = action.process(&config, &obj, &elem).unwrap_err();
+        assert!(matches!(err, ActionError::InvalidHashDateTag(_)));
+    }
+}
@@ -0,0 +1,96 @@
+use dicom_core::value::Value;
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use std::borrow::Cow;
+
+use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+use crate::uid_mapper::UidMapper;
+
+/// Action that replaces a UID value with a newly generated one, rooted at
+/// [`Config::get_uid_root`].
+///
+/// If `config` has a [`UidMapper`] set (see [`Config::get_uid_mapper`]), the
+/// replacement is looked up there, so the same original UID maps to the same
+/// replacement everywhere it's referenced across the whole run. Otherwise an
+/// ephemeral mapper is used for just this call: generation is a pure
+/// function of the original UID and the UID root, so the result is still
+/// deterministic for a given input, it's just not cached or shared with any
+/// other element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashUID;
+
+impl DataElementAction for HashUID {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        _obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let value = match elem.value().to_str() {
+            Ok(value) => value,
+            Err(_) => return Ok(Some(Cow::Borrowed(elem))),
+        };
+
+        let mapped = match config.get_uid_mapper() {
+            Some(mapper) => mapper.map(&value),
+            None => UidMapper::new(config.get_uid_root().clone()).map(&value),
+        };
+
+        let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(mapped));
+        Ok(Some(Cow::Owned(new_elem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+    use dicom_dictionary_std::tags;
+    use dicom_object::FileDicomObject;
+    use std::sync::Arc;
+
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_process_replaces_uid_with_mapped_value() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().build();
+
+        let elem = InMemElement::new(tags::SOP_INSTANCE_UID, VR::UI, Value::from("1.2.3.4.5"));
+        let processed = HashUID.process(&config, &obj, &elem).unwrap().unwrap();
+
+        assert_ne!(processed.value().to_str().unwrap(), "1.2.3.4.5");
+    }
+
+    #[test]
+    fn test_process_is_deterministic_without_a_configured_mapper() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().build();
+
+        let elem = InMemElement::new(tags::SOP_INSTANCE_UID, VR::UI, Value::from("1.2.3.4.5"));
+        let first = HashUID.process(&config, &obj, &elem).unwrap().unwrap();
+        let second = HashUID.process(&config, &obj, &elem).unwrap().unwrap();
+
+        assert_eq!(first.into_owned(), second.into_owned());
+    }
+
+    #[test]
+    fn test_process_uses_the_configured_mapper_when_set() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let mapper = Arc::new(UidMapper::new(config_default_uid_root()));
+        let config = ConfigBuilder::new().uid_mapper(mapper.clone()).build();
+
+        let elem = InMemElement::new(tags::SOP_INSTANCE_UID, VR::UI, Value::from("1.2.3.4.5"));
+        let processed = HashUID.process(&config, &obj, &elem).unwrap().unwrap();
+
+        assert_eq!(processed.value().to_str().unwrap(), mapper.map("1.2.3.4.5"));
+    }
+
+    fn config_default_uid_root() -> crate::config::UidRoot {
+        ConfigBuilder::new().build().get_uid_root().clone()
+    }
+}
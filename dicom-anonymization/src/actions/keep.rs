@@ -1,21 +1,21 @@
-use dicom_object::DefaultDicomObject;
 use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
 use std::borrow::Cow;
 
-use crate::actions::ProcessElement;
 use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
 use crate::config::Config;
 
-/// Action that preserves DICOM element values unchanged.
+/// Action that preserves a data element's value unchanged.
 ///
-/// This action returns the original element without any modifications,
-/// effectively keeping the data as-is during the anonymization process.
-/// It can (also) be used to keep certain private tags, even when
-/// `remove_private_tags` in the config is set to `true`.
+/// Useful for overriding a broader rule (e.g. `--exclude`, or a VR-level
+/// default) for one specific tag that should survive de-identification
+/// as-is, including a private tag that would otherwise be dropped by
+/// `remove_private_tags`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Keep;
 
-impl ProcessElement for Keep {
+impl DataElementAction for Keep {
     fn process<'a>(
         &'a self,
         _config: &Config,
@@ -30,13 +30,13 @@ impl ProcessElement for Keep {
 mod tests {
     use super::*;
 
-    use dicom_core::VR;
     use dicom_core::value::Value;
-    use dicom_object::FileDicomObject;
+    use dicom_core::VR;
+    use dicom_dictionary_std::tags;
     use dicom_object::mem::InMemElement;
+    use dicom_object::FileDicomObject;
 
     use crate::config::Config;
-    use crate::tags;
     use crate::test_utils::make_file_meta;
 
     #[test]
@@ -0,0 +1,843 @@
+mod blank_pixels;
+mod clean_text;
+mod conditional;
+mod date_shift;
+mod empty;
+pub(crate) mod errors;
+pub mod hash;
+mod hash_date;
+mod hash_uid;
+mod keep;
+mod no_action;
+pub mod pseudonymize;
+mod remove;
+mod replace;
+pub(crate) mod utils;
+
+use crate::actions::errors::ActionError;
+use crate::actions::hash::HASH_LENGTH_MINIMUM;
+use crate::config::Config;
+use crate::Tag;
+use blank_pixels::BlankPixels;
+pub use blank_pixels::Rect;
+use clean_text::CleanText;
+pub use clean_text::CleanTextRule;
+pub use conditional::CompiledPattern;
+use conditional::{OnlyIfMatches, OnlyIfNonEmpty};
+use date_shift::{DateShift, MAX_OFFSET_DAYS};
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use empty::Empty;
+pub use empty::Placeholder;
+use garde::Validate;
+use hash::{Hash, HashLength};
+use hash_date::HashDate;
+use hash_uid::HashUID;
+use keep::Keep;
+use no_action::NoAction;
+use pseudonymize::Pseudonymize;
+use remove::Remove;
+use replace::Replace;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+
+pub(crate) trait DataElementAction {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError>;
+}
+
+// Lets a dispatched `Box<dyn DataElementAction>` (e.g. from
+// `Action::get_action_struct`) be wrapped by `conditional::OnlyIfNonEmpty`/
+// `OnlyIfMatches`, which are generic over any `A: DataElementAction`.
+impl DataElementAction for Box<dyn DataElementAction> {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        (**self).process(config, obj, elem)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TagString(pub Tag);
+
+#[cfg(feature = "serde")]
+impl Serialize for TagString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let tag_str = format!("{}", self.0);
+        serializer.serialize_str(&tag_str)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TagString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tag_str = String::deserialize(deserializer)?;
+
+        let tag: Tag = tag_str.parse().map_err(|_| {
+            serde::de::Error::custom(format!(
+                "Tag must be in format '(XXXX,XXXX)' where X is a hex digit, got: {}",
+                tag_str
+            ))
+        })?;
+
+        // Make sure the tag string starts and ends with parentheses
+        if !tag_str.starts_with('(') || !tag_str.ends_with(')') {
+            return Err(serde::de::Error::custom(format!(
+                "Tag must be in format '(XXXX,XXXX)', got: {}",
+                tag_str
+            )));
+        }
+
+        Ok(TagString(tag))
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) mod tag_string_wrapper {
+    use super::TagString;
+    use crate::Tag;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(tag: &Tag, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        TagString(*tag).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Tag, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        TagString::deserialize(deserializer).map(|wrapper| wrapper.0)
+    }
+}
+
+/// Specifies the action to perform on DICOM data elements during processing.
+#[derive(Validate, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "action", rename_all = "lowercase"))]
+pub enum Action {
+    /// Clear the value of the data element.
+    Empty,
+
+    /// Hash the data element value using an optional custom hash length.
+    Hash {
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        #[garde(inner(range(min = HASH_LENGTH_MINIMUM)))]
+        length: Option<usize>,
+    },
+
+    /// Change a date, using a hash of the given other tag value to determine the offset.
+    HashDate {
+        #[cfg_attr(feature = "serde", serde(with = "tag_string_wrapper"))]
+        #[garde(skip)]
+        other_tag: Tag,
+    },
+
+    /// Shift a date (or the date portion of a date/time value) by a signed,
+    /// bounded offset that is deterministic per subject, so intervals between
+    /// dates belonging to the same subject are preserved. Also selectable as
+    /// `"shiftdates"` or `"shift_date"` in config, other spellings some
+    /// callers (e.g. the Python bindings) prefer.
+    #[cfg_attr(feature = "serde", serde(alias = "shiftdates", alias = "shift_date"))]
+    DateShift {
+        #[cfg_attr(feature = "serde", serde(with = "tag_string_wrapper"))]
+        #[garde(skip)]
+        subject_tag: Tag,
+        #[cfg_attr(feature = "serde", serde(alias = "max_days"))]
+        #[garde(range(max = MAX_OFFSET_DAYS))]
+        max_offset_days: u32,
+    },
+
+    /// Generate a new unique identifier (UID) by hashing the original UID.
+    HashUID,
+
+    /// Replace the value with a keyed, reversible encryption of itself
+    /// (base64-encoded), using [`Config::get_pseudonymization_key`]. Unlike
+    /// [`Action::Hash`]/[`Action::HashUID`], an authorized holder of the key
+    /// can recover the original value with
+    /// [`crate::actions::pseudonymize::decrypt`], which supports legitimate
+    /// re-identification workflows. [`Config::validate`]
+    /// rejects using this action with an empty pseudonymization key.
+    Pseudonymize,
+
+    /// Preserve the original data element value without modification.
+    Keep,
+
+    /// No action specified.
+    None,
+
+    /// Completely remove the data element from the DICOM dataset.
+    Remove,
+
+    /// Replace the data element value with the specified string.
+    ///
+    /// `value` may contain `{{(gggg,eeee)}}` tokens, each replaced with that
+    /// tag's value elsewhere in the dataset (empty if absent), optionally
+    /// preceded by a `hash` or `upper` helper, e.g. `{{hash (0010,0020)}}`.
+    /// A `value` with no `{{` is used verbatim.
+    Replace {
+        #[garde(skip)]
+        value: String,
+    },
+
+    /// Zero out the given rectangular regions of native pixel data, in every
+    /// frame, to redact burned-in annotations.
+    BlankPixelRegions {
+        #[garde(skip)]
+        regions: Vec<Rect>,
+    },
+
+    /// Zero out every pixel of native pixel data, in every frame.
+    BlankAllPixels,
+
+    /// Scrub identifiers embedded in a free-text value (e.g. Study
+    /// Description, Image Comments) while leaving the rest of the text
+    /// intact: every [`CleanTextRule`] pattern match, and every literal
+    /// occurrence of a `literal_tags` value, is replaced with `replacement`.
+    /// Each rule's pattern is compiled when this action is deserialized, so
+    /// a malformed pattern is rejected at config-load time rather than the
+    /// first time the rule runs.
+    #[cfg_attr(feature = "serde", serde(rename = "clean_text"))]
+    CleanText {
+        #[garde(skip)]
+        rules: Vec<CleanTextRule>,
+        #[garde(skip)]
+        replacement: String,
+    },
+
+    /// Run `action` only when the element currently has a non-empty value,
+    /// otherwise leave it untouched - e.g. "empty `PatientComments` only if
+    /// it contains text" instead of emptying it indiscriminately. See
+    /// [`conditional::OnlyIfNonEmpty`].
+    OnlyIfNonEmpty {
+        #[garde(skip)]
+        action: Box<Action>,
+    },
+
+    /// Run `action` only when the element's value matches `pattern`,
+    /// otherwise leave it untouched - e.g. "replace `AccessionNumber` only
+    /// when it matches a regex". See [`conditional::OnlyIfMatches`].
+    OnlyIfMatches {
+        #[garde(skip)]
+        action: Box<Action>,
+        #[garde(skip)]
+        pattern: CompiledPattern,
+    },
+}
+
+impl Action {
+    pub(crate) fn get_action_struct(&self) -> Box<dyn DataElementAction> {
+        match self {
+            Action::Empty => Box::new(Empty),
+            Action::Hash { length } => {
+                let hash_length = length.as_ref().map(|length| HashLength(*length));
+                Box::new(Hash::new(hash_length))
+            }
+            Action::HashDate { other_tag } => Box::new(HashDate::new(*other_tag)),
+            Action::DateShift {
+                subject_tag,
+                max_offset_days,
+            } => Box::new(DateShift::new(*subject_tag, *max_offset_days)),
+            Action::HashUID => Box::new(HashUID),
+            Action::Pseudonymize => Box::new(Pseudonymize),
+            Action::Keep => Box::new(Keep),
+            Action::None => Box::new(NoAction),
+            Action::Remove => Box::new(Remove),
+            Action::Replace { value } => Box::new(Replace::new(value.clone())),
+            Action::BlankPixelRegions { regions } => {
+                Box::new(BlankPixels::regions(regions.clone()))
+            }
+            Action::BlankAllPixels => Box::new(BlankPixels::all()),
+            Action::CleanText { rules, replacement } => {
+                Box::new(CleanText::new(rules.clone(), replacement.clone()))
+            }
+            Action::OnlyIfNonEmpty { action } => {
+                Box::new(OnlyIfNonEmpty::new(action.get_action_struct()))
+            }
+            Action::OnlyIfMatches { action, pattern } => Box::new(OnlyIfMatches::new(
+                action.get_action_struct(),
+                pattern.regex().clone(),
+            )),
+        }
+    }
+
+    /// A short, PHI-free name for this action, suitable for an audit trail
+    /// (see [`AuditRecord`]) - e.g. `"HashDate"`, `"NoAction"`, `"Remove"`.
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Action::Empty => "Empty",
+            Action::Hash { .. } => "Hash",
+            Action::HashDate { .. } => "HashDate",
+            Action::DateShift { .. } => "DateShift",
+            Action::HashUID => "HashUID",
+            Action::Pseudonymize => "Pseudonymize",
+            Action::Keep => "Keep",
+            Action::None => "NoAction",
+            Action::Remove => "Remove",
+            Action::Replace { .. } => "Replace",
+            Action::BlankPixelRegions { .. } => "BlankPixelRegions",
+            Action::BlankAllPixels => "BlankAllPixels",
+            Action::CleanText { .. } => "CleanText",
+            Action::OnlyIfNonEmpty { .. } => "OnlyIfNonEmpty",
+            Action::OnlyIfMatches { .. } => "OnlyIfMatches",
+        }
+    }
+
+    /// A short, PHI-free description of what this action does to a kept
+    /// value, for [`AuditRecord::transform`]. `None` for actions that leave
+    /// the value as found (or remove it outright, which `AuditRecord`
+    /// already reports via `kept: false`).
+    fn transform_description(&self) -> Option<&'static str> {
+        match self {
+            Action::Empty => Some("cleared"),
+            Action::Hash { .. } => Some("hashed"),
+            Action::HashDate { .. } => Some("date hashed"),
+            Action::DateShift { .. } => Some("date shifted"),
+            Action::HashUID => Some("replaced with hashed UID"),
+            Action::Pseudonymize => Some("encrypted (reversible)"),
+            Action::Keep | Action::None | Action::Remove => None,
+            Action::Replace { .. } => Some("replaced"),
+            Action::BlankPixelRegions { .. } => Some("pixel regions blanked"),
+            Action::BlankAllPixels => Some("pixel data blanked"),
+            Action::CleanText { .. } => Some("text scrubbed"),
+            Action::OnlyIfNonEmpty { action } => action.transform_description(),
+            Action::OnlyIfMatches { action, .. } => action.transform_description(),
+        }
+    }
+}
+
+/// One entry in the structured audit trail an anonymization run can
+/// optionally produce (see [`crate::processor::DefaultProcessor::process_object_with_report`]):
+/// which tag was affected, what action applied, and whether the value was
+/// kept (possibly transformed) or removed outright. Deliberately carries no
+/// original or transformed *value* - only metadata about what happened - so
+/// the report itself is safe to hand to a compliance reviewer who shouldn't
+/// see PHI. `original_length` is the one exception that's still safe: the
+/// byte length of the value before processing, useful for spotting a
+/// suspiciously short or long value without ever reading it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct AuditRecord {
+    pub tag: TagString,
+    pub vr: String,
+    pub action: String,
+    pub kept: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub transform: Option<String>,
+    pub original_length: u32,
+    /// Set when the configured action couldn't run as configured (e.g.
+    /// [`crate::actions::errors::ActionError::InvalidHashDateTag`]) and was
+    /// downgraded to leaving the value untouched, rather than failing the
+    /// whole pass - the same condition [`log::warn!`] reports at the time,
+    /// kept here too so it shows up in the audit trail itself.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub downgraded_warning: Option<String>,
+}
+
+impl AuditRecord {
+    pub(crate) fn kept(
+        tag: Tag,
+        vr: dicom_core::VR,
+        action: &Action,
+        original_length: u32,
+        downgraded_warning: Option<String>,
+    ) -> Self {
+        Self {
+            tag: TagString(tag),
+            vr: vr.to_string(),
+            action: action.label().to_string(),
+            kept: true,
+            transform: action.transform_description().map(str::to_string),
+            original_length,
+            downgraded_warning,
+        }
+    }
+
+    pub(crate) fn removed(
+        tag: Tag,
+        vr: dicom_core::VR,
+        action: &Action,
+        original_length: u32,
+    ) -> Self {
+        Self {
+            tag: TagString(tag),
+            vr: vr.to_string(),
+            action: action.label().to_string(),
+            kept: false,
+            transform: None,
+            original_length,
+            downgraded_warning: None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::{Action, AuditRecord};
+    use crate::tags;
+    use serde_json;
+
+    #[test]
+    fn test_serialize_empty() {
+        let action = Action::Empty;
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"empty"}"#);
+    }
+
+    #[test]
+    fn test_serialize_hash() {
+        let action = Action::Hash { length: Some(10) };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"hash","length":10}"#);
+    }
+
+    #[test]
+    fn test_serialize_hash_date() {
+        let action = Action::HashDate {
+            other_tag: tags::PATIENT_ID,
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"hashdate","other_tag":"(0010,0020)"}"#);
+    }
+
+    #[test]
+    fn test_serialize_date_shift() {
+        let action = Action::DateShift {
+            subject_tag: tags::PATIENT_ID,
+            max_offset_days: 365,
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"dateshift","subject_tag":"(0010,0020)","max_offset_days":365}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_hash_uid() {
+        let action = Action::HashUID;
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"hashuid"}"#);
+    }
+
+    #[test]
+    fn test_serialize_keep() {
+        let action = Action::Keep;
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"keep"}"#);
+    }
+
+    #[test]
+    fn test_serialize_none() {
+        let action = Action::None;
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"none"}"#);
+    }
+
+    #[test]
+    fn test_serialize_remove() {
+        let action = Action::Remove;
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"remove"}"#);
+    }
+
+    #[test]
+    fn test_serialize_replace() {
+        let action = Action::Replace {
+            value: "ANONYMIZED".to_string(),
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"replace","value":"ANONYMIZED"}"#);
+    }
+
+    #[test]
+    fn test_serialize_blank_pixel_regions() {
+        let action = Action::BlankPixelRegions {
+            regions: vec![crate::actions::Rect::Absolute {
+                x: 0,
+                y: 0,
+                width: 10,
+                height: 10,
+            }],
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"blankpixelregions","regions":[{"absolute":{"x":0,"y":0,"width":10,"height":10}}]}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_blank_all_pixels() {
+        let action = Action::BlankAllPixels;
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"action":"blankallpixels"}"#);
+    }
+
+    #[test]
+    fn test_serialize_clean_text() {
+        let action = Action::CleanText {
+            rules: vec![crate::actions::CleanTextRule::new("mrn", r"\d{6,}").unwrap()],
+            replacement: "[REDACTED]".to_string(),
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"clean_text","rules":[{"name":"mrn","pattern":"\\d{6,}","literal_tags":[]}],"replacement":"[REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_empty() {
+        let json = r#"{"action":"empty"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::Empty);
+    }
+
+    #[test]
+    fn test_deserialize_hash() {
+        let json = r#"{"action":"hash","length":null}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::Hash { length: None });
+    }
+
+    #[test]
+    fn test_deserialize_hash_date() {
+        let json = r#"{"action":"hashdate","other_tag":"(0010,0020)"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::HashDate {
+                other_tag: tags::PATIENT_ID
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_date_shift() {
+        let json = r#"{"action":"dateshift","subject_tag":"(0010,0020)","max_offset_days":365}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::DateShift {
+                subject_tag: tags::PATIENT_ID,
+                max_offset_days: 365
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_date_shift_accepts_shiftdates_alias() {
+        let json = r#"{"action":"shiftdates","subject_tag":"(0010,0020)","max_offset_days":365}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::DateShift {
+                subject_tag: tags::PATIENT_ID,
+                max_offset_days: 365
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_date_shift_accepts_shift_date_action_and_max_days_field_aliases() {
+        let json = r#"{"action":"shift_date","subject_tag":"(0010,0020)","max_days":365}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::DateShift {
+                subject_tag: tags::PATIENT_ID,
+                max_offset_days: 365
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_hash_uid() {
+        let json = r#"{"action":"hashuid"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::HashUID);
+    }
+
+    #[test]
+    fn test_deserialize_keep() {
+        let json = r#"{"action":"keep"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::Keep);
+    }
+
+    #[test]
+    fn test_deserialize_none() {
+        let json = r#"{"action":"none"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::None);
+    }
+
+    #[test]
+    fn test_deserialize_remove() {
+        let json = r#"{"action":"remove"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::Remove);
+    }
+
+    #[test]
+    fn test_deserialize_replace() {
+        let json = r#"{"action":"replace","value":"ANONYMIZED"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::Replace {
+                value: "ANONYMIZED".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_blank_pixel_regions() {
+        let json = r#"{"action":"blankpixelregions","regions":[{"absolute":{"x":0,"y":0,"width":10,"height":10}}]}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::BlankPixelRegions {
+                regions: vec![crate::actions::Rect::Absolute {
+                    x: 0,
+                    y: 0,
+                    width: 10,
+                    height: 10,
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_blank_all_pixels() {
+        let json = r#"{"action":"blankallpixels"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::BlankAllPixels);
+    }
+
+    #[test]
+    fn test_deserialize_clean_text() {
+        let json = r#"{"action":"clean_text","rules":[{"name":"mrn","pattern":"\\d{6,}"}],"replacement":"[REDACTED]"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::CleanText {
+                rules: vec![crate::actions::CleanTextRule::new("mrn", r"\d{6,}").unwrap()],
+                replacement: "[REDACTED]".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_clean_text_rejects_malformed_pattern() {
+        let json = r#"{"action":"clean_text","rules":[{"name":"bad","pattern":"(unclosed"}],"replacement":"[REDACTED]"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_only_if_non_empty() {
+        let json = r#"{"action":"onlyifnonempty","action":{"action":"empty"}}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            action,
+            Action::OnlyIfNonEmpty {
+                action: Box::new(Action::Empty)
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_only_if_matches() {
+        let json = r#"{"action":"onlyifmatches","action":{"action":"empty"},"pattern":"^\\d+$"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        match action {
+            Action::OnlyIfMatches { action, pattern } => {
+                assert_eq!(*action, Action::Empty);
+                assert_eq!(pattern.regex().as_str(), "^\\d+$");
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_only_if_matches_rejects_malformed_pattern() {
+        let json =
+            r#"{"action":"onlyifmatches","action":{"action":"empty"},"pattern":"(unclosed"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_case_handling_on_deserialization() {
+        // This test passes - lowercase is expected
+        let json = r#"{"action":"empty"}"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action, Action::Empty);
+
+        // Uppercase will fail without aliases
+        let json = r#"{"action":"EMPTY"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+
+        // Same for mixed case
+        let json = r#"{"action":"Hash"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_all_variants() {
+        // Test all variants in one go
+        let variants = vec![
+            Action::Empty,
+            Action::Hash { length: None },
+            Action::HashDate {
+                other_tag: tags::PATIENT_ID,
+            },
+            Action::DateShift {
+                subject_tag: tags::PATIENT_ID,
+                max_offset_days: 365,
+            },
+            Action::HashUID,
+            Action::Keep,
+            Action::None,
+            Action::Remove,
+            Action::Replace {
+                value: "TEST".to_string(),
+            },
+            Action::BlankPixelRegions {
+                regions: vec![crate::actions::Rect::Absolute {
+                    x: 0,
+                    y: 0,
+                    width: 10,
+                    height: 10,
+                }],
+            },
+            Action::BlankAllPixels,
+            Action::CleanText {
+                rules: vec![crate::actions::CleanTextRule::new("mrn", r"\d{6,}").unwrap()],
+                replacement: "[REDACTED]".to_string(),
+            },
+        ];
+
+        for variant in variants {
+            let json = serde_json::to_string(&variant).unwrap();
+            let deserialized: Action = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                variant, deserialized,
+                "Roundtrip failed for variant: {:?}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_handling_missing_action() {
+        let json = r#"{"with":"ANONYMIZED"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_error_handling_invalid_action() {
+        let json = r#"{"action":"invalidaction"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_error_handling_missing_replace_with() {
+        let json = r#"{"action":"replace"}"#;
+        let result: Result<Action, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let action = Action::Replace {
+            value: "ANONYMIZED".to_string(),
+        };
+        let json = serde_json::to_string_pretty(&action).unwrap();
+        let expected = r#"{
+  "action": "replace",
+  "value": "ANONYMIZED"
+}"#;
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn test_label_matches_the_names_an_audit_trail_should_report() {
+        assert_eq!(Action::None.label(), "NoAction");
+        assert_eq!(Action::Remove.label(), "Remove");
+        assert_eq!(
+            Action::HashDate {
+                other_tag: tags::PATIENT_ID
+            }
+            .label(),
+            "HashDate"
+        );
+    }
+
+    #[test]
+    fn test_audit_record_for_a_kept_element_serializes_without_leaking_values() {
+        let record = AuditRecord::kept(
+            tags::PATIENT_ID,
+            dicom_core::VR::LO,
+            &Action::Hash { length: None },
+            5,
+            None,
+        );
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tag":"(0010,0020)","vr":"LO","action":"Hash","kept":true,"transform":"hashed","original_length":5}"#
+        );
+    }
+
+    #[test]
+    fn test_audit_record_for_a_removed_element_omits_transform() {
+        let record =
+            AuditRecord::removed(tags::PATIENT_NAME, dicom_core::VR::PN, &Action::Remove, 8);
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tag":"(0010,0010)","vr":"PN","action":"Remove","kept":false,"original_length":8}"#
+        );
+    }
+
+    #[test]
+    fn test_audit_record_reports_a_downgraded_warning() {
+        let record = AuditRecord::kept(
+            tags::STUDY_DATE,
+            dicom_core::VR::DA,
+            &Action::HashDate {
+                other_tag: tags::PATIENT_ID,
+            },
+            8,
+            Some("other tag not found".to_string()),
+        );
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tag":"(0008,0020)","vr":"DA","action":"HashDate","kept":true,"transform":"date hashed","original_length":8,"downgraded_warning":"other tag not found"}"#
+        );
+    }
+}
@@ -1,19 +1,15 @@
-use dicom_object::DefaultDicomObject;
 use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
 use std::borrow::Cow;
 
-use crate::actions::ProcessElement;
 use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
 use crate::config::Config;
 
-/// Action that performs no operation on DICOM elements.
-///
-/// This action is functionally equivalent to `Keep` but semantically represents
-/// an explicit decision to perform no anonymization action on an element.
 #[derive(Debug, Clone, PartialEq)]
 pub struct NoAction;
 
-impl ProcessElement for NoAction {
+impl DataElementAction for NoAction {
     fn process<'a>(
         &'a self,
         _config: &Config,
@@ -28,13 +24,13 @@ impl ProcessElement for NoAction {
 mod tests {
     use super::*;
 
-    use dicom_core::VR;
     use dicom_core::value::Value;
-    use dicom_object::FileDicomObject;
+    use dicom_core::VR;
+    use dicom_dictionary_std::tags;
     use dicom_object::mem::InMemElement;
+    use dicom_object::FileDicomObject;
 
     use crate::config::Config;
-    use crate::tags;
     use crate::test_utils::make_file_meta;
 
     #[test]
@@ -0,0 +1,209 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use dicom_core::value::Value;
+use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use rand::RngCore;
+use std::borrow::Cow;
+use thiserror::Error;
+
+use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
+use crate::config::Config;
+
+/// Error returned by [`decrypt`].
+///
+/// [`ActionError`], the error type [`DataElementAction::process`] returns, is
+/// crate-private - it can't appear in a public function's signature without
+/// external callers being unable to name or match on it. `pseudonymize` is a
+/// `pub mod`, and [`decrypt`] is a plain function rather than a trait method,
+/// so it gets its own public error type instead.
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum DecryptError {
+    #[error("invalid base64: {0}")]
+    InvalidBase64(String),
+
+    #[error("encrypted value is too short to contain a valid nonce")]
+    CiphertextTooShort,
+
+    #[error("decrypted value is not valid UTF-8: {0}")]
+    InvalidUtf8(String),
+}
+
+/// Length in bytes of the random nonce prefixed to every ciphertext [`encrypt`] produces.
+const NONCE_LEN: usize = 16;
+
+/// Action implementing [`crate::actions::Action::Pseudonymize`]: replaces a
+/// value with a keyed, reversible encryption of itself, base64-encoded so the
+/// result stays valid for string-VR elements.
+///
+/// Unlike [`crate::actions::hash::Hash`]/`HashUID`, this is reversible: an
+/// authorized holder of [`Config::get_pseudonymization_key`] can recover the
+/// original value with [`decrypt`], supporting legitimate re-identification
+/// of study subjects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pseudonymize;
+
+impl DataElementAction for Pseudonymize {
+    fn process<'a>(
+        &'a self,
+        config: &Config,
+        _obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
+        let value = match elem.value().to_str() {
+            Ok(value) => value,
+            Err(_) => return Ok(Some(Cow::Borrowed(elem))),
+        };
+
+        let encrypted = encrypt(config.get_pseudonymization_key(), &value);
+        let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(encrypted));
+        Ok(Some(Cow::Owned(new_elem)))
+    }
+}
+
+/// Derives a keystream of `len` bytes from `key` and `nonce`, used to
+/// XOR-encrypt and decrypt values symmetrically.
+///
+/// `key` and `nonce` are concatenated and hashed down to BLAKE3's 32-byte key
+/// size, then used to seed a keyed hasher whose extendable output (XOF) mode
+/// produces as much pseudorandom, key-dependent output as needed - a standard
+/// construction for turning a hash function into a stream cipher without
+/// pulling in a dedicated crypto crate. Mixing `nonce` into the derived key
+/// (rather than just `key` and `len`, as an earlier version of this function
+/// did) means the same `value` under the same `key` encrypts to a different
+/// keystream - and so a different ciphertext - every time, instead of a
+/// stream cipher with a fixed, value-length-keyed pad an attacker who sees
+/// two ciphertexts of the same value could otherwise exploit.
+fn keystream(key: &str, nonce: &[u8; NONCE_LEN], len: usize) -> Vec<u8> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(key.as_bytes());
+    hasher.update(nonce);
+    let derived_key = *hasher.finalize().as_bytes();
+
+    let mut output = vec![0u8; len];
+    blake3::Hasher::new_keyed(&derived_key)
+        .finalize_xof()
+        .fill(&mut output);
+    output
+}
+
+/// Encrypts `value` with `key`, returning the base64-encoded ciphertext with
+/// a random [`NONCE_LEN`]-byte nonce prefixed to it.
+///
+/// Encryption is randomized: the nonce is freshly generated on every call, so
+/// the same `value` under the same `key` produces a different result each
+/// time, the same way `bindings/python/src/reident.rs`'s AES-GCM keyfile
+/// encryption is randomized per save. [`decrypt`] reads the nonce back off
+/// the front of the ciphertext it's given.
+pub fn encrypt(key: &str, value: &str) -> String {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let plaintext = value.as_bytes();
+    let ciphertext: Vec<u8> = plaintext
+        .iter()
+        .zip(keystream(key, &nonce, plaintext.len()))
+        .map(|(byte, pad)| byte ^ pad)
+        .collect();
+
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    STANDARD.encode(out)
+}
+
+/// Reverses [`encrypt`], recovering the original value from its
+/// base64-encoded, nonce-prefixed ciphertext under the same `key`.
+///
+/// Returns [`DecryptError::InvalidBase64`] if `encoded` isn't valid base64,
+/// [`DecryptError::CiphertextTooShort`] if it's shorter than a nonce, or
+/// [`DecryptError::InvalidUtf8`] if the decrypted bytes aren't valid UTF-8
+/// (which, short of a wrong `key`, means `encoded` didn't originate from
+/// [`encrypt`]).
+pub fn decrypt(key: &str, encoded: &str) -> Result<String, DecryptError> {
+    let decoded = STANDARD
+        .decode(encoded)
+        .map_err(|err| DecryptError::InvalidBase64(err.to_string()))?;
+
+    if decoded.len() < NONCE_LEN {
+        return Err(DecryptError::CiphertextTooShort);
+    }
+    let (nonce, ciphertext) = decoded.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().expect("split at NONCE_LEN");
+
+    let plaintext: Vec<u8> = ciphertext
+        .iter()
+        .zip(keystream(key, &nonce, ciphertext.len()))
+        .map(|(byte, pad)| byte ^ pad)
+        .collect();
+
+    String::from_utf8(plaintext)
+        .map_err(|err| DecryptError::InvalidUtf8(err.utf8_error().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+    use dicom_object::FileDicomObject;
+
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+    use dicom_dictionary_std::tags;
+
+    #[test]
+    fn test_encrypt_is_randomized_per_call() {
+        assert_ne!(encrypt("secret", "203087"), encrypt("secret", "203087"));
+    }
+
+    #[test]
+    fn test_encrypt_differs_by_key() {
+        assert_ne!(encrypt("secret-a", "203087"), encrypt("secret-b", "203087"));
+    }
+
+    #[test]
+    fn test_decrypt_reverses_encrypt() {
+        let encrypted = encrypt("secret", "John Doe");
+        assert_eq!(decrypt("secret", &encrypted).unwrap(), "John Doe");
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key_does_not_recover_value() {
+        let encrypted = encrypt("secret-a", "John Doe");
+        let decrypted = decrypt("secret-b", &encrypted);
+        assert_ne!(decrypted.ok(), Some("John Doe".to_string()));
+    }
+
+    #[test]
+    fn test_decrypt_rejects_invalid_base64() {
+        let err = decrypt("secret", "not-valid-base64!!").unwrap_err();
+        assert!(matches!(err, DecryptError::InvalidBase64(_)));
+    }
+
+    #[test]
+    fn test_decrypt_rejects_ciphertext_too_short_for_a_nonce() {
+        let encoded = STANDARD.encode([0u8; NONCE_LEN - 1]);
+        let err = decrypt("secret", &encoded).unwrap_err();
+        assert_eq!(err, DecryptError::CiphertextTooShort);
+    }
+
+    #[test]
+    fn test_process_replaces_value_with_reversible_ciphertext() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new()
+            .pseudonymization_key("institution-secret")
+            .build();
+
+        let action = Pseudonymize;
+        let elem = InMemElement::new(tags::PATIENT_ID, VR::LO, Value::from("203087"));
+
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        let encoded = processed.value().to_str().unwrap();
+        assert_ne!(encoded.as_ref(), "203087");
+
+        let recovered = decrypt("institution-secret", &encoded).unwrap();
+        assert_eq!(recovered, "203087");
+    }
+}
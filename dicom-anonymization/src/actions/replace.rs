@@ -1,38 +1,117 @@
-use dicom_core::header::Header;
 use dicom_core::value::Value;
-use dicom_object::DefaultDicomObject;
+use dicom_core::{Tag, VR};
 use dicom_object::mem::InMemElement;
+use dicom_object::DefaultDicomObject;
+use regex::Regex;
 use std::borrow::Cow;
+use std::sync::OnceLock;
 
-use crate::actions::ProcessElement;
 use crate::actions::errors::ActionError;
+use crate::actions::DataElementAction;
 use crate::config::Config;
 
-/// Action that replaces DICOM element values with a fixed replacement value.
+static TEMPLATE_TOKEN_REGEX: OnceLock<Regex> = OnceLock::new();
+
+fn template_token_regex() -> &'static Regex {
+    TEMPLATE_TOKEN_REGEX.get_or_init(|| {
+        Regex::new(r"\{\{\s*(?:(hash|upper)\s+)?\(([0-9A-Fa-f]{4}),\s*([0-9A-Fa-f]{4})\)\s*\}\}")
+            .unwrap()
+    })
+}
+
+/// Replaces a data element's value, either with a constant string or with a
+/// mustache-style template that interpolates other elements of the dataset.
+///
+/// A `value` containing no `{{` is used verbatim, exactly as a plain
+/// `Action::Replace` always has. Otherwise, every `{{(gggg,eeee)}}` token is
+/// replaced with that tag's string value in the dataset being processed (an
+/// empty string if the tag isn't present), which lets a config build a
+/// synthetic identifier out of existing fields - e.g.
+/// `"{{(0010,0020)}}-{{(0008,0060)}}"` - instead of a single fixed constant.
 ///
-/// This action substitutes the original element value with a predetermined
-/// replacement string, useful for standardizing values or replacing sensitive
-/// data with placeholder text.
+/// A token may name one leading helper before the tag: `{{hash (0010,0020)}}`
+/// runs the referenced value through the configured hasher (see
+/// [`Config::get_hash_fn_for`]) before substituting it, and `{{upper ...}}`
+/// upper-cases it.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Replace {
-    new_value: String,
+    template: String,
 }
 
 impl Replace {
-    pub fn new(new_value: String) -> Self {
-        Self { new_value }
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Renders `self.template` against `config`/`obj`, resolving every
+    /// `{{...}}` token. Returns `ActionError::InvalidInput` if the template
+    /// contains a `{{` that [`template_token_regex`] can't parse as a valid
+    /// token.
+    fn render(&self, config: &Config, obj: &DefaultDicomObject) -> Result<String, ActionError> {
+        if !self.template.contains("{{") {
+            return Ok(self.template.clone());
+        }
+
+        if !has_only_valid_tokens(&self.template) {
+            return Err(ActionError::InvalidInput(format!(
+                "malformed replacement template: {}",
+                self.template
+            )));
+        }
+
+        let regex = template_token_regex();
+        let mut rendered = String::with_capacity(self.template.len());
+        let mut last_end = 0;
+
+        for capture in regex.captures_iter(&self.template) {
+            let whole_match = capture.get(0).unwrap();
+            rendered.push_str(&self.template[last_end..whole_match.start()]);
+            last_end = whole_match.end();
+
+            let helper = capture.get(1).map(|m| m.as_str());
+            let group = u16::from_str_radix(&capture[2], 16).unwrap();
+            let element = u16::from_str_radix(&capture[3], 16).unwrap();
+            let tag = Tag(group, element);
+
+            let value = obj
+                .element(tag)
+                .ok()
+                .and_then(|elem| elem.value().to_str().ok())
+                .map(|value| value.into_owned())
+                .unwrap_or_default();
+
+            let value = match helper {
+                Some("hash") => config.get_hash_fn_for(&tag).call(&value),
+                Some("upper") => value.to_uppercase(),
+                _ => value,
+            };
+
+            rendered.push_str(&value);
+        }
+
+        rendered.push_str(&self.template[last_end..]);
+        Ok(rendered)
     }
 }
 
-impl ProcessElement for Replace {
+/// Returns whether every `{{...}}` occurrence in `template` is matched by
+/// [`template_token_regex`], i.e. the template has no unparseable token.
+fn has_only_valid_tokens(template: &str) -> bool {
+    let brace_count = template.matches("{{").count();
+    template_token_regex().find_iter(template).count() == brace_count
+}
+
+impl DataElementAction for Replace {
     fn process<'a>(
         &'a self,
-        _config: &Config,
-        _obj: &DefaultDicomObject,
+        config: &Config,
+        obj: &DefaultDicomObject,
         elem: &'a InMemElement,
     ) -> Result<Option<Cow<'a, InMemElement>>, ActionError> {
-        let new_elem =
-            InMemElement::new(elem.tag(), elem.vr(), Value::from(self.new_value.clone()));
+        let rendered = self.render(config, obj)?;
+        let new_elem = InMemElement::new(elem.tag(), elem.vr(), Value::from(rendered));
         Ok(Some(Cow::Owned(new_elem)))
     }
 }
@@ -41,29 +120,103 @@ impl ProcessElement for Replace {
 mod tests {
     use super::*;
 
-    use dicom_core::VR;
-    use dicom_core::value::Value;
     use dicom_object::FileDicomObject;
 
-    use crate::tags;
+    use crate::config::ConfigBuilder;
     use crate::test_utils::make_file_meta;
+    use dicom_dictionary_std::tags;
 
-    #[test]
-    fn test_process() {
+    fn obj_with_patient_id(patient_id: &str) -> DefaultDicomObject {
         let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
-        let elem = InMemElement::new(
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from(patient_id),
+        ));
+        obj
+    }
+
+    #[test]
+    fn test_plain_value_is_used_verbatim() {
+        let obj = obj_with_patient_id("203087");
+        let config = ConfigBuilder::new().build();
+
+        let action = Replace::new("ANONYMIZED");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("ANONYMIZED"));
+    }
+
+    #[test]
+    fn test_interpolates_referenced_tag() {
+        let obj = obj_with_patient_id("203087");
+        let config = ConfigBuilder::new().build();
+
+        let action = Replace::new("id-{{(0010,0020)}}");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("id-203087"));
+    }
+
+    #[test]
+    fn test_missing_referenced_tag_resolves_to_empty_string() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let config = ConfigBuilder::new().build();
+
+        let action = Replace::new("id-{{(0010,0020)}}");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("id-"));
+    }
+
+    #[test]
+    fn test_upper_helper() {
+        let obj = obj_with_patient_id("patient-a");
+        let config = ConfigBuilder::new().build();
+
+        let action = Replace::new("{{upper (0010,0020)}}");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("PATIENT-A"));
+    }
+
+    #[test]
+    fn test_hash_helper_matches_configured_hasher() {
+        let obj = obj_with_patient_id("203087");
+        let config = ConfigBuilder::new().keyed_hash_fn("secret").build();
+
+        let action = Replace::new("{{hash (0010,0020)}}");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+
+        let expected = config.get_hash_fn_for(&tags::PATIENT_ID).call("203087");
+        assert_eq!(processed.value(), &Value::from(expected));
+    }
+
+    #[test]
+    fn test_malformed_template_is_an_error() {
+        let obj = obj_with_patient_id("203087");
+        let config = ConfigBuilder::new().build();
+
+        let action = Replace::new("id-{{not a valid token}}");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let err = action.process(&config, &obj, &elem).unwrap_err();
+        assert!(matches!(err, ActionError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_multiple_tokens() {
+        let mut obj = obj_with_patient_id("203087");
+        obj.put(InMemElement::new(
             tags::ACCESSION_NUMBER,
             VR::SH,
-            Value::from("0123456789ABCDEF"),
-        );
-        obj.put(elem.clone());
-
-        let action_struct = Replace::new("new_value_123".into());
-        let config = Config::default();
+            Value::from("ACC1"),
+        ));
+        let config = ConfigBuilder::new().build();
 
-        let processed = action_struct.process(&config, &obj, &elem).unwrap();
-        let processed = processed.unwrap();
-        let processed = processed.into_owned();
-        assert_eq!(processed.value(), &Value::from("new_value_123"));
+        let action = Replace::new("{{(0010,0020)}}-{{(0008,0050)}}");
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("John Doe"));
+        let processed = action.process(&config, &obj, &elem).unwrap().unwrap();
+        assert_eq!(processed.value(), &Value::from("203087-ACC1"));
     }
 }
@@ -0,0 +1,258 @@
+use dicom_core::{DataDictionary, Tag, VR};
+use dicom_dictionary_std::StandardDataDictionary;
+use dicom_object::DefaultDicomObject;
+
+use crate::actions::errors::ActionError;
+
+/// The Implicit VR Little Endian transfer syntax UID. Elements encoded under
+/// this transfer syntax don't carry their VR on the wire; it must instead be
+/// looked up from a tag dictionary, as [`resolve_vr`] does.
+pub(crate) const IMPLICIT_VR_LITTLE_ENDIAN_UID: &str = "1.2.840.10008.1.2";
+
+/// Looks up the VR the standard data dictionary associates with `tag`.
+///
+/// Explicit VR transfer syntaxes carry the VR on the wire, but Implicit VR
+/// Little Endian does not, so value-mutating actions (e.g. [`super::hash`],
+/// [`super::replace`]) that need to pad or validate the value they produce
+/// must resolve it from here instead of assuming it was already known.
+/// Returns `None` for tags the standard dictionary doesn't cover, such as
+/// private tags, which callers should treat as `VR::UN`.
+pub(crate) fn resolve_vr(tag: &Tag) -> Option<VR> {
+    let data_dict = StandardDataDictionary;
+    let data_entry = data_dict.by_tag(*tag);
+    match data_entry {
+        Some(entry) => Some(entry.vr),
+        _ => None,
+    }
+}
+
+/// Returns whether `obj`'s File Meta Information (0002,0010) declares the
+/// Implicit VR Little Endian transfer syntax.
+///
+/// `transfer_syntax` may carry a trailing padding byte (`\0` or space), since
+/// UI values are padded to an even length like any other string VR.
+pub(crate) fn is_implicit_vr(obj: &DefaultDicomObject) -> bool {
+    obj.meta().transfer_syntax.trim_end_matches(['\0', ' ']) == IMPLICIT_VR_LITTLE_ENDIAN_UID
+}
+
+/// Resolves the VR to use for `tag`, given the VR `elem_vr` already reports.
+///
+/// Under Implicit VR Little Endian, a parser with no dictionary of its own
+/// reports every element's VR as `VR::UN` (Unknown); this heuristic - not the
+/// transfer syntax claimed in `obj`'s File Meta, which [`is_implicit_vr`]
+/// checks directly - is what catches a `VR::UN` element even if the
+/// transfer syntax couldn't be determined. Either signal falls back to the
+/// built-in tag dictionary via [`resolve_vr`], keeping `elem_vr` only when
+/// neither signal applies or the dictionary has no entry for `tag`.
+pub(crate) fn resolve_element_vr(obj: &DefaultDicomObject, tag: &Tag, elem_vr: VR) -> VR {
+    if elem_vr == VR::UN || is_implicit_vr(obj) {
+        resolve_vr(tag).unwrap_or(elem_vr)
+    } else {
+        elem_vr
+    }
+}
+
+/// Pads `value` to an even byte length the way `vr` requires: a trailing
+/// NUL for `VR::UI`, a trailing space for every other padded string VR.
+/// Values already of even length are returned unchanged.
+pub(crate) fn pad_to_vr(value: &str, vr: VR) -> String {
+    if value.len() % 2 == 0 {
+        return value.to_string();
+    }
+
+    match vr {
+        VR::UI => format!("{value}\0"),
+        _ => format!("{value} "),
+    }
+}
+
+/// Returns the maximum value length (in bytes) PS3.5 specifies for `vr`, or
+/// `None` for VRs with no fixed maximum (e.g. `VR::UT`) or that this table
+/// doesn't cover.
+fn vr_max_length(vr: VR) -> Option<usize> {
+    match vr {
+        VR::AE => Some(16),
+        VR::AS => Some(4),
+        VR::CS => Some(16),
+        VR::DA => Some(8),
+        VR::DS => Some(16),
+        VR::IS => Some(12),
+        VR::LO => Some(64),
+        VR::SH => Some(16),
+        VR::TM => Some(14),
+        VR::UI => Some(64),
+        _ => None,
+    }
+}
+
+/// Returns whether `c` is legal in a `VR::CS` (Code String) value: uppercase
+/// letters, digits, space, and underscore.
+fn is_legal_cs_char(c: char) -> bool {
+    c.is_ascii_uppercase() || c.is_ascii_digit() || c == ' ' || c == '_'
+}
+
+/// Drops characters `vr` doesn't allow. Currently only `VR::CS` is enforced,
+/// since it's the only VR in [`vr_max_length`] with a restricted charset;
+/// every other covered VR already produces a legal charset from hashing or is
+/// taken verbatim from a user-supplied replacement value.
+fn strip_illegal_chars(value: &str, vr: VR) -> String {
+    match vr {
+        VR::CS => value.chars().filter(|c| is_legal_cs_char(*c)).collect(),
+        _ => value.to_string(),
+    }
+}
+
+/// Normalizes `value` so it conforms to `vr`'s PS3.5 constraints: strips
+/// characters illegal for `vr`, truncates to `vr`'s maximum length, then pads
+/// to an even length via [`pad_to_vr`].
+///
+/// Returns the normalized value together with whether it had to be
+/// truncated, so callers (e.g. [`super::hash`], [`super::replace`]) can
+/// surface a warning when a generated value didn't fit.
+pub(crate) fn normalize_for_vr(value: &str, vr: VR) -> (String, bool) {
+    let sanitized = strip_illegal_chars(value, vr);
+
+    let (truncated_value, was_truncated) = match vr_max_length(vr) {
+        Some(max) if sanitized.len() > max => (sanitized[..max].to_string(), true),
+        _ => (sanitized, false),
+    };
+
+    (pad_to_vr(&truncated_value, vr), was_truncated)
+}
+
+/// Checks that a `Hash { length }` request of `length` bytes fits within
+/// `vr`'s maximum value length.
+///
+/// Returns [`ActionError::ValueExceedsVrLength`] if it doesn't, so the caller
+/// can surface a structured error instead of silently emitting a value the
+/// VR doesn't allow.
+pub(crate) fn check_hash_length_fits_vr(length: usize, vr: VR) -> Result<(), ActionError> {
+    match vr_max_length(vr) {
+        Some(max) if length > max => Err(ActionError::ValueExceedsVrLength(format!(
+            "requested hash length {length} exceeds the maximum {max} bytes allowed for VR {vr:?}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dicom_dictionary_std::tags;
+    use dicom_object::FileDicomObject;
+
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_resolve_vr_known_tag() {
+        assert_eq!(resolve_vr(&tags::PATIENT_NAME), Some(VR::PN));
+    }
+
+    #[test]
+    fn test_resolve_vr_unknown_tag_is_none() {
+        // odd group number: private tag block, not in the standard dictionary
+        assert_eq!(resolve_vr(&Tag(0x0009, 0x0010)), None);
+    }
+
+    #[test]
+    fn test_pad_to_vr_leaves_even_length_unchanged() {
+        assert_eq!(pad_to_vr("ABCD", VR::LO), "ABCD");
+    }
+
+    #[test]
+    fn test_pad_to_vr_pads_odd_length_with_space() {
+        assert_eq!(pad_to_vr("ABC", VR::LO), "ABC ");
+    }
+
+    #[test]
+    fn test_pad_to_vr_pads_ui_with_null() {
+        assert_eq!(pad_to_vr("1.2.3", VR::UI), "1.2.3\0");
+    }
+
+    #[test]
+    fn test_strip_illegal_chars_filters_cs() {
+        assert_eq!(strip_illegal_chars("ok_VALUE 1!", VR::CS), "ok_VALUE 1");
+    }
+
+    #[test]
+    fn test_strip_illegal_chars_leaves_other_vrs_unchanged() {
+        assert_eq!(strip_illegal_chars("Doe^John", VR::PN), "Doe^John");
+    }
+
+    #[test]
+    fn test_normalize_for_vr_truncates_to_max_length() {
+        let (value, truncated) = normalize_for_vr(&"A".repeat(20), VR::CS);
+        assert_eq!(value, "A".repeat(16));
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_normalize_for_vr_pads_after_truncation() {
+        // DA's max length (8) is even, but a value already at the max with an
+        // odd remainder after stripping should still come out padded.
+        let (value, truncated) = normalize_for_vr("2020010", VR::DA);
+        assert_eq!(value, "2020010 ");
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_normalize_for_vr_strips_then_truncates() {
+        let (value, truncated) = normalize_for_vr("AAAA!!!!BBBBBBBBBBBBBBBB", VR::CS);
+        assert_eq!(value, "AAAABBBBBBBBBBBB");
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_normalize_for_vr_untouched_when_within_limits() {
+        let (value, truncated) = normalize_for_vr("ABC", VR::LO);
+        assert_eq!(value, "ABC ");
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_check_hash_length_fits_vr_ok_within_limit() {
+        assert!(check_hash_length_fits_vr(16, VR::SH).is_ok());
+    }
+
+    #[test]
+    fn test_check_hash_length_fits_vr_errors_when_over_limit() {
+        let err = check_hash_length_fits_vr(17, VR::SH).unwrap_err();
+        assert!(matches!(err, ActionError::ValueExceedsVrLength(_)));
+    }
+
+    #[test]
+    fn test_check_hash_length_fits_vr_ok_for_unbounded_vr() {
+        assert!(check_hash_length_fits_vr(10_000, VR::UT).is_ok());
+    }
+
+    #[test]
+    fn test_is_implicit_vr_false_for_explicit_syntax() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        assert!(!is_implicit_vr(&obj));
+    }
+
+    #[test]
+    fn test_resolve_element_vr_keeps_known_elem_vr() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        assert_eq!(
+            resolve_element_vr(&obj, &tags::PATIENT_NAME, VR::PN),
+            VR::PN
+        );
+    }
+
+    #[test]
+    fn test_resolve_element_vr_falls_back_to_dictionary_when_unknown() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        assert_eq!(
+            resolve_element_vr(&obj, &tags::PATIENT_NAME, VR::UN),
+            VR::PN
+        );
+    }
+
+    #[test]
+    fn test_resolve_element_vr_keeps_un_when_dictionary_has_no_entry() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        let private_tag = Tag(0x0009, 0x0010);
+        assert_eq!(resolve_element_vr(&obj, &private_tag, VR::UN), VR::UN);
+    }
+}
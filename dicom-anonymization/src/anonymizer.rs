@@ -0,0 +1,55 @@
+use dicom_object::DefaultDicomObject;
+use thiserror::Error;
+
+use crate::processor::{DefaultProcessor, Processor};
+
+/// Thin, stateless facade over a configured [`DefaultProcessor`] for a caller
+/// that just wants to anonymize one DICOM stream at a time, without dealing
+/// with [`dicom_object::from_reader`] or [`DefaultProcessor::process_object`]
+/// directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anonymizer {
+    processor: DefaultProcessor,
+}
+
+impl Anonymizer {
+    pub fn new(processor: DefaultProcessor) -> Self {
+        Self { processor }
+    }
+
+    /// Reads a DICOM data set from `reader` and returns it anonymized
+    /// according to this `Anonymizer`'s configured [`DefaultProcessor`].
+    pub fn anonymize<R: std::io::Read>(
+        &self,
+        reader: R,
+    ) -> Result<AnonymizationResult, AnonymizationError> {
+        let original = dicom_object::from_reader(reader)
+            .map_err(|e| AnonymizationError::ReadError(e.to_string()))?;
+
+        let anonymized = self
+            .processor
+            .process_object(&original)
+            .map_err(|e| AnonymizationError::ProcessingError(e.to_string()))?;
+
+        Ok(AnonymizationResult { anonymized })
+    }
+}
+
+/// The result of [`Anonymizer::anonymize`].
+#[derive(Debug, Clone)]
+pub struct AnonymizationResult {
+    pub anonymized: DefaultDicomObject,
+}
+
+/// Everything that can go wrong in [`Anonymizer::anonymize`].
+#[derive(Error, Debug)]
+pub enum AnonymizationError {
+    /// `reader` didn't hold a well-formed DICOM data set.
+    #[error("failed to read DICOM data: {0}")]
+    ReadError(String),
+
+    /// The data set was read fine, but a configured action failed while
+    /// applying it (see [`crate::actions::errors::ActionError`]).
+    #[error("failed to anonymize DICOM data: {0}")]
+    ProcessingError(String),
+}
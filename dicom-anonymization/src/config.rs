@@ -1,13 +1,23 @@
 use crate::actions::Action;
 use crate::actions::Action::HashUID;
-use crate::hasher::{blake3_hash_fn, HashFn};
-use dicom_core::{DataDictionary, Tag};
+use crate::config_format;
+pub use crate::config_format::{ConfigFormat, ConfigFormatError};
+use crate::hasher::{blake3_hash_fn, sha256_hash_fn, sha512_hash_fn, HashFn, KeyedDigest};
+use crate::private_creator::{PrivateCreatorPolicy, PrivateCreatorRule};
+use crate::transfer_syntax::TransferSyntax;
+use crate::uid_mapper::UidMapper;
+use dicom_core::dictionary::TagRange;
+use dicom_core::{DataDictionary, Tag, VR};
 use dicom_dictionary_std::{tags, StandardDataDictionary};
 use garde::Validate;
 use regex::Regex;
+#[cfg(feature = "serde")]
 use serde::ser::SerializeMap;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
 use std::str::FromStr;
 use std::sync::OnceLock;
 use thiserror::Error;
@@ -16,7 +26,7 @@ static UID_ROOT_REGEX: OnceLock<Regex> = OnceLock::new();
 
 const UID_ROOT_MAX_LENGTH: usize = 32;
 const UID_ROOT_DEFAULT_VALUE: &str = "9999";
-const DEIDENTIFIER: &str = "CARECODERS";
+pub(crate) const DEIDENTIFIER: &str = "CARECODERS";
 
 /// The [`UidRoot`] struct represents a DICOM UID root that can be used as prefix for
 /// generating new UIDs during de-identification.
@@ -39,13 +49,20 @@ const DEIDENTIFIER: &str = "CARECODERS";
 /// let invalid = "0.1.2".parse::<UidRoot>();
 /// assert!(invalid.is_err());
 /// ```
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UidRoot(String);
 
 #[derive(Error, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 #[error("{0} is not a valid UID root")]
 pub struct UidRootError(String);
 
+#[derive(Error, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[error(
+    "{0} is not a valid tag pattern; expected a DICOM tag address with 'x' wildcard nibbles, e.g. \"(50xx,xxxx)\""
+)]
+pub struct TagPatternError(String);
+
 #[derive(Error, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum ConfigError {
     #[error("invalid UID root: {0}")]
@@ -53,6 +70,21 @@ pub enum ConfigError {
 
     #[error("invalid hash length: {0}")]
     InvalidHashLength(String),
+
+    #[error("unknown config profile: {0}")]
+    UnknownProfile(String),
+
+    #[error("unknown config environment: {0}")]
+    UnknownEnvironment(String),
+
+    #[error("unknown tag keyword: {0}")]
+    UnknownTagKeyword(String),
+
+    #[error("invalid tag pattern: {0}")]
+    InvalidTagPattern(String),
+
+    #[error("merged config failed validation: {}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+    InvalidMergedConfig(Vec<ConfigValidationError>),
 }
 
 impl From<UidRootError> for ConfigError {
@@ -61,6 +93,22 @@ impl From<UidRootError> for ConfigError {
     }
 }
 
+impl From<TagPatternError> for ConfigError {
+    fn from(err: TagPatternError) -> Self {
+        ConfigError::InvalidTagPattern(err.0)
+    }
+}
+
+/// A single problem found by [`Config::validate`], naming the offending
+/// field (`"uid_root"`, `"tag_actions.(gggg,eeee)"`, ...) and the reason it's
+/// invalid.
+#[derive(Error, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[error("{path}: {reason}")]
+pub struct ConfigValidationError {
+    pub path: String,
+    pub reason: String,
+}
+
 impl UidRoot {
     pub fn new(uid_root: &str) -> Result<Self, UidRootError> {
         let regex = UID_ROOT_REGEX.get_or_init(|| {
@@ -119,28 +167,192 @@ impl AsRef<str> for UidRoot {
     }
 }
 
+/// A [`TagActionMap`] entry: the [`Action`] to apply, plus an optional
+/// user-authored comment explaining why.
+#[derive(Debug, Clone, PartialEq)]
+struct TagActionEntry {
+    action: Action,
+    comment: Option<String>,
+}
+
+/// A wildcard tag key like `"(60xx,0010)"`: each group/element nibble is
+/// either pinned to a literal value or left as an `x` don't-care, expressed
+/// the same `(mask, value)` way [`TagRule::GroupMaskElement`] does.
+///
+/// Lets [`TagActionMap`] express a rule for a whole repeating group (overlays
+/// `60xx`, curves `50xx`, per-frame groups) without enumerating every
+/// concrete tag, the same problem [`TagRule`] solves for the `tag_rules`
+/// fallback list - but scoped to `tag_actions` entries themselves, which take
+/// precedence over `tag_rules` in [`Config::get_action_for_vr`].
+#[derive(Debug, Clone, PartialEq)]
+struct TagKeyPattern {
+    group_mask: u16,
+    group_value: u16,
+    element_mask: u16,
+    element_value: u16,
+}
+
+impl TagKeyPattern {
+    fn matches(&self, tag: &Tag) -> bool {
+        (tag.group() & self.group_mask) == self.group_value
+            && (tag.element() & self.element_mask) == self.element_value
+    }
+
+    /// Number of hex nibbles (out of 8) this pattern pins to a literal value,
+    /// rather than leaving as an `x` wildcard - used to rank overlapping
+    /// pattern matches, most specific (fewest wildcards) first.
+    fn specificity(&self) -> u32 {
+        self.group_mask.count_ones() + self.element_mask.count_ones()
+    }
+}
+
+impl fmt::Display for TagKeyPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({},{})",
+            render_nibble_pattern(self.group_mask, self.group_value),
+            render_nibble_pattern(self.element_mask, self.element_value)
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
-pub struct TagActionMap(BTreeMap<Tag, Action>);
+pub struct TagActionMap {
+    exact: BTreeMap<Tag, TagActionEntry>,
+    patterns: Vec<(TagKeyPattern, TagActionEntry)>,
+}
 
 impl TagActionMap {
     pub fn new() -> Self {
-        TagActionMap(BTreeMap::new())
+        TagActionMap {
+            exact: BTreeMap::new(),
+            patterns: Vec::new(),
+        }
     }
 
     pub fn insert(&mut self, tag: Tag, action: Action) -> Option<Action> {
-        self.0.insert(tag, action)
+        self.insert_with_comment(tag, action, None)
     }
 
+    /// Inserts `action` for `tag` along with a user-authored `comment`.
+    ///
+    /// Unlike the dictionary alias [`Serialize`] falls back to otherwise, this
+    /// comment is round-tripped verbatim: a config re-serialized after loading
+    /// keeps the explanatory notes it was authored with instead of being
+    /// rewritten to generic aliases.
+    pub fn insert_with_comment(
+        &mut self,
+        tag: Tag,
+        action: Action,
+        comment: Option<String>,
+    ) -> Option<Action> {
+        self.exact
+            .insert(tag, TagActionEntry { action, comment })
+            .map(|entry| entry.action)
+    }
+
+    fn insert_pattern_with_comment(
+        &mut self,
+        pattern: TagKeyPattern,
+        action: Action,
+        comment: Option<String>,
+    ) {
+        self.patterns
+            .push((pattern, TagActionEntry { action, comment }));
+    }
+
+    /// Returns the action for `tag`: its exact entry if one exists, otherwise
+    /// the most specific (fewest wildcards) matching pattern entry, with the
+    /// most recently inserted pattern breaking a tie - mirroring how
+    /// [`Config::matching_rule_action`] resolves `tag_rules`.
     pub fn get(&self, tag: &Tag) -> Option<&Action> {
-        self.0.get(tag)
+        if let Some(entry) = self.exact.get(tag) {
+            return Some(&entry.action);
+        }
+
+        self.best_pattern_match(tag).map(|entry| &entry.action)
+    }
+
+    /// Returns the user-authored comment for `tag`, if one was set via
+    /// [`Self::insert_with_comment`] or survived a deserialization round-trip.
+    pub fn get_comment(&self, tag: &Tag) -> Option<&str> {
+        if let Some(entry) = self.exact.get(tag) {
+            return entry.comment.as_deref();
+        }
+
+        self.best_pattern_match(tag)
+            .and_then(|entry| entry.comment.as_deref())
+    }
+
+    fn best_pattern_match(&self, tag: &Tag) -> Option<&TagActionEntry> {
+        self.patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.matches(tag))
+            .max_by_key(|(pattern, _)| pattern.specificity())
+            .map(|(_, entry)| entry)
     }
 
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.exact.len() + self.patterns.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.exact.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Iterates over the exact `(tag, action)` pairs in this map, in tag
+    /// order. Pattern-keyed entries have no single [`Tag`] to yield and are
+    /// omitted - see [`Self::pattern_iter`] for those.
+    pub fn iter(&self) -> impl Iterator<Item = (&Tag, &Action)> {
+        self.exact.iter().map(|(tag, entry)| (tag, &entry.action))
+    }
+
+    /// Iterates over pattern-keyed entries as `(pattern string, action)`
+    /// pairs, e.g. for [`Config::validate`] to check alongside [`Self::iter`].
+    pub(crate) fn pattern_iter(&self) -> impl Iterator<Item = (String, &Action)> {
+        self.patterns
+            .iter()
+            .map(|(pattern, entry)| (pattern.to_string(), &entry.action))
+    }
+
+    /// Inserts every entry from `other` into this map, overwriting any
+    /// existing exact entry for the same tag or identical pattern. Comments
+    /// carry over with their entry.
+    pub fn extend(&mut self, other: &TagActionMap) {
+        for (tag, entry) in other.exact.iter() {
+            self.exact.insert(*tag, entry.clone());
+        }
+
+        for (pattern, entry) in other.patterns.iter() {
+            if let Some(existing) = self
+                .patterns
+                .iter_mut()
+                .find(|(existing, _)| existing == pattern)
+            {
+                existing.1 = entry.clone();
+            } else {
+                self.patterns.push((pattern.clone(), entry.clone()));
+            }
+        }
+    }
+
+    /// Replaces the action of every entry matching `predicate` with `new_action`.
+    pub(crate) fn replace_where(
+        &mut self,
+        predicate: impl Fn(&Action) -> bool,
+        new_action: Action,
+    ) {
+        for entry in self.exact.values_mut() {
+            if predicate(&entry.action) {
+                entry.action = new_action.clone();
+            }
+        }
+        for (_, entry) in self.patterns.iter_mut() {
+            if predicate(&entry.action) {
+                entry.action = new_action.clone();
+            }
+        }
     }
 }
 
@@ -151,6 +363,7 @@ impl Default for TagActionMap {
 }
 
 // Struct to hold the action and an optional comment
+#[cfg(feature = "serde")]
 #[derive(Serialize)]
 struct TagActionWithComment<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -160,16 +373,17 @@ struct TagActionWithComment<'a> {
 }
 
 // For deserialization, we need an owned version
+#[cfg(feature = "serde")]
 #[derive(Deserialize)]
 struct OwnedTagActionWithComment {
     #[serde(default)]
-    #[allow(dead_code)]
     comment: Option<String>,
     #[serde(flatten)]
     action: Action,
 }
 
 // Function to get the tag alias from the data dictionary
+#[cfg(feature = "serde")]
 fn get_tag_alias(tag: &Tag) -> Option<&'static str> {
     let data_dict = StandardDataDictionary;
     let data_entry = data_dict.by_tag(*tag);
@@ -179,33 +393,61 @@ fn get_tag_alias(tag: &Tag) -> Option<&'static str> {
     }
 }
 
+/// Resolves `keyword` (a standard DICOM keyword, e.g. `"PatientID"`) to the
+/// [`Tag`] the built-in data dictionary associates with it, used by
+/// [`TagActionMap`]'s `Deserialize` impl to accept keyword-keyed entries.
+///
+/// Returns `None` for keywords the dictionary doesn't know, and for keywords
+/// that name a repeating tag range (e.g. overlay group tags), since those
+/// don't resolve to a single [`Tag`].
+#[cfg(feature = "serde")]
+fn resolve_tag_by_keyword(keyword: &str) -> Option<Tag> {
+    let entry = StandardDataDictionary.by_name(keyword)?;
+    match entry.tag {
+        TagRange::Single(tag) => Some(tag),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "serde")]
 impl Serialize for TagActionMap {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        let mut map = serializer.serialize_map(Some(self.len()))?;
 
-        for (tag, action) in &self.0 {
-            // Try to get the alias for this tag
-            let alias = get_tag_alias(tag);
+        for (tag, entry) in &self.exact {
+            // The user's own comment wins; fall back to the dictionary alias
+            // so a config with no authored comments still documents itself.
+            let comment = entry.comment.as_deref().or_else(|| get_tag_alias(tag));
 
             // Convert tag to string format
             let tag_str = format!("{}", tag);
 
             // Create the combined structure with an optional comment
             let action_with_desc = TagActionWithComment {
-                comment: alias,
-                action,
+                comment,
+                action: &entry.action,
             };
 
             map.serialize_entry(&tag_str, &action_with_desc)?;
         }
 
+        for (pattern, entry) in &self.patterns {
+            let action_with_desc = TagActionWithComment {
+                comment: entry.comment.as_deref(),
+                action: &entry.action,
+            };
+
+            map.serialize_entry(&pattern.to_string(), &action_with_desc)?;
+        }
+
         map.end()
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for TagActionMap {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -215,26 +457,67 @@ impl<'de> Deserialize<'de> for TagActionMap {
         let string_map: BTreeMap<String, OwnedTagActionWithComment> =
             BTreeMap::deserialize(deserializer)?;
 
-        // Convert string map to Tag map
-        let mut tag_map = BTreeMap::new();
+        let mut tag_actions = TagActionMap::new();
 
         for (tag_str, action_with_comment) in string_map {
-            // Parse the tag string
-            let tag: Tag = tag_str.parse().map_err(|_| {
-                serde::de::Error::custom(format!(
-                    "Tag must be in format '(XXXX,XXXX)' where X is a hex digit, got: {}",
-                    tag_str
-                ))
-            })?;
-
-            // Make sure the tag string starts and ends with parentheses
-            if !tag_str.starts_with('(') || !tag_str.ends_with(')') {
-                return Err(serde::de::Error::custom(format!(
-                    "Tag must be in format '(XXXX,XXXX)', got: {}",
-                    tag_str
-                )));
+            // A key is the canonical "(gggg,eeee)" hex form (optionally with
+            // 'x'/'X' wildcard nibbles, e.g. "(60xx,0010)" for a whole
+            // repeating group), or a standard DICOM keyword (e.g.
+            // "PatientID") resolved against the data dictionary - this lets a
+            // config be authored with whichever reads more clearly per entry.
+            enum Key {
+                Exact(Tag),
+                Pattern(TagKeyPattern),
             }
 
+            let key = if tag_str.starts_with('(') {
+                if !tag_str.ends_with(')') {
+                    return Err(serde::de::Error::custom(format!(
+                        "Tag must be in format '(XXXX,XXXX)', got: {}",
+                        tag_str
+                    )));
+                }
+
+                match tag_str.parse::<Tag>() {
+                    Ok(tag) => Key::Exact(tag),
+                    Err(_) => {
+                        let inner = &tag_str[1..tag_str.len() - 1];
+                        let (group, element) = inner.split_once(',').ok_or_else(|| {
+                            serde::de::Error::custom(format!(
+                                "Tag must be in format '(XXXX,XXXX)' where X is a hex digit or 'x' wildcard, got: {}",
+                                tag_str
+                            ))
+                        })?;
+
+                        let (group_mask, group_value) =
+                            parse_nibble_pattern(group).ok_or_else(|| {
+                                serde::de::Error::custom(format!(
+                                    "Tag must be in format '(XXXX,XXXX)' where X is a hex digit or 'x' wildcard, got: {}",
+                                    tag_str
+                                ))
+                            })?;
+                        let (element_mask, element_value) =
+                            parse_nibble_pattern(element).ok_or_else(|| {
+                                serde::de::Error::custom(format!(
+                                    "Tag must be in format '(XXXX,XXXX)' where X is a hex digit or 'x' wildcard, got: {}",
+                                    tag_str
+                                ))
+                            })?;
+
+                        Key::Pattern(TagKeyPattern {
+                            group_mask,
+                            group_value,
+                            element_mask,
+                            element_value,
+                        })
+                    }
+                }
+            } else {
+                Key::Exact(resolve_tag_by_keyword(&tag_str).ok_or_else(|| {
+                    serde::de::Error::custom(format!("unknown tag keyword: {}", tag_str))
+                })?)
+            };
+
             let action = action_with_comment.action;
 
             // Make sure the action is valid
@@ -242,16 +525,328 @@ impl<'de> Deserialize<'de> for TagActionMap {
                 serde::de::Error::custom(format!("Validation error for tag {}: {}", tag_str, err))
             })?;
 
-            // We only keep the action, not the comment
-            tag_map.insert(tag, action);
+            // Keep the user's comment (if any) so it survives a load-save cycle
+            // instead of being rewritten to the generic dictionary alias.
+            match key {
+                Key::Exact(tag) => {
+                    tag_actions.insert_with_comment(tag, action, action_with_comment.comment);
+                }
+                Key::Pattern(pattern) => {
+                    tag_actions.insert_pattern_with_comment(
+                        pattern,
+                        action,
+                        action_with_comment.comment,
+                    );
+                }
+            }
         }
 
-        Ok(TagActionMap(tag_map))
+        Ok(tag_actions)
     }
 }
 
 pub fn default_hash_fn() -> HashFn {
-    blake3_hash_fn
+    blake3_hash_fn.into()
+}
+
+/// The digest algorithm [`Config::get_hash_fn`] derives its [`HashFn`] from.
+///
+/// Unlike `hash_fn` itself, this is part of `Config`'s serialized form, so a
+/// config round-trip never silently resets the algorithm back to BLAKE3 -
+/// the previously hardcoded behavior, and still this enum's [`Default`] -
+/// and a site that must match an existing pipeline's digest choice can
+/// select the same one explicitly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HashAlgorithm {
+    #[default]
+    Blake3,
+    Sha256,
+    Sha512,
+}
+
+impl From<HashAlgorithm> for HashFn {
+    fn from(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Blake3 => blake3_hash_fn.into(),
+            HashAlgorithm::Sha256 => sha256_hash_fn.into(),
+            HashAlgorithm::Sha512 => sha512_hash_fn.into(),
+        }
+    }
+}
+
+impl HashAlgorithm {
+    /// Builds a keyed [`HashFn`] for this algorithm, mixing `key` into every
+    /// hashed value - the keyed counterpart to `HashFn::from(self)`. Reuses
+    /// [`HashFn::keyed`]'s BLAKE3 keyed mode for [`HashAlgorithm::Blake3`];
+    /// other variants key via a real HMAC through [`HashFn::keyed_with`], so a
+    /// site whose approved algorithm isn't BLAKE3 can still get a keyed hasher
+    /// through [`ConfigBuilder::keyed_hash_fn_with_algorithm`].
+    pub fn keyed_hash_fn(self, key: impl Into<String>) -> HashFn {
+        match self {
+            HashAlgorithm::Blake3 => HashFn::keyed(key),
+            HashAlgorithm::Sha256 => HashFn::keyed_with(key, KeyedDigest::Sha256),
+            HashAlgorithm::Sha512 => HashFn::keyed_with(key, KeyedDigest::Sha512),
+        }
+    }
+}
+
+/// A rule that matches a whole set of tags, rather than a single exact [`Tag`].
+///
+/// Rules are consulted by [`Config::get_action`] as a fallback when a tag has
+/// no exact entry in `tag_actions`, which lets a config express things like
+/// "empty every private creator element" or "remove one overlay group but keep
+/// another" without enumerating every individual tag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagRule {
+    /// Matches any tag whose group falls within `start..=end`, regardless of element.
+    GroupRange { start: u16, end: u16 },
+    /// Matches any tag whose group, masked with `mask`, equals `value`
+    /// (e.g. `mask: 0xFF00, value: 0x6000` matches every overlay group).
+    GroupMask { mask: u16, value: u16 },
+    /// Matches `element` within any private (odd-numbered) group, e.g. the
+    /// private creator element `(gggg,0010)` across every private block.
+    PrivateElement { element: u16 },
+    /// Matches `element` within any group matching `mask`/`value`, e.g.
+    /// `mask: 0xFF00, value: 0x6000, element: 0x3000` matches Overlay Data
+    /// (`60xx,3000`) across every overlay group. This is the dynamically
+    /// numbered equivalent of [`TagRule::GroupMask`] when only one element
+    /// within the repeating group should match, rather than the whole group.
+    GroupMaskElement { mask: u16, value: u16, element: u16 },
+}
+
+impl TagRule {
+    fn matches(&self, tag: &Tag) -> bool {
+        match self {
+            TagRule::GroupRange { start, end } => (*start..=*end).contains(&tag.group()),
+            TagRule::GroupMask { mask, value } => (tag.group() & mask) == *value,
+            TagRule::PrivateElement { element } => is_private_tag(tag) && tag.element() == *element,
+            TagRule::GroupMaskElement {
+                mask,
+                value,
+                element,
+            } => (tag.group() & mask) == *value && tag.element() == *element,
+        }
+    }
+
+    /// Parses a DICOM-style wildcard tag pattern like `"(50xx,xxxx)"` into a
+    /// [`TagRule::GroupMask`] (when the element half is all `x`) or a
+    /// [`TagRule::GroupMaskElement`] (when it names one concrete element),
+    /// where each of the 4 hex digits on either side of the comma is either a
+    /// literal nibble or an `x`/`X` don't-care. Since a mask bit is only ever
+    /// set or cleared a whole nibble at a time, a pattern parsed this way can
+    /// never clear a partial nibble.
+    pub fn from_pattern(pattern: &str) -> Result<Self, TagPatternError> {
+        let invalid = || TagPatternError(pattern.to_string());
+
+        let inner = pattern
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let (group, element) = inner.split_once(',').ok_or_else(invalid)?;
+
+        let (group_mask, group_value) = parse_nibble_pattern(group).ok_or_else(invalid)?;
+        let (element_mask, element_value) = parse_nibble_pattern(element).ok_or_else(invalid)?;
+
+        if element_mask == 0 {
+            Ok(TagRule::GroupMask {
+                mask: group_mask,
+                value: group_value,
+            })
+        } else if element_mask == 0xFFFF {
+            Ok(TagRule::GroupMaskElement {
+                mask: group_mask,
+                value: group_value,
+                element: element_value,
+            })
+        } else {
+            // A partially wildcarded element (e.g. "30xx") can't be expressed
+            // by either variant, which only support a fully wildcarded or a
+            // fully concrete element half.
+            Err(invalid())
+        }
+    }
+
+    /// Renders this rule as the wildcard pattern [`Self::from_pattern`]
+    /// parses, for the two variants a single tag pattern can express.
+    fn to_pattern(&self) -> Option<String> {
+        match self {
+            TagRule::GroupMask { mask, value } => {
+                Some(format!("({},xxxx)", render_nibble_pattern(*mask, *value)))
+            }
+            TagRule::GroupMaskElement {
+                mask,
+                value,
+                element,
+            } => Some(format!(
+                "({},{:04X})",
+                render_nibble_pattern(*mask, *value),
+                element
+            )),
+            TagRule::GroupRange { .. } | TagRule::PrivateElement { .. } => None,
+        }
+    }
+
+    /// Returns this rule's group mask, if it has one, for
+    /// [`Config::validate`] to check nibble alignment on - including masks
+    /// built directly rather than through [`Self::from_pattern`].
+    fn group_mask(&self) -> Option<u16> {
+        match self {
+            TagRule::GroupMask { mask, .. } | TagRule::GroupMaskElement { mask, .. } => Some(*mask),
+            TagRule::GroupRange { .. } | TagRule::PrivateElement { .. } => None,
+        }
+    }
+}
+
+/// Whether `mask` only ever clears a whole hex nibble at a time, i.e. every
+/// 4-bit group is either `0x0` or `0xF`.
+fn is_nibble_aligned(mask: u16) -> bool {
+    (0..4).all(|shift| matches!((mask >> (shift * 4)) & 0xF, 0x0 | 0xF))
+}
+
+/// Parses a 4-hex-digit-or-`x` string (e.g. `"50xx"`) into the `(mask,
+/// value)` pair [`TagRule::GroupMask`]/[`TagRule::GroupMaskElement`] expect,
+/// or `None` if it isn't exactly 4 characters of hex digits and `x`/`X`.
+fn parse_nibble_pattern(nibbles: &str) -> Option<(u16, u16)> {
+    if nibbles.len() != 4 {
+        return None;
+    }
+
+    let (mut mask, mut value) = (0u16, 0u16);
+    for c in nibbles.chars() {
+        mask <<= 4;
+        value <<= 4;
+        if !c.eq_ignore_ascii_case(&'x') {
+            mask |= 0xF;
+            value |= c.to_digit(16)? as u16;
+        }
+    }
+
+    Some((mask, value))
+}
+
+/// The inverse of [`parse_nibble_pattern`]: renders `mask`/`value` back as a
+/// 4-character `x`-wildcarded hex string.
+fn render_nibble_pattern(mask: u16, value: u16) -> String {
+    (0..4)
+        .rev()
+        .map(|shift| {
+            let nibble_mask = (mask >> (shift * 4)) & 0xF;
+            if nibble_mask == 0 {
+                'x'
+            } else {
+                let nibble_value = (value >> (shift * 4)) & 0xF;
+                std::char::from_digit(nibble_value as u32, 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            }
+        })
+        .collect()
+}
+
+/// Mirrors [`TagRule`] field-for-field, used only to derive the externally
+/// tagged JSON representation for [`TagRule::GroupRange`] and
+/// [`TagRule::PrivateElement`], which have no wildcard tag pattern of their
+/// own to serialize as.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+enum TagRuleRepr {
+    GroupRange { start: u16, end: u16 },
+    GroupMask { mask: u16, value: u16 },
+    PrivateElement { element: u16 },
+    GroupMaskElement { mask: u16, value: u16, element: u16 },
+}
+
+#[cfg(feature = "serde")]
+impl From<TagRuleRepr> for TagRule {
+    fn from(repr: TagRuleRepr) -> Self {
+        match repr {
+            TagRuleRepr::GroupRange { start, end } => TagRule::GroupRange { start, end },
+            TagRuleRepr::GroupMask { mask, value } => TagRule::GroupMask { mask, value },
+            TagRuleRepr::PrivateElement { element } => TagRule::PrivateElement { element },
+            TagRuleRepr::GroupMaskElement {
+                mask,
+                value,
+                element,
+            } => TagRule::GroupMaskElement {
+                mask,
+                value,
+                element,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<&TagRule> for TagRuleRepr {
+    fn from(rule: &TagRule) -> Self {
+        match *rule {
+            TagRule::GroupRange { start, end } => TagRuleRepr::GroupRange { start, end },
+            TagRule::GroupMask { mask, value } => TagRuleRepr::GroupMask { mask, value },
+            TagRule::PrivateElement { element } => TagRuleRepr::PrivateElement { element },
+            TagRule::GroupMaskElement {
+                mask,
+                value,
+                element,
+            } => TagRuleRepr::GroupMaskElement {
+                mask,
+                value,
+                element,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for TagRule {
+    /// Serializes [`TagRule::GroupMask`]/[`TagRule::GroupMaskElement`] as
+    /// their wildcard pattern string (e.g. `"(50xx,xxxx)"`), which reads far
+    /// better in a hand-edited config than the equivalent `mask`/`value`
+    /// object; other variants fall back to [`TagRuleRepr`]'s tagged form.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.to_pattern() {
+            Some(pattern) => serializer.serialize_str(&pattern),
+            None => TagRuleRepr::from(self).serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TagRule {
+    /// Accepts either a wildcard pattern string (see [`Self::from_pattern`])
+    /// or [`TagRuleRepr`]'s tagged form, so a hand-authored config can use
+    /// the readable pattern syntax while round-tripping whatever this crate
+    /// itself produced.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Pattern(String),
+            Tagged(TagRuleRepr),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Pattern(pattern) => {
+                TagRule::from_pattern(&pattern).map_err(serde::de::Error::custom)
+            }
+            Repr::Tagged(repr) => Ok(repr.into()),
+        }
+    }
+}
+
+/// A [`TagRule`] paired with the [`Action`] to apply when it matches.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TagRuleAction {
+    rule: TagRule,
+    action: Action,
 }
 
 /// Configuration for DICOM de-identification.
@@ -259,31 +854,109 @@ pub fn default_hash_fn() -> HashFn {
 /// This struct contains all the settings that control how DICOM objects will be de-identified, including
 /// UID handling, tag-specific actions, and policies for special tag groups.
 ///
+/// `Serialize`/`Deserialize` (and everything under [`crate::config_format`]) are
+/// only available behind the `serde` cargo feature, which is on by default;
+/// building a [`Config`] programmatically with [`ConfigBuilder`] needs neither
+/// `serde` nor a config file.
+///
 /// # Fields
 ///
 /// * `hash_fn` - The hash function used for all operations requiring hashing
+/// * `hash_algorithm` - The [`HashAlgorithm`] `hash_fn` was derived from, serialized so a config round-trip preserves the choice of digest
 /// * `uid_root` - The [`UidRoot`] to use as prefix when generating new UIDs during de-identification
 /// * `remove_private_tags` - Policy determining whether to keep or remove private DICOM tags
 /// * `remove_curves` - Policy determining whether to keep or remove curve data (groups `0x5000-0x50FF`)
 /// * `remove_overlays` - Policy determining whether to keep or remove overlay data (groups `0x6000-0x60FF`)
 /// * `tag_actions` - Mapping of specific DICOM tags to their corresponding de-identification actions
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// * `tag_rules` - Range/pattern rules consulted when a tag has no entry in `tag_actions`
+/// * `tag_hash_fns` - Per-tag [`HashFn`] overrides, consulted by [`Config::get_hash_fn_for`]
+/// * `date_shift_key` - Secret key [`Action::DateShift`] uses to derive each subject's offset
+/// * `pseudonymization_key` - Secret key [`Action::Pseudonymize`] uses to encrypt and decrypt element values
+/// * `private_creator_policy` - Optional [`PrivateCreatorPolicy`] governing private tags by creator, consulted ahead of `remove_private_tags`
+/// * `uid_mapper` - Optional shared [`UidMapper`] consulted by [`Action::HashUID`] for cross-file UID consistency, set via [`ConfigBuilder::uid_mapper`]
+/// * `vr_actions` - Per-VR fallback actions, consulted by [`Config::get_action_for_vr`] when no `tag_actions`/`tag_rules` entry matches
+/// * `private_tag_default` - Fallback [`Action`] for private tags whose VR can't be resolved and that have no `vr_actions` entry, consulted ahead of `remove_private_tags`
+/// * `output_transfer_syntax` - Desired transfer syntax for re-encoded output, queried via [`Config::get_output_transfer_syntax`]; `None` means "preserve the input transfer syntax"
+/// * `environments` - Named [`PartialConfig`] overrides that inherit from this config as their base, resolved via [`Config::resolve`]
+/// * `profile_codes` - PS3.15 CID 7050 profile option codes recorded via [`ConfigBuilder::basic_profile`] and its option modifiers, consulted by [`Config::get_profile_codes`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Config {
-    #[serde(skip, default = "default_hash_fn")]
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_hash_fn"))]
     hash_fn: HashFn,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    hash_algorithm: HashAlgorithm,
+
+    #[cfg_attr(feature = "serde", serde(default))]
     uid_root: UidRoot,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     remove_private_tags: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     remove_curves: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     remove_overlays: bool,
 
-    #[serde(default = "TagActionMap::default")]
+    #[cfg_attr(feature = "serde", serde(default = "TagActionMap::default"))]
     tag_actions: TagActionMap,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    tag_rules: Vec<TagRuleAction>,
+
+    #[cfg_attr(feature = "serde", serde(skip, default))]
+    tag_hash_fns: HashMap<Tag, HashFn>,
+
+    /// Secret key used to derive the per-subject offset for [`Action::DateShift`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    date_shift_key: String,
+
+    /// Secret key used by [`Action::Pseudonymize`] to encrypt (and, given the
+    /// same key, decrypt) element values. Empty means pseudonymization is
+    /// unconfigured; [`Config::validate`] rejects a `tag_actions` entry using
+    /// [`Action::Pseudonymize`] while this is empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pseudonymization_key: String,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    private_creator_policy: Option<PrivateCreatorPolicy>,
+
+    /// Shared cache consulted by [`Action::HashUID`] so the same source UID
+    /// always maps to the same replacement across a whole de-identification
+    /// run, not just within one call. Runtime state, like `hash_fn` - absent
+    /// by default, so building a `Config` without one leaves `HashUID`
+    /// hashing each UID independently exactly as before this existed.
+    #[cfg_attr(feature = "serde", serde(skip, default))]
+    uid_mapper: Option<std::sync::Arc<UidMapper>>,
+
+    #[cfg_attr(feature = "serde", serde(skip, default))]
+    vr_actions: HashMap<VR, Action>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    private_tag_default: Option<Action>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    output_transfer_syntax: Option<TransferSyntax>,
+
+    /// Named overrides that inherit from `self` as their base, resolved via
+    /// [`Config::resolve`]. Absent entirely from a config document that
+    /// doesn't use this feature, so deserialization of an older config
+    /// behaves exactly as before it existed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    environments: BTreeMap<String, PartialConfig>,
+
+    /// PS3.15 CID 7050 profile option codes recorded via
+    /// [`ConfigBuilder::basic_profile`] and its option modifiers (e.g.
+    /// [`ConfigBuilder::retain_uids`]). Consulted by
+    /// [`DefaultProcessor::finalize`](crate::processor::DefaultProcessor::finalize)
+    /// to fill in `DeidentificationMethodCodeSequence` when it's missing from
+    /// the object being processed; empty unless a profile builder method
+    /// recorded at least one code.
+    #[cfg_attr(feature = "serde", serde(skip, default))]
+    profile_codes: Vec<String>,
 }
 
 impl Config {
@@ -296,18 +969,30 @@ impl Config {
     ) -> Self {
         Self {
             hash_fn,
+            hash_algorithm: HashAlgorithm::default(),
             uid_root,
             remove_private_tags,
             remove_curves,
             remove_overlays,
             tag_actions: TagActionMap::new(),
+            tag_rules: Vec::new(),
+            tag_hash_fns: HashMap::new(),
+            date_shift_key: String::new(),
+            pseudonymization_key: String::new(),
+            private_creator_policy: None,
+            uid_mapper: None,
+            vr_actions: HashMap::new(),
+            private_tag_default: None,
+            output_transfer_syntax: None,
+            environments: BTreeMap::new(),
+            profile_codes: Vec::new(),
         }
     }
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self::new(blake3_hash_fn, UidRoot::default(), false, false, false)
+        Self::new(default_hash_fn(), UidRoot::default(), false, false, false)
     }
 }
 
@@ -325,26 +1010,187 @@ pub(crate) fn is_overlay_tag(tag: &Tag) -> bool {
 }
 
 impl Config {
+    /// Returns the default [`HashFn`], used for tags with no per-tag override.
     pub fn get_hash_fn(&self) -> HashFn {
-        self.hash_fn
+        self.hash_fn.clone()
+    }
+
+    /// Returns the [`HashAlgorithm`] `get_hash_fn` was derived from, unless it
+    /// was overridden with a custom or keyed [`HashFn`] via
+    /// [`ConfigBuilder::hash_fn`]/[`ConfigBuilder::keyed_hash_fn`], in which
+    /// case this still reports the last algorithm explicitly selected via
+    /// [`ConfigBuilder::hash_algorithm`] (or the default, [`HashAlgorithm::Blake3`]).
+    pub fn get_hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    /// Returns the [`HashFn`] that applies to `tag`: its per-tag override if one
+    /// was registered via [`ConfigBuilder::tag_hash_fn`], otherwise the default
+    /// [`HashFn`] returned by [`Self::get_hash_fn`].
+    pub fn get_hash_fn_for(&self, tag: &Tag) -> HashFn {
+        self.tag_hash_fns
+            .get(tag)
+            .cloned()
+            .unwrap_or_else(|| self.hash_fn.clone())
     }
 
     pub fn get_uid_root(&self) -> &UidRoot {
         &self.uid_root
     }
 
+    /// Re-checks every constraint this `Config` should satisfy, returning
+    /// every violation found rather than stopping at the first.
+    ///
+    /// Deserializing a `Config` from JSON already enforces most of these one
+    /// at a time and aborts the parse on the first problem (a malformed tag
+    /// key or an invalid action - see [`TagActionMap`]'s `Deserialize` impl).
+    /// A `Config` assembled through [`ConfigBuilder`] instead, though, never
+    /// goes through that path, so nothing has validated it since its pieces
+    /// were put together by hand. This method gives a caller - a CLI
+    /// printing a complete report for a config it built up in code, say - a
+    /// way to ask explicitly and see every problem at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::{Config, ConfigBuilder};
+    ///
+    /// let config = ConfigBuilder::new().build();
+    /// assert!(config.validate().is_empty());
+    /// ```
+    pub fn validate(&self) -> Vec<ConfigValidationError> {
+        let mut errors = Vec::new();
+
+        if UidRoot::new(self.uid_root.as_ref()).is_err() {
+            errors.push(ConfigValidationError {
+                path: "uid_root".to_string(),
+                reason: format!("{} is not a valid UID root", self.uid_root.as_ref()),
+            });
+        }
+
+        let exact_entries = self
+            .tag_actions
+            .iter()
+            .map(|(tag, action)| (tag.to_string(), action));
+        let pattern_entries = self.tag_actions.pattern_iter();
+
+        for (key, action) in exact_entries.chain(pattern_entries) {
+            if let Err(err) = action.validate() {
+                errors.push(ConfigValidationError {
+                    path: format!("tag_actions.{key}"),
+                    reason: err.to_string(),
+                });
+            }
+
+            if matches!(action, Action::Pseudonymize) && self.pseudonymization_key.is_empty() {
+                errors.push(ConfigValidationError {
+                    path: format!("tag_actions.{key}"),
+                    reason: "Action::Pseudonymize requires a non-empty pseudonymization_key"
+                        .to_string(),
+                });
+            }
+        }
+
+        for (i, entry) in self.tag_rules.iter().enumerate() {
+            if let Some(mask) = entry.rule.group_mask() {
+                if !is_nibble_aligned(mask) {
+                    errors.push(ConfigValidationError {
+                        path: format!("tag_rules.{i}"),
+                        reason: format!(
+                            "group mask {mask:#06X} must clear whole hex nibbles, not partial bits"
+                        ),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the secret key used to derive per-subject offsets for
+    /// [`Action::DateShift`].
+    pub fn get_date_shift_key(&self) -> &str {
+        &self.date_shift_key
+    }
+
+    /// Returns the secret key used to encrypt and decrypt
+    /// [`Action::Pseudonymize`] values.
+    pub fn get_pseudonymization_key(&self) -> &str {
+        &self.pseudonymization_key
+    }
+
+    /// Returns the [`PrivateCreatorPolicy`] governing private tags by creator,
+    /// if one was set via [`ConfigBuilder::private_creator_policy`].
+    pub fn get_private_creator_policy(&self) -> Option<&PrivateCreatorPolicy> {
+        self.private_creator_policy.as_ref()
+    }
+
+    /// Returns the [`UidMapper`] consulted by [`Action::HashUID`] for
+    /// consistent UID replacement across a whole run, if one was set via
+    /// [`ConfigBuilder::uid_mapper`]. `None` means `HashUID` hashes each UID
+    /// independently instead, with no shared cache - but still
+    /// deterministically, since [`UidMapper::map`] is a pure function of the
+    /// original UID and [`Self::get_uid_root`]. This is what replaces this
+    /// crate's old, now-removed `RemapUid` action, which always consulted a
+    /// single shared mapper: the same guarantee (the same original UID always
+    /// maps to the same replacement, everywhere it's referenced) holds either
+    /// way here, whether or not a mapper was explicitly configured - see
+    /// `test_hash_uid_is_consistent_with_or_without_a_shared_mapper` below.
+    /// The only thing an explicit mapper buys is sharing the cache/lookup
+    /// table across calls instead of recomputing it each time.
+    pub fn get_uid_mapper(&self) -> Option<&UidMapper> {
+        self.uid_mapper.as_deref()
+    }
+
+    /// Returns the PS3.15 CID 7050 profile option codes recorded via
+    /// [`ConfigBuilder::basic_profile`] and its option modifiers, if any.
+    /// Empty when this `Config` wasn't built from a profile.
+    pub fn get_profile_codes(&self) -> &[String] {
+        &self.profile_codes
+    }
+
+    /// Returns the fallback [`Action`] registered for `vr` via
+    /// [`ConfigBuilder::vr_action`], if any.
+    pub fn get_vr_action(&self, vr: VR) -> Option<&Action> {
+        self.vr_actions.get(&vr)
+    }
+
+    /// Returns the fallback [`Action`] registered for private tags with an
+    /// unresolvable VR via [`ConfigBuilder::private_tag_default`], if any.
+    pub fn get_private_tag_default(&self) -> Option<&Action> {
+        self.private_tag_default.as_ref()
+    }
+
+    /// Returns the desired output [`TransferSyntax`] registered via
+    /// [`ConfigBuilder::output_transfer_syntax`], if any. `None` means the
+    /// input transfer syntax (see [`crate::transfer_syntax::detect`]) should
+    /// be preserved.
+    pub fn get_output_transfer_syntax(&self) -> Option<&TransferSyntax> {
+        self.output_transfer_syntax.as_ref()
+    }
+
+    /// Returns this config's [`TagActionMap`], e.g. to export it as a
+    /// [`crate::keyword_config::KeywordConfig`] document via
+    /// [`crate::keyword_config::KeywordConfig::from_tag_action_map`].
+    pub fn get_tag_actions(&self) -> &TagActionMap {
+        &self.tag_actions
+    }
+
     /// Returns the appropriate [`Action`] to take for a given DICOM tag.
     ///
     /// This function determines what action should be taken for a specific tag during de-identification
     /// by checking:
     /// 1. If the tag has an explicit action defined in `tag_actions`
-    /// 2. Whether the tag should be removed based on the configuration for tag groups (i.e. private tags, curves, overlays)
+    /// 2. If a `tag_rules` entry matches the tag
+    /// 3. Whether the tag should be removed based on the configuration for tag groups (i.e. private tags, curves, overlays)
     ///
     /// # Priority Rules
-    /// - If the tag has an explicit action configured of `Action::None` but should be removed based on point 2., returns `Action::Remove`
+    /// - If the tag has an explicit action configured of `Action::None` but should be removed based on point 3., returns `Action::Remove`
     /// - If the tag has any other explicit action configured, returns that action
-    /// - If the tag has no explicit action configured but should be removed based on point 2., returns `Action::Remove`
-    /// - If the tag has no explicit action configured and shouldn't be removed based on point 2., returns `Action::Keep`
+    /// - If no `tag_actions` entry exists but a `tag_rules` entry matches, returns that rule's action. When several
+    ///   rules match, the one added last (i.e. the most specific, by convention) wins
+    /// - If nothing above matched but the tag should be removed based on point 3., returns `Action::Remove`
+    /// - Otherwise, returns `Action::Keep`
     ///
     /// # Arguments
     ///
@@ -354,16 +1200,49 @@ impl Config {
     ///
     /// A reference to the appropriate [`Action`] to take for the given tag
     pub fn get_action(&self, tag: &Tag) -> &Action {
+        self.get_action_for_vr(tag, None)
+    }
+
+    /// Returns the appropriate [`Action`] to take for `tag`, like [`Self::get_action`],
+    /// but also consulting a VR-keyed fallback action (see [`ConfigBuilder::vr_action`])
+    /// for `vr` before the should-be-removed/Keep defaults, when no `tag_actions` or
+    /// `tag_rules` entry matches.
+    ///
+    /// `vr` should be the element's VR as carried on the wire for an explicit VR
+    /// transfer syntax, or resolved from a tag dictionary otherwise (see
+    /// [`crate::actions::utils::resolve_vr`]). Pass `None` if it's unavailable; this
+    /// simply skips the VR fallback, matching [`Self::get_action`]'s behavior.
+    pub fn get_action_for_vr(&self, tag: &Tag, vr: Option<VR>) -> &Action {
         match self.tag_actions.get(tag) {
             Some(action) if action == &Action::None && self.should_be_removed(tag) => {
                 &Action::Remove
             }
             Some(action) => action,
-            None if self.should_be_removed(tag) => &Action::Remove,
-            None => &Action::Keep,
+            None => match self.matching_rule_action(tag) {
+                Some(action) => action,
+                None => match vr.and_then(|vr| self.vr_actions.get(&vr)) {
+                    Some(action) => action,
+                    None if is_private_tag(tag) => match &self.private_tag_default {
+                        Some(action) => action,
+                        None if self.should_be_removed(tag) => &Action::Remove,
+                        None => &Action::Keep,
+                    },
+                    None if self.should_be_removed(tag) => &Action::Remove,
+                    None => &Action::Keep,
+                },
+            },
         }
     }
 
+    /// Returns the action of the last-added `tag_rules` entry matching `tag`, if any.
+    fn matching_rule_action(&self, tag: &Tag) -> Option<&Action> {
+        self.tag_rules
+            .iter()
+            .rev()
+            .find(|entry| entry.rule.matches(tag))
+            .map(|entry| &entry.action)
+    }
+
     fn should_be_removed(&self, tag: &Tag) -> bool {
         self.remove_private_tags && is_private_tag(tag)
             || self.remove_curves && is_curve_tag(tag)
@@ -371,252 +1250,1000 @@ impl Config {
     }
 }
 
-/// A builder for [`Config`] to configure DICOM de-identification settings.
+/// A sparse overlay of [`Config`] fields, used to describe a named profile that
+/// inherits from a shared base configuration.
 ///
-/// The builder provides methods to customize various aspects of de-identification, including:
-/// - Setting the UID root prefix for generating UIDs
-/// - Configuring actions for specific DICOM tags
-/// - Setting policies for private tags, curves, and overlays
+/// Every field is optional: a field present in the profile overrides the base,
+/// while an absent field falls back to it. `tag_actions` is special-cased to be
+/// merged rather than replaced wholesale, so a profile only needs to list the
+/// tags it wants to change.
 ///
 /// # Example
 ///
 /// ```
-/// use dicom_anonymization::config::ConfigBuilder;
 /// use dicom_anonymization::actions::Action;
-/// use dicom_anonymization::tags;
+/// use dicom_anonymization::config::PartialConfig;
+/// use dicom_dictionary_std::tags;
 ///
-/// let config = ConfigBuilder::new()
-///     .uid_root("1.2.840.123".parse().unwrap())
-///     .tag_action(tags::PATIENT_NAME, Action::Empty)
-///     .tag_action(tags::PATIENT_ID, Action::Hash{length: None})
-///     .remove_private_tags(true)
-///     .build();
+/// let profile = PartialConfig::new()
+///     .remove_private_tags(false)
+///     .tag_action(tags::PATIENT_NAME, Action::Keep);
 /// ```
-#[derive(Debug, Clone, PartialEq)]
-pub struct ConfigBuilder(Config);
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartialConfig {
+    #[cfg_attr(feature = "serde", serde(default))]
+    uid_root: Option<UidRoot>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hash_algorithm: Option<HashAlgorithm>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    remove_private_tags: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    remove_curves: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    remove_overlays: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tag_actions: Option<TagActionMap>,
+}
 
-impl ConfigBuilder {
+impl PartialConfig {
     pub fn new() -> Self {
-        ConfigBuilder(Config::default())
+        Self::default()
     }
 
-    /// Sets a custom hash function for use in hash operations.
-    ///
-    /// The hash function will be used for all operations requiring hashing like generating new UIDs and
-    /// computing hash values for specific elements.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use dicom_anonymization::config::ConfigBuilder;
-    /// use dicom_anonymization::hasher::blake3_hash_fn;
-    ///
-    /// let config = ConfigBuilder::new()
-    ///    .hash_fn(blake3_hash_fn)
-    ///    .build();
-    /// ```
-    pub fn hash_fn(mut self, hash_fn: HashFn) -> Self {
-        self.0.hash_fn = hash_fn;
+    pub fn uid_root(mut self, uid_root: UidRoot) -> Self {
+        self.uid_root = Some(uid_root);
         self
     }
 
-    /// Sets the UID root for the configuration.
-    ///
-    /// The [`UidRoot`] provides the prefix that will be used when creating new UIDs with [`Action::HashUID`].
-    /// It must follow DICOM UID format rules: start with a digit 1-9 and contain only numbers and dots.
-    /// It must also have no more than 32 characters.
-    ///
-    /// Setting it is optional. In that case, no specific UID prefix will be used when creating new UIDs.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use dicom_anonymization::config::ConfigBuilder;
-    ///
-    /// let config = ConfigBuilder::new()
-    ///     .uid_root("1.2.840.123".parse().unwrap())
-    ///     .build();
-    /// ```
-    pub fn uid_root(mut self, uid_root: UidRoot) -> Self {
-        self.0.uid_root = uid_root;
+    pub fn hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = Some(algorithm);
         self
     }
 
-    /// Sets the action to take for a specific DICOM tag.
+    pub fn remove_private_tags(mut self, remove: bool) -> Self {
+        self.remove_private_tags = Some(remove);
+        self
+    }
+
+    pub fn remove_curves(mut self, remove: bool) -> Self {
+        self.remove_curves = Some(remove);
+        self
+    }
+
+    pub fn remove_overlays(mut self, remove: bool) -> Self {
+        self.remove_overlays = Some(remove);
+        self
+    }
+
+    /// Sets the action for a specific tag within this profile, merging it into
+    /// (rather than replacing) the base configuration's tag actions.
+    pub fn tag_action(mut self, tag: Tag, action: Action) -> Self {
+        self.tag_actions
+            .get_or_insert_with(TagActionMap::new)
+            .insert(tag, action);
+        self
+    }
+
+    /// Merges this profile onto `base`, producing the final [`Config`].
     ///
-    /// The action determines how the tag value will be handled during de-identification.
+    /// Present fields override `base`; absent fields fall back to it.
+    /// `tag_actions` entries are inserted into the base's [`TagActionMap`],
+    /// overwriting only the tags the profile explicitly lists - so an overlay
+    /// can map a tag to [`Action::Keep`] to cancel a removal the base applies,
+    /// without having to repeat the rest of the base's tag list.
+    fn merge_onto(&self, base: &Config) -> Config {
+        let mut merged = base.clone();
+
+        if let Some(uid_root) = &self.uid_root {
+            merged.uid_root = uid_root.clone();
+        }
+        if let Some(hash_algorithm) = self.hash_algorithm {
+            merged.hash_algorithm = hash_algorithm;
+            merged.hash_fn = hash_algorithm.into();
+        }
+        if let Some(remove_private_tags) = self.remove_private_tags {
+            merged.remove_private_tags = remove_private_tags;
+        }
+        if let Some(remove_curves) = self.remove_curves {
+            merged.remove_curves = remove_curves;
+        }
+        if let Some(remove_overlays) = self.remove_overlays {
+            merged.remove_overlays = remove_overlays;
+        }
+        if let Some(tag_actions) = &self.tag_actions {
+            merged.tag_actions.extend(tag_actions);
+        }
+
+        merged
+    }
+}
+
+impl Config {
+    /// Merges `overlay` onto `base` (see [`PartialConfig::merge_onto`]) and
+    /// validates the result, so a layered profile can never resolve to a
+    /// `Config` that [`Config::validate`] would reject - e.g. an overlay that
+    /// maps a tag to [`Action::Pseudonymize`] without the base ever having set
+    /// `pseudonymization_key`.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `tag` - The DICOM tag to apply the action to
-    /// * `action` - The [`Action`] to take
+    /// Returns [`ConfigError::InvalidMergedConfig`] with every violation found
+    /// if the merged config fails [`Config::validate`].
+    pub fn merge(base: &Config, overlay: &PartialConfig) -> Result<Config, ConfigError> {
+        let merged = overlay.merge_onto(base);
+        let errors = merged.validate();
+
+        if errors.is_empty() {
+            Ok(merged)
+        } else {
+            Err(ConfigError::InvalidMergedConfig(errors))
+        }
+    }
+}
+
+impl Config {
+    /// Reads and parses a [`Config`] document of the given [`ConfigFormat`]
+    /// from `reader`, without assuming JSON the way [`Config`]'s own
+    /// `Deserialize` impl does everywhere else in this crate.
     ///
-    /// # Examples
+    /// This delegates to [`crate::config_format`], which also backs the
+    /// extension-based auto-detection in [`Config::from_path`]; this method
+    /// is for callers that already know the format (e.g. a fixed pipeline
+    /// input) or are reading from something other than a file, such as
+    /// stdin or an embedded resource.
+    ///
+    /// # Example
     ///
     /// ```
-    /// use dicom_anonymization::actions::Action;
-    /// use dicom_anonymization::config::ConfigBuilder;
-    /// use dicom_anonymization::tags;
-    /// use dicom_core::Tag;
+    /// use dicom_anonymization::config::{Config, ConfigFormat};
     ///
-    /// let mut config_builder = ConfigBuilder::new();
+    /// let input = r#"{"uid_root": "1.2.3"}"#;
+    /// let config = Config::from_reader_with_format(input.as_bytes(), ConfigFormat::Json).unwrap();
+    /// assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+    /// ```
+    pub fn from_reader_with_format<R: std::io::Read>(
+        mut reader: R,
+        format: ConfigFormat,
+    ) -> Result<Self, ConfigFormatError> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(ConfigFormatError::Read)?;
+
+        config_format::load_config(&input, format)
+    }
+
+    /// Reads `path` and parses it as a [`Config`], guessing the format from
+    /// the file extension. See [`crate::config_format::load_config_file`].
+    pub fn from_path(path: &std::path::Path) -> Result<Self, ConfigFormatError> {
+        config_format::load_config_file(path)
+    }
+
+    /// Serializes `self` as a [`Config`] document in the given [`ConfigFormat`].
     ///
-    /// // No specific action, leave the tag and its value unchanged
-    /// config_builder = config_builder.tag_action(tags::MODALITY, Action::None);
+    /// The `Tag` string key form (e.g. `"(0010,0010)"`) round-trips
+    /// identically across every format this supports, since all of them
+    /// reuse [`TagActionMap`]'s own `Serialize`/`Deserialize` impls rather
+    /// than a format-specific representation.
     ///
-    /// // Remove the tag completely
-    /// config_builder = config_builder.tag_action(tags::SERIES_DATE, Action::Remove);
+    /// # Example
     ///
-    /// // Replace the tag value with an empty value
-    /// config_builder = config_builder.tag_action(tags::PATIENT_SEX, Action::Empty);
+    /// ```
+    /// use dicom_anonymization::config::{Config, ConfigBuilder, ConfigFormat};
     ///
-    /// // Hash the value with a specified length
-    /// config_builder = config_builder.tag_action(tags::PATIENT_ID, Action::Hash { length: Some(10) });
+    /// let config = ConfigBuilder::new().uid_root("1.2.3".parse().unwrap()).build();
+    /// let yaml = config.to_string_with_format(ConfigFormat::Yaml).unwrap();
+    /// assert!(yaml.contains("1.2.3"));
+    /// ```
+    pub fn to_string_with_format(&self, format: ConfigFormat) -> Result<String, ConfigFormatError> {
+        config_format::config_to_string(self, format)
+    }
+}
+
+/// A base [`Config`] together with a set of named [`PartialConfig`] profiles
+/// that inherit from it.
+///
+/// This allows one config file to describe several export modes (e.g. research
+/// export, clinical trial submission, internal sharing) that differ only in a
+/// handful of fields, instead of duplicating the full tag-action table for each.
+///
+/// # Example
+///
+/// ```
+/// use dicom_anonymization::actions::Action;
+/// use dicom_anonymization::config::{Config, ConfigBuilder, PartialConfig, ProfiledConfig};
+/// use dicom_dictionary_std::tags;
+///
+/// let profiled = ProfiledConfig::new(ConfigBuilder::new().build())
+///     .with_profile(
+///         "research_export",
+///         PartialConfig::new().tag_action(tags::PATIENT_NAME, Action::Keep),
+///     );
+///
+/// let config = profiled.resolve("research_export").unwrap();
+/// assert_eq!(config.get_action(&tags::PATIENT_NAME), &Action::Keep);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProfiledConfig {
+    #[cfg_attr(feature = "serde", serde(default))]
+    base: Config,
+    #[cfg_attr(feature = "serde", serde(default))]
+    profiles: BTreeMap<String, PartialConfig>,
+}
+
+impl ProfiledConfig {
+    pub fn new(base: Config) -> Self {
+        Self {
+            base,
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_profile(mut self, name: impl Into<String>, profile: PartialConfig) -> Self {
+        self.profiles.insert(name.into(), profile);
+        self
+    }
+
+    /// Returns the base configuration, with no profile applied.
+    pub fn base(&self) -> &Config {
+        &self.base
+    }
+
+    /// Merges the named profile onto the base configuration via [`Config::merge`].
     ///
-    /// // Hash a UID
-    /// config_builder = config_builder.tag_action(tags::STUDY_INSTANCE_UID, Action::HashUID);
+    /// # Errors
     ///
-    /// // Replace a date with another date using a hash of another tag value to determine the offset
-    /// config_builder = config_builder.tag_action(tags::STUDY_DATE, Action::HashDate { other_tag: tags::PATIENT_ID });
+    /// Returns [`ConfigError::UnknownProfile`] if no profile with that name was
+    /// registered, or whatever [`Config::merge`] returns if the merged result
+    /// fails validation.
+    pub fn resolve(&self, profile_name: &str) -> Result<Config, ConfigError> {
+        let profile = self
+            .profiles
+            .get(profile_name)
+            .ok_or_else(|| ConfigError::UnknownProfile(profile_name.to_string()))?;
+
+        Config::merge(&self.base, profile)
+    }
+}
+
+impl Config {
+    /// Registers `name` as an environment that inherits from `self`, with
+    /// `override_` supplying the fields that differ.
     ///
-    /// // Replace the tag value with a specific value
-    /// config_builder = config_builder.tag_action(tags::DEIDENTIFICATION_METHOD, Action::Replace { value: "MYAPP".into() });
+    /// This is the self-referential counterpart to [`ProfiledConfig`]: rather
+    /// than pairing a base with a separate set of profiles, the overrides
+    /// live directly on the [`Config`] they inherit from, so a single config
+    /// document can describe e.g. `"research"` and `"external_sharing"`
+    /// deployments without a wrapper type.
+    ///
+    /// # Example
     ///
-    /// // Keep the specified tag even when the related group is to be removed
-    /// config_builder = config_builder.remove_private_tags(true).tag_action(Tag(0x0033, 0x0010), Action::Keep);
     /// ```
-    pub fn tag_action(mut self, tag: Tag, action: Action) -> Self {
-        self.0.tag_actions.insert(tag, action);
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::{ConfigBuilder, PartialConfig};
+    /// use dicom_dictionary_std::tags;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .build()
+    ///     .with_environment(
+    ///         "external_sharing",
+    ///         PartialConfig::new().tag_action(tags::PATIENT_NAME, Action::Remove),
+    ///     );
+    ///
+    /// let resolved = config.resolve("external_sharing").unwrap();
+    /// assert_eq!(resolved.get_action(&tags::PATIENT_NAME), &Action::Remove);
+    /// ```
+    pub fn with_environment(mut self, name: impl Into<String>, override_: PartialConfig) -> Self {
+        self.environments.insert(name.into(), override_);
         self
     }
 
-    /// Controls whether private DICOM tags will be removed during de-identification.
+    /// Deep-merges the named environment's override onto `self` via
+    /// [`Config::merge`] (per-tag `Action` replacement, not whole-map
+    /// replacement - see [`PartialConfig::merge_onto`]) and returns a flat
+    /// [`Config`] ready to run.
     ///
-    /// Private DICOM tags are those with odd group numbers. This function configures whether
-    /// these tags should be removed or preserved.
-    ///
-    /// By default (i.e. if not explicitly set to `false`) all private tags will be removed. If enabled,
-    /// individual private tags can still be kept by setting a specific tag [`Action`] for those
-    /// (except [`Action::None`]).
+    /// # Errors
     ///
-    /// # Arguments
+    /// Returns [`ConfigError::UnknownEnvironment`] if no environment with
+    /// that name was registered, or whatever [`Config::merge`] returns if the
+    /// merged result fails validation.
+    pub fn resolve(&self, env_name: &str) -> Result<Config, ConfigError> {
+        let override_ = self
+            .environments
+            .get(env_name)
+            .ok_or_else(|| ConfigError::UnknownEnvironment(env_name.to_string()))?;
+
+        Config::merge(self, override_)
+    }
+}
+
+/// A builder for [`Config`] to configure DICOM de-identification settings.
+///
+/// The builder provides methods to customize various aspects of de-identification, including:
+/// - Setting the UID root prefix for generating UIDs
+/// - Configuring actions for specific DICOM tags
+/// - Setting policies for private tags, curves, and overlays
+///
+/// # Example
+///
+/// ```
+/// use dicom_anonymization::config::ConfigBuilder;
+/// use dicom_anonymization::actions::Action;
+/// use dicom_anonymization::tags;
+///
+/// let config = ConfigBuilder::new()
+///     .uid_root("1.2.840.123".parse().unwrap())
+///     .tag_action(tags::PATIENT_NAME, Action::Empty)
+///     .tag_action(tags::PATIENT_ID, Action::Hash{length: None})
+///     .remove_private_tags(true)
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigBuilder(Config, Vec<&'static str>);
+
+impl ConfigBuilder {
+    pub fn new() -> Self {
+        ConfigBuilder(Config::default(), Vec::new())
+    }
+
+    /// Records that `code` (a PS3.15 CID 7050 De-identification Method code
+    /// value, e.g. `"113100"` for the Basic Application Confidentiality
+    /// Profile) applies to this configuration, so it ends up in the
+    /// `DEIDENTIFICATION_METHOD_CODE_SEQUENCE` value produced by [`Self::build`].
+    fn with_profile_code(mut self, code: &'static str) -> Self {
+        self.1.push(code);
+        self
+    }
+
+    /// Sets a custom hash function for use in hash operations.
     ///
-    /// * `remove` - If `true`, all private tags will be removed. If `false`, they will be kept.
+    /// The hash function will be used for all operations requiring hashing like generating new UIDs and
+    /// computing hash values for specific elements.
     ///
-    /// # Examples
+    /// # Example
     ///
     /// ```
     /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::hasher::blake3_hash_fn;
     ///
-    /// // Remove private tags (default)
     /// let config = ConfigBuilder::new()
-    ///     .remove_private_tags(true)
-    ///     .build();
+    ///    .hash_fn(blake3_hash_fn)
+    ///    .build();
+    /// ```
+    pub fn hash_fn(mut self, hash_fn: impl Into<HashFn>) -> Self {
+        self.0.hash_fn = hash_fn.into();
+        self
+    }
+
+    /// Selects the default hash function by [`HashAlgorithm`] instead of a raw
+    /// [`HashFn`]. Unlike [`Self::hash_fn`], the choice is recorded in
+    /// `hash_algorithm` and therefore survives a config serialize/deserialize
+    /// round-trip, so a de-identification run stays reproducible from its
+    /// saved config and a site can match an existing pipeline's digest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::{ConfigBuilder, HashAlgorithm};
     ///
-    /// // Keep private tags
     /// let config = ConfigBuilder::new()
-    ///     .remove_private_tags(false)
+    ///     .hash_algorithm(HashAlgorithm::Sha256)
     ///     .build();
+    /// assert_eq!(config.get_hash_algorithm(), HashAlgorithm::Sha256);
     /// ```
-    pub fn remove_private_tags(mut self, remove: bool) -> Self {
-        self.0.remove_private_tags = remove;
+    pub fn hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.0.hash_algorithm = algorithm;
+        self.0.hash_fn = algorithm.into();
         self
     }
 
-    /// Controls whether DICOM curve tags (from groups `0x5000-0x50FF`) will be removed during de-identification.
-    ///
-    /// By default (i.e. if not explicitly set to `false`) all curve tags will be removed. If enabled,
-    /// individual curve tags can still be kept by setting a specific tag [`Action`] for those
-    /// (except [`Action::None`]).
-    ///
-    /// # Arguments
+    /// Sets the default hash function to a keyed BLAKE3 hasher, wrapping
+    /// [`blake3_hash_fn`] with `key` mixed into every input.
     ///
-    /// * `remove` - If `true`, all curve tags will be removed. If `false`, they will be kept.
+    /// This allows hashed identifiers and UIDs to stay consistent within one
+    /// institution's key while preventing re-identification via rainbow tables
+    /// on unsalted BLAKE3. Use [`Self::tag_hash_fn`] to apply a different (e.g.
+    /// unkeyed) hasher to specific tags instead.
     ///
-    /// # Examples
+    /// # Example
     ///
     /// ```
     /// use dicom_anonymization::config::ConfigBuilder;
     ///
-    /// // Remove curve tags (default)
-    /// let config = ConfigBuilder::new()
-    ///     .remove_curves(true)
-    ///     .build();
-    ///
-    /// // Keep curve tags
     /// let config = ConfigBuilder::new()
-    ///     .remove_curves(false)
+    ///     .keyed_hash_fn("institution-secret")
     ///     .build();
     /// ```
-    pub fn remove_curves(mut self, remove: bool) -> Self {
-        self.0.remove_curves = remove;
+    pub fn keyed_hash_fn(mut self, key: impl Into<String>) -> Self {
+        self.0.hash_fn = HashFn::keyed(key);
         self
     }
 
-    /// Controls whether DICOM overlay tags (from groups `0x6000-0x60FF`) will be removed during de-identification.
+    /// Like [`Self::keyed_hash_fn`], but keys a hasher for `algorithm`
+    /// instead of always using BLAKE3 (see [`HashAlgorithm::keyed_hash_fn`]),
+    /// for sites whose approved digest isn't BLAKE3. The choice is recorded
+    /// in `hash_algorithm`, so it survives a config serialize/deserialize
+    /// round-trip the same way [`Self::hash_algorithm`] does.
     ///
-    /// By default (i.e. if not explicitly set to `false`) all overlay tags will be removed. If enabled,
-    /// individual overlay tags can still be kept by setting a specific tag [`Action`] for those
-    /// (except [`Action::None`]).
+    /// # Example
     ///
-    /// # Arguments
+    /// ```
+    /// use dicom_anonymization::config::{ConfigBuilder, HashAlgorithm};
     ///
-    /// * `remove` - If `true`, all overlay tags will be removed. If `false`, they will be kept.
+    /// let config = ConfigBuilder::new()
+    ///     .keyed_hash_fn_with_algorithm("institution-secret", HashAlgorithm::Sha256)
+    ///     .build();
+    /// assert_eq!(config.get_hash_algorithm(), HashAlgorithm::Sha256);
+    /// ```
+    pub fn keyed_hash_fn_with_algorithm(
+        mut self,
+        key: impl Into<String>,
+        algorithm: HashAlgorithm,
+    ) -> Self {
+        self.0.hash_algorithm = algorithm;
+        self.0.hash_fn = algorithm.keyed_hash_fn(key);
+        self
+    }
+
+    /// Registers a [`HashFn`] override for a specific tag, used instead of the
+    /// default hash function ([`Self::hash_fn`]) whenever that tag is hashed.
     ///
-    /// # Examples
+    /// # Example
     ///
     /// ```
     /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::hasher::HashFn;
+    /// use dicom_dictionary_std::tags;
     ///
-    /// // Remove overlay tags (default)
     /// let config = ConfigBuilder::new()
-    ///     .remove_overlays(true)
+    ///     .keyed_hash_fn("institution-secret")
+    ///     .tag_hash_fn(tags::PATIENT_ID, HashFn::keyed("patient-id-pepper"))
     ///     .build();
+    /// ```
+    pub fn tag_hash_fn(mut self, tag: Tag, hash_fn: impl Into<HashFn>) -> Self {
+        self.0.tag_hash_fns.insert(tag, hash_fn.into());
+        self
+    }
+
+    /// Sets the secret key [`Action::DateShift`] uses to derive each subject's
+    /// offset. Callers who rely on `DateShift` for the "Retain Longitudinal
+    /// Temporal Information with Modified Dates" option should set a real
+    /// secret here; the default is an empty key.
+    ///
+    /// # Example
     ///
-    /// // Keep overlay tags
-    /// let config = ConfigBuilder::new()
-    ///     .remove_overlays(false)
-    ///     .build();
     /// ```
-    pub fn remove_overlays(mut self, remove: bool) -> Self {
-        self.0.remove_overlays = remove;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new().date_shift_key("institution-secret").build();
+    /// ```
+    pub fn date_shift_key(mut self, key: impl Into<String>) -> Self {
+        self.0.date_shift_key = key.into();
         self
     }
 
-    /// Transforms the [`ConfigBuilder`] into a [`Config`] with all configured options.
+    /// Sets the secret key [`Action::Pseudonymize`] uses to encrypt (and, given
+    /// the same key, decrypt) element values.
     ///
     /// # Example
     ///
     /// ```
     /// use dicom_anonymization::config::ConfigBuilder;
-    /// use dicom_anonymization::actions::Action;
-    /// use dicom_core::Tag;
-    /// use dicom_dictionary_std::tags;
     ///
     /// let config = ConfigBuilder::new()
-    ///     .uid_root("1.2.840.123".parse().unwrap())
-    ///     .remove_private_tags(true)
-    ///     .tag_action(tags::SOP_INSTANCE_UID, Action::HashUID)
-    ///     .tag_action(tags::PATIENT_NAME, Action::Empty)
-    ///     .tag_action(Tag(0x0033, 0x0010), Action::Keep)
+    ///     .pseudonymization_key("institution-secret")
     ///     .build();
     /// ```
-    pub fn build(self) -> Config {
-        self.0
+    pub fn pseudonymization_key(mut self, key: impl Into<String>) -> Self {
+        self.0.pseudonymization_key = key.into();
+        self
     }
-}
 
-impl Default for ConfigBuilder {
-    #[allow(deprecated)]
-    /// Creates a new `ConfigBuilder` with the default configuration.
+    /// Sets the UID root for the configuration.
     ///
-    /// The default configuration includes a standard set of tag actions for DICOM de-identification,
-    /// as well as default settings for removing private tags, curves, and overlays. Also, a default
-    /// [`UidRoot`] value is used (i.e. `"9999"`).
+    /// The [`UidRoot`] provides the prefix that will be used when creating new UIDs with [`Action::HashUID`].
+    /// It must follow DICOM UID format rules: start with a digit 1-9 and contain only numbers and dots.
+    /// It must also have no more than 32 characters.
     ///
-    /// Returns a `ConfigBuilder` initialized with these default settings, which can be further customized
-    /// if needed before building the final [`Config`].
-    fn default() -> Self {
+    /// Setting it is optional. In that case, no specific UID prefix will be used when creating new UIDs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .uid_root("1.2.840.123".parse().unwrap())
+    ///     .build();
+    /// ```
+    pub fn uid_root(mut self, uid_root: UidRoot) -> Self {
+        self.0.uid_root = uid_root;
+        self
+    }
+
+    /// Sets the action to take for a specific DICOM tag.
+    ///
+    /// The action determines how the tag value will be handled during de-identification.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The DICOM tag to apply the action to
+    /// * `action` - The [`Action`] to take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::tags;
+    /// use dicom_core::Tag;
+    ///
+    /// let mut config_builder = ConfigBuilder::new();
+    ///
+    /// // No specific action, leave the tag and its value unchanged
+    /// config_builder = config_builder.tag_action(tags::MODALITY, Action::None);
+    ///
+    /// // Remove the tag completely
+    /// config_builder = config_builder.tag_action(tags::SERIES_DATE, Action::Remove);
+    ///
+    /// // Replace the tag value with an empty value
+    /// config_builder = config_builder.tag_action(tags::PATIENT_SEX, Action::Empty);
+    ///
+    /// // Hash the value with a specified length
+    /// config_builder = config_builder.tag_action(tags::PATIENT_ID, Action::Hash { length: Some(10) });
+    ///
+    /// // Hash a UID
+    /// config_builder = config_builder.tag_action(tags::STUDY_INSTANCE_UID, Action::HashUID);
+    ///
+    /// // Replace a date with another date using a hash of another tag value to determine the offset
+    /// config_builder = config_builder.tag_action(tags::STUDY_DATE, Action::HashDate { other_tag: tags::PATIENT_ID });
+    ///
+    /// // Replace the tag value with a specific value
+    /// config_builder = config_builder.tag_action(tags::DEIDENTIFICATION_METHOD, Action::Replace { value: "MYAPP".into() });
+    ///
+    /// // Keep the specified tag even when the related group is to be removed
+    /// config_builder = config_builder.remove_private_tags(true).tag_action(Tag(0x0033, 0x0010), Action::Keep);
+    /// ```
+    pub fn tag_action(mut self, tag: Tag, action: Action) -> Self {
+        self.0.tag_actions.insert(tag, action);
+        self
+    }
+
+    /// Merges every `(tag, action)` pair from `map` into this builder's
+    /// [`TagActionMap`], overwriting any existing entry for the same tag.
+    ///
+    /// This is the usual way to load a
+    /// [`crate::keyword_config::KeywordConfig`] document: resolve it with
+    /// [`crate::keyword_config::KeywordConfig::into_tag_action_map`] and pass
+    /// the result here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::keyword_config::{KeywordAction, KeywordConfig};
+    ///
+    /// let document = KeywordConfig::new()
+    ///     .with_action("PatientName", KeywordAction::Empty)
+    ///     .with_action("StudyInstanceUID", KeywordAction::HashUID);
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .tag_action_map(document.into_tag_action_map().unwrap())
+    ///     .build();
+    /// ```
+    pub fn tag_action_map(mut self, map: TagActionMap) -> Self {
+        self.0.tag_actions.extend(&map);
+        self
+    }
+
+    /// Adds a range/pattern rule applying `action` to every tag it matches.
+    ///
+    /// Rules are consulted only for tags with no exact entry from [`Self::tag_action`],
+    /// and are tried most-recently-added first, so add more specific rules last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::{ConfigBuilder, TagRule};
+    /// use dicom_core::Tag;
+    ///
+    /// // Remove every curve tag (group range 0x5000-0x50FF)
+    /// let config_builder = ConfigBuilder::new()
+    ///     .tag_rule(TagRule::GroupRange { start: 0x5000, end: 0x50FF }, Action::Remove);
+    ///
+    /// // Empty the private creator element (gggg,0010) across every private block
+    /// let config_builder = config_builder
+    ///     .tag_rule(TagRule::PrivateElement { element: 0x0010 }, Action::Empty);
+    /// ```
+    pub fn tag_rule(mut self, rule: TagRule, action: Action) -> Self {
+        self.0.tag_rules.push(TagRuleAction { rule, action });
+        self
+    }
+
+    /// Adds a rule applying `action` to `element` within every group matching
+    /// `mask`/`value`, e.g. a single element repeated across a dynamically
+    /// numbered group such as the overlay planes (`60xx,xxxx`).
+    ///
+    /// Shorthand for `.tag_rule(TagRule::GroupMaskElement { mask, value, element }, action)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// // Remove Overlay Data (60xx,3000) across every overlay group
+    /// let config_builder = ConfigBuilder::new().group_mask_action(0x6000, 0xFF00, 0x3000, Action::Remove);
+    /// ```
+    pub fn group_mask_action(self, value: u16, mask: u16, element: u16, action: Action) -> Self {
+        self.tag_rule(
+            TagRule::GroupMaskElement {
+                mask,
+                value,
+                element,
+            },
+            action,
+        )
+    }
+
+    /// Adds a rule applying `action` to every tag matching the DICOM-style
+    /// wildcard `pattern`, e.g. `"(50xx,xxxx)"` for every curve group or
+    /// `"(60xx,3000)"` for Overlay Data across every overlay group.
+    ///
+    /// Shorthand for `.tag_rule(TagRule::from_pattern(pattern)?, action)`; see
+    /// [`TagRule::from_pattern`] for the pattern syntax.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config_builder = ConfigBuilder::new()
+    ///     .tag_pattern("(50xx,xxxx)", Action::Remove)
+    ///     .unwrap();
+    /// ```
+    pub fn tag_pattern(self, pattern: &str, action: Action) -> Result<Self, ConfigError> {
+        Ok(self.tag_rule(TagRule::from_pattern(pattern)?, action))
+    }
+
+    /// Sets a [`PrivateCreatorPolicy`] governing private tags by the creator
+    /// string that reserved their block, rather than by absolute tag.
+    ///
+    /// When set, a private tag whose creator can be resolved from the dataset
+    /// is handled by this policy instead of `remove_private_tags`/`tag_rules`;
+    /// see [`crate::private_creator`] for details. Private tags whose creator
+    /// can't be resolved (e.g. the Private Creator Data Element is missing)
+    /// still fall back to the usual `tag_actions`/`tag_rules`/`remove_private_tags`
+    /// handling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::private_creator::{PrivateCreatorPolicy, PrivateCreatorRule};
+    ///
+    /// let policy = PrivateCreatorPolicy::new()
+    ///     .with_rule(PrivateCreatorRule::new("ACME CORP", 0x10), Action::Remove)
+    ///     .with_allowed(PrivateCreatorRule::new("ACME CORP", 0x11));
+    ///
+    /// let config = ConfigBuilder::new().private_creator_policy(policy).build();
+    /// ```
+    pub fn private_creator_policy(mut self, policy: PrivateCreatorPolicy) -> Self {
+        self.0.private_creator_policy = Some(policy);
+        self
+    }
+
+    /// Shares a [`UidMapper`] for [`Action::HashUID`] to consult, so every
+    /// tag using that action - across every object processed with this
+    /// `Config` - maps the same source UID to the same replacement.
+    ///
+    /// Pass the same `Arc<UidMapper>` to every `Config` built for a run
+    /// (and, via [`UidMapper::restore`], mappings exported from a prior run)
+    /// to keep Study/Series/SOPInstanceUID references and similar
+    /// cross-references consistent over however many files the run spans.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::uid_mapper::UidMapper;
+    ///
+    /// let mapper = Arc::new(UidMapper::new("1.2.840.123".parse().unwrap()));
+    /// let config = ConfigBuilder::new().uid_mapper(mapper).build();
+    /// ```
+    pub fn uid_mapper(mut self, mapper: std::sync::Arc<UidMapper>) -> Self {
+        self.0.uid_mapper = Some(mapper);
+        self
+    }
+
+    /// Adds a rule to this builder's [`PrivateCreatorPolicy`], applying
+    /// `action` to the private element `element_byte` reserves within
+    /// `creator`'s block, creating the policy (with its default "remove
+    /// every other private element" fallback) if one isn't set yet.
+    ///
+    /// Shorthand for building a [`PrivateCreatorPolicy`] with
+    /// [`PrivateCreatorPolicy::with_rule`] and passing it to
+    /// [`Self::private_creator_policy`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .private_creator_action("SIEMENS CSA HEADER", 0x10, Action::Keep)
+    ///     .build();
+    /// ```
+    pub fn private_creator_action(
+        mut self,
+        creator: impl Into<String>,
+        element_byte: u8,
+        action: Action,
+    ) -> Self {
+        let policy = self.0.private_creator_policy.take().unwrap_or_default();
+        self.0.private_creator_policy =
+            Some(policy.with_rule(PrivateCreatorRule::new(creator, element_byte), action));
+        self
+    }
+
+    /// Adds `(creator, element_byte)` to this builder's [`PrivateCreatorPolicy`]
+    /// allowlist, keeping that private element even though every other
+    /// private element defaults to being removed. Creates the policy if one
+    /// isn't set yet, same as [`Self::private_creator_action`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .private_creator_allow("SIEMENS CSA HEADER", 0x10)
+    ///     .build();
+    /// ```
+    pub fn private_creator_allow(mut self, creator: impl Into<String>, element_byte: u8) -> Self {
+        let policy = self.0.private_creator_policy.take().unwrap_or_default();
+        self.0.private_creator_policy =
+            Some(policy.with_allowed(PrivateCreatorRule::new(creator, element_byte)));
+        self
+    }
+
+    /// Registers a fallback [`Action`] for every tag whose VR is `vr` and
+    /// that has no explicit [`Self::tag_action`] or matching [`Self::tag_rule`].
+    ///
+    /// This closes the gap for tags not individually enumerated - private
+    /// elements, newer standard tags, vendor additions - by letting a policy
+    /// be stated in terms of "scrub every person name" ([`VR::PN`]) or "hash
+    /// every UID" ([`VR::UI`]) instead of exhaustive tag enumeration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_core::VR;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .vr_action(VR::PN, Action::Empty)
+    ///     .vr_action(VR::UI, Action::HashUID)
+    ///     .build();
+    /// ```
+    pub fn vr_action(mut self, vr: VR, action: Action) -> Self {
+        self.0.vr_actions.insert(vr, action);
+        self
+    }
+
+    /// Registers a fallback [`Action`] for private tags whose VR can't be
+    /// resolved (the standard dictionary doesn't cover private tags) and that
+    /// have no matching [`Self::vr_action`] entry.
+    ///
+    /// This is consulted ahead of `remove_private_tags`, so it lets a private
+    /// tag with unknown VR be scrubbed by a specific [`Action`] (e.g.
+    /// [`Action::Empty`]) instead of only the coarse remove-or-keep choice
+    /// `remove_private_tags` offers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .private_tag_default(Action::Empty)
+    ///     .build();
+    /// ```
+    pub fn private_tag_default(mut self, action: Action) -> Self {
+        self.0.private_tag_default = Some(action);
+        self
+    }
+
+    /// Records the desired transfer syntax for re-encoded output, queryable
+    /// via [`Config::get_output_transfer_syntax`]. Leaving this unset means
+    /// "preserve the input transfer syntax" - whatever [`crate::transfer_syntax::detect`]
+    /// finds in the source object's File Meta Information.
+    ///
+    /// This only records the caller's intent; this crate doesn't itself
+    /// re-encode a dataset's bytes to a different transfer syntax yet, so
+    /// setting this has no effect on [`crate::processor::Processor`] output
+    /// on its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::transfer_syntax::TransferSyntax;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .output_transfer_syntax(TransferSyntax::ExplicitVrLittleEndian)
+    ///     .build();
+    /// ```
+    pub fn output_transfer_syntax(mut self, transfer_syntax: TransferSyntax) -> Self {
+        self.0.output_transfer_syntax = Some(transfer_syntax);
+        self
+    }
+
+    /// Controls whether private DICOM tags will be removed during de-identification.
+    ///
+    /// Private DICOM tags are those with odd group numbers. This function configures whether
+    /// these tags should be removed or preserved.
+    ///
+    /// By default (i.e. if not explicitly set to `false`) all private tags will be removed. If enabled,
+    /// individual private tags can still be kept by setting a specific tag [`Action`] for those
+    /// (except [`Action::None`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `remove` - If `true`, all private tags will be removed. If `false`, they will be kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// // Remove private tags (default)
+    /// let config = ConfigBuilder::new()
+    ///     .remove_private_tags(true)
+    ///     .build();
+    ///
+    /// // Keep private tags
+    /// let config = ConfigBuilder::new()
+    ///     .remove_private_tags(false)
+    ///     .build();
+    /// ```
+    pub fn remove_private_tags(mut self, remove: bool) -> Self {
+        self.0.remove_private_tags = remove;
+        self
+    }
+
+    /// Controls whether DICOM curve tags (from groups `0x5000-0x50FF`) will be removed during de-identification.
+    ///
+    /// By default (i.e. if not explicitly set to `false`) all curve tags will be removed. If enabled,
+    /// individual curve tags can still be kept by setting a specific tag [`Action`] for those
+    /// (except [`Action::None`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `remove` - If `true`, all curve tags will be removed. If `false`, they will be kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// // Remove curve tags (default)
+    /// let config = ConfigBuilder::new()
+    ///     .remove_curves(true)
+    ///     .build();
+    ///
+    /// // Keep curve tags
+    /// let config = ConfigBuilder::new()
+    ///     .remove_curves(false)
+    ///     .build();
+    /// ```
+    pub fn remove_curves(mut self, remove: bool) -> Self {
+        self.0.remove_curves = remove;
+        self
+    }
+
+    /// Controls whether DICOM overlay tags (from groups `0x6000-0x60FF`) will be removed during de-identification.
+    ///
+    /// By default (i.e. if not explicitly set to `false`) all overlay tags will be removed. If enabled,
+    /// individual overlay tags can still be kept by setting a specific tag [`Action`] for those
+    /// (except [`Action::None`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `remove` - If `true`, all overlay tags will be removed. If `false`, they will be kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// // Remove overlay tags (default)
+    /// let config = ConfigBuilder::new()
+    ///     .remove_overlays(true)
+    ///     .build();
+    ///
+    /// // Keep overlay tags
+    /// let config = ConfigBuilder::new()
+    ///     .remove_overlays(false)
+    ///     .build();
+    /// ```
+    pub fn remove_overlays(mut self, remove: bool) -> Self {
+        self.0.remove_overlays = remove;
+        self
+    }
+
+    /// Transforms the [`ConfigBuilder`] into a [`Config`] with all configured options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    /// use dicom_anonymization::actions::Action;
+    /// use dicom_core::Tag;
+    /// use dicom_dictionary_std::tags;
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .uid_root("1.2.840.123".parse().unwrap())
+    ///     .remove_private_tags(true)
+    ///     .tag_action(tags::SOP_INSTANCE_UID, Action::HashUID)
+    ///     .tag_action(tags::PATIENT_NAME, Action::Empty)
+    ///     .tag_action(Tag(0x0033, 0x0010), Action::Keep)
+    ///     .build();
+    /// ```
+    /// Applies any recorded PS3.15 profile option codes to
+    /// `DEIDENTIFICATION_METHOD_CODE_SEQUENCE` and returns the final [`Config`].
+    ///
+    /// When [`Self::basic_profile`] or one of its option modifiers (e.g.
+    /// [`Self::retain_uids`]) was used, this replaces the tag's default `Remove`
+    /// action with `Keep`, so [`crate::processor::DefaultProcessor::finalize`]
+    /// fills the tag with a proper PS3.3 Code Sequence Macro item per
+    /// applicable CID 7050 code (see [`Config::get_profile_codes`]) instead of
+    /// leaving it removed.
+    pub fn build(self) -> Config {
+        let ConfigBuilder(mut config, mut codes) = self;
+
+        if !codes.is_empty() {
+            codes.sort_unstable();
+            codes.dedup();
+            config
+                .tag_actions
+                .insert(tags::DEIDENTIFICATION_METHOD_CODE_SEQUENCE, Action::Keep);
+            config.profile_codes = codes.into_iter().map(str::to_string).collect();
+        }
+
+        config
+    }
+
+    /// Seeds a `ConfigBuilder` with the DICOM PS3.15 Basic Application Level
+    /// Confidentiality Profile's standard (no-options-retained) tag-action table.
+    ///
+    /// This is the same table used by [`Default`], exposed under its own name so
+    /// the profile's named option modifiers (e.g. [`Self::retain_uids`],
+    /// [`Self::retain_longitudinal_temporal`]) read as building on top of a
+    /// documented baseline rather than an opaque default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::basic_profile()
+    ///     .retain_uids(true)
+    ///     .build();
+    /// ```
+    #[allow(deprecated)]
+    pub fn basic_profile() -> Self {
         Self::new()
             .uid_root(UidRoot::new(UID_ROOT_DEFAULT_VALUE).unwrap())
             .remove_private_tags(true)
@@ -1042,8 +2669,9 @@ impl Default for ConfigBuilder {
             .tag_action(tags::COMPRESSION_FORCE, Action::None)
             .tag_action(
                 tags::DATE_OF_LAST_CALIBRATION,
-                Action::HashDate {
-                    other_tag: tags::PATIENT_ID,
+                Action::DateShift {
+                    subject_tag: tags::PATIENT_ID,
+                    max_offset_days: DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS,
                 },
             )
             .tag_action(tags::TIME_OF_LAST_CALIBRATION, Action::None)
@@ -1176,8 +2804,9 @@ impl Default for ConfigBuilder {
             .tag_action(tags::DETECTOR_ID, Action::Remove)
             .tag_action(
                 tags::DATE_OF_LAST_DETECTOR_CALIBRATION,
-                Action::HashDate {
-                    other_tag: tags::PATIENT_ID,
+                Action::DateShift {
+                    subject_tag: tags::PATIENT_ID,
+                    max_offset_days: DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS,
                 },
             )
             .tag_action(tags::TIME_OF_LAST_DETECTOR_CALIBRATION, Action::None)
@@ -1272,8 +2901,9 @@ impl Default for ConfigBuilder {
             .tag_action(tags::ACQUISITION_DURATION, Action::None)
             .tag_action(
                 tags::FRAME_ACQUISITION_DATE_TIME,
-                Action::HashDate {
-                    other_tag: tags::PATIENT_ID,
+                Action::DateShift {
+                    subject_tag: tags::PATIENT_ID,
+                    max_offset_days: DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS,
                 },
             )
             .tag_action(tags::DIFFUSION_DIRECTIONALITY, Action::None)
@@ -1317,8 +2947,9 @@ impl Default for ConfigBuilder {
             .tag_action(tags::DIFFUSION_ANISOTROPY_TYPE, Action::None)
             .tag_action(
                 tags::FRAME_REFERENCE_DATE_TIME,
-                Action::HashDate {
-                    other_tag: tags::PATIENT_ID,
+                Action::DateShift {
+                    subject_tag: tags::PATIENT_ID,
+                    max_offset_days: DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS,
                 },
             )
             .tag_action(tags::MR_METABOLITE_MAP_SEQUENCE, Action::None)
@@ -1642,10 +3273,10 @@ impl Default for ConfigBuilder {
             .tag_action(tags::NUMBER_OF_WAVEFORM_CHANNELS, Action::None)
             .tag_action(tags::NUMBER_OF_WAVEFORM_SAMPLES, Action::None)
             .tag_action(tags::SAMPLING_FREQUENCY, Action::None)
-            .tag_action(tags::MULTIPLEX_GROUP_LABEL, Action::None)
+            .tag_action(tags::MULTIPLEX_GROUP_LABEL, Action::Remove)
             .tag_action(tags::CHANNEL_DEFINITION_SEQUENCE, Action::None)
             .tag_action(tags::WAVEFORM_CHANNEL_NUMBER, Action::None)
-            .tag_action(tags::CHANNEL_LABEL, Action::None)
+            .tag_action(tags::CHANNEL_LABEL, Action::Remove)
             .tag_action(tags::CHANNEL_STATUS, Action::None)
             .tag_action(tags::CHANNEL_SOURCE_SEQUENCE, Action::None)
             .tag_action(tags::CHANNEL_SOURCE_MODIFIERS_SEQUENCE, Action::None)
@@ -1901,7 +3532,7 @@ impl Default for ConfigBuilder {
             .tag_action(tags::CONTENT_TEMPLATE_SEQUENCE, Action::None)
             .tag_action(tags::IDENTICAL_DOCUMENTS_SEQUENCE, Action::None)
             .tag_action(tags::CONTENT_SEQUENCE, Action::Remove)
-            .tag_action(tags::WAVEFORM_ANNOTATION_SEQUENCE, Action::None)
+            .tag_action(tags::WAVEFORM_ANNOTATION_SEQUENCE, Action::Remove)
             .tag_action(tags::TEMPLATE_VERSION, Action::None)
             .tag_action(tags::TEMPLATE_LOCAL_VERSION, Action::None)
             .tag_action(tags::TEMPLATE_EXTENSION_FLAG, Action::None)
@@ -1937,674 +3568,2006 @@ impl Default for ConfigBuilder {
             .tag_action(tags::RESULTS_COMMENTS, Action::Remove)
             .tag_action(tags::DIGITAL_SIGNATURES_SEQUENCE, Action::Remove)
             .tag_action(tags::DATA_SET_TRAILING_PADDING, Action::Remove)
+            .with_profile_code("113100")
+    }
+
+    /// Retains UIDs (`Action::HashUID` tags) unmodified instead of remapping them.
+    ///
+    /// Corresponds to the PS3.15 "Retain UIDs Option".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::basic_profile().retain_uids(true).build();
+    /// ```
+    pub fn retain_uids(mut self, retain: bool) -> Self {
+        if retain {
+            self.0
+                .tag_actions
+                .replace_where(|action| matches!(action, Action::HashUID), Action::Keep);
+            self = self.with_profile_code("113110");
+        }
+        self
+    }
+
+    /// Retains longitudinal temporal information (`Action::HashDate` tags) according
+    /// to `mode`.
+    ///
+    /// Corresponds to the PS3.15 "Retain Longitudinal Temporal Information Options".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::{ConfigBuilder, TemporalMode};
+    ///
+    /// let config = ConfigBuilder::basic_profile()
+    ///     .retain_longitudinal_temporal(TemporalMode::Full)
+    ///     .build();
+    /// ```
+    pub fn retain_longitudinal_temporal(mut self, mode: TemporalMode) -> Self {
+        match mode {
+            TemporalMode::Full => {
+                self.0.tag_actions.replace_where(
+                    |action| matches!(action, Action::HashDate { .. }),
+                    Action::Keep,
+                );
+                self = self.with_profile_code("113106");
+            }
+            TemporalMode::Modified => {
+                self.0.tag_actions.replace_where(
+                    |action| matches!(action, Action::HashDate { .. }),
+                    Action::DateShift {
+                        subject_tag: tags::PATIENT_ID,
+                        max_offset_days: DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS,
+                    },
+                );
+                self = self.with_profile_code("113107");
+            }
+        }
+        self
+    }
+
+    /// Retains patient characteristic tags (age, sex, weight, etc.) instead of
+    /// removing them.
+    ///
+    /// Corresponds to the PS3.15 "Retain Patient Characteristics Option".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::basic_profile()
+    ///     .retain_patient_characteristics(true)
+    ///     .build();
+    /// ```
+    pub fn retain_patient_characteristics(mut self, retain: bool) -> Self {
+        if retain {
+            for tag in PATIENT_CHARACTERISTIC_TAGS {
+                self.0.tag_actions.insert(*tag, Action::Keep);
+            }
+            self = self.with_profile_code("113108");
+        }
+        self
+    }
+
+    /// Retains device identity tags (manufacturer, station name, serial numbers,
+    /// etc.) instead of removing them.
+    ///
+    /// Corresponds to the PS3.15 "Retain Device Identity Option".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::basic_profile()
+    ///     .retain_device_identity(true)
+    ///     .build();
+    /// ```
+    pub fn retain_device_identity(mut self, retain: bool) -> Self {
+        if retain {
+            for tag in DEVICE_IDENTITY_TAGS {
+                self.0.tag_actions.insert(*tag, Action::Keep);
+            }
+            self = self.with_profile_code("113109");
+        }
+        self
+    }
+
+    /// Retains institution identity tags (institution name/address/department)
+    /// instead of removing them.
+    ///
+    /// This is not one of the standard's named options and has no CID 7050 code
+    /// of its own, so enabling it doesn't add an entry to
+    /// `DEIDENTIFICATION_METHOD_CODE_SEQUENCE`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::basic_profile()
+    ///     .retain_institution_identity(true)
+    ///     .build();
+    /// ```
+    pub fn retain_institution_identity(mut self, retain: bool) -> Self {
+        if retain {
+            for tag in INSTITUTION_IDENTITY_TAGS {
+                self.0.tag_actions.insert(*tag, Action::Keep);
+            }
+        }
+        self
+    }
+
+    /// Replaces free-text descriptor tags (study/series description, image
+    /// comments, etc.) with a fixed placeholder instead of removing them outright.
+    ///
+    /// Corresponds to the PS3.15 "Clean Descriptors Option".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::basic_profile()
+    ///     .clean_descriptors(true)
+    ///     .build();
+    /// ```
+    pub fn clean_descriptors(mut self, clean: bool) -> Self {
+        if clean {
+            for tag in DESCRIPTOR_TAGS {
+                self.0.tag_actions.insert(
+                    *tag,
+                    Action::Replace {
+                        value: "ANONYMIZED".into(),
+                    },
+                );
+            }
+            self = self.with_profile_code("113105");
+        }
+        self
+    }
+}
+
+/// How much longitudinal temporal information to retain when enabling
+/// [`ConfigBuilder::retain_longitudinal_temporal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemporalMode {
+    /// Keep dates and times completely unmodified.
+    Full,
+    /// Replace the profile's default `HashDate` actions with [`Action::DateShift`],
+    /// which obscures the actual date/time values while preserving the
+    /// intervals between them for a given subject.
+    Modified,
+}
+
+/// Default bound, in days, for the per-subject offset applied by
+/// [`ConfigBuilder::retain_longitudinal_temporal`]'s `Modified` mode.
+const DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS: u32 = 365;
+
+/// Tags governed by the PS3.15 "Retain Patient Characteristics Option".
+const PATIENT_CHARACTERISTIC_TAGS: &[Tag] = &[
+    tags::PATIENT_SEX,
+    tags::PATIENT_AGE,
+    tags::PATIENT_SIZE,
+    tags::PATIENT_WEIGHT,
+    tags::ETHNIC_GROUP,
+    tags::OCCUPATION,
+    tags::SMOKING_STATUS,
+    tags::ADDITIONAL_PATIENT_HISTORY,
+    tags::PREGNANCY_STATUS,
+    tags::PATIENT_SEX_NEUTERED,
+];
+
+/// Tags governed by the PS3.15 "Retain Device Identity Option".
+const DEVICE_IDENTITY_TAGS: &[Tag] = &[
+    tags::MANUFACTURER,
+    tags::STATION_NAME,
+    tags::MANUFACTURER_MODEL_NAME,
+    tags::DEVICE_SERIAL_NUMBER,
+    tags::PLATE_ID,
+    tags::GENERATOR_ID,
+    tags::CASSETTE_ID,
+    tags::GANTRY_ID,
+    tags::MODIFYING_DEVICE_MANUFACTURER,
+    tags::SCHEDULED_STATION_NAME,
+    tags::PERFORMED_STATION_NAME,
+];
+
+/// Tags identifying the institution where a study was performed. Not one of
+/// PS3.15's standard options; grouped separately from [`DEVICE_IDENTITY_TAGS`]
+/// so callers can retain institution identity without also retaining device
+/// identity (or vice versa).
+const INSTITUTION_IDENTITY_TAGS: &[Tag] = &[
+    tags::INSTITUTION_NAME,
+    tags::INSTITUTION_ADDRESS,
+    tags::INSTITUTIONAL_DEPARTMENT_NAME,
+];
+
+/// Tags governed by the PS3.15 "Clean Descriptors Option".
+const DESCRIPTOR_TAGS: &[Tag] = &[
+    tags::STUDY_DESCRIPTION,
+    tags::SERIES_DESCRIPTION,
+    tags::IMAGE_COMMENTS,
+    tags::PERFORMED_PROCEDURE_STEP_DESCRIPTION,
+];
+
+impl Default for ConfigBuilder {
+    /// Creates a new `ConfigBuilder` with the default configuration.
+    ///
+    /// The default configuration includes a standard set of tag actions for DICOM de-identification,
+    /// as well as default settings for removing private tags, curves, and overlays. Also, a default
+    /// [`UidRoot`] value is used (i.e. `"9999"`).
+    ///
+    /// Returns a `ConfigBuilder` initialized with these default settings, which can be further customized
+    /// if needed before building the final [`Config`].
+    fn default() -> Self {
+        Self::basic_profile()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_builder() {
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Empty)
+            .build();
+        let tag_action = config.get_action(&tags::PATIENT_NAME);
+        assert_eq!(tag_action, &Action::Empty);
+
+        // tags without explicit action should be kept by default
+        let tag_action = config.get_action(&tags::PATIENT_ID);
+        assert_eq!(tag_action, &Action::Keep);
+    }
+
+    #[test]
+    fn test_uid_root_validation() {
+        // Valid cases
+        assert!(UidRoot::new("").is_ok());
+        assert!(UidRoot::new("1").is_ok());
+        assert!(UidRoot::new("1.2.3").is_ok());
+        assert!(UidRoot::new("123.456.").is_ok());
+        assert!(UidRoot::new(&"1".repeat(32)).is_ok());
+
+        // Invalid cases
+        assert!(UidRoot::new("0123").is_err()); // starts with 0
+        assert!(UidRoot::new("a.1.2").is_err()); // contains letter
+        assert!(UidRoot::new("1.2.3-4").is_err()); // contains invalid character
+        assert!(UidRoot::new(&"1".repeat(33)).is_err()); // too long
+    }
+
+    #[test]
+    fn test_uid_root_from_str() {
+        // Valid cases
+        let uid_root: Result<UidRoot, _> = "1.2.736.120".parse();
+        assert!(uid_root.is_ok());
+
+        let uid_root: Result<UidRoot, _> = "".parse();
+        assert!(uid_root.is_ok());
+
+        // Invalid cases
+        let uid_root: Result<UidRoot, _> = "0.1.2".parse();
+        assert!(uid_root.is_err());
+
+        let uid_root: Result<UidRoot, _> = "invalid".parse();
+        assert!(uid_root.is_err());
+    }
+
+    #[test]
+    fn test_uid_root_as_ref() {
+        // Test empty string
+        let uid_root = UidRoot::new("").unwrap();
+        assert_eq!(uid_root.as_ref(), "");
+
+        // Test normal UID root
+        let uid_root = UidRoot::new("1.2.3").unwrap();
+        assert_eq!(uid_root.as_ref(), "1.2.3");
+
+        // Test UID root with trailing dot
+        let uid_root = UidRoot::new("1.2.3.").unwrap();
+        assert_eq!(uid_root.as_ref(), "1.2.3.");
+
+        // Test using as_ref in a function that expects &str
+        fn takes_str(_s: &str) {}
+        let uid_root = UidRoot::new("1.2.3").unwrap();
+        takes_str(uid_root.as_ref());
+    }
+
+    #[test]
+    fn test_is_private_tag() {
+        // private tags
+        assert!(is_private_tag(&Tag::from([1, 0])));
+        assert!(is_private_tag(&Tag::from([13, 12])));
+        assert!(is_private_tag(&Tag::from([33, 33])));
+
+        // non_private tags
+        assert!(!is_private_tag(&tags::ACCESSION_NUMBER));
+        assert!(!is_private_tag(&tags::PATIENT_ID));
+        assert!(!is_private_tag(&tags::PIXEL_DATA));
+    }
+
+    #[test]
+    fn test_keep_private_tag() {
+        let tag = Tag(0x0033, 0x0010);
+        let config = ConfigBuilder::new()
+            .remove_private_tags(true)
+            .tag_action(tag, Action::Keep)
+            .build();
+
+        // explicitly kept private tags should be kept
+        let tag_action = config.get_action(&tag);
+        assert_eq!(tag_action, &Action::Keep);
+        // any other private tag should be removed
+        assert_eq!(config.get_action(&Tag(0x0033, 0x1010)), &Action::Remove);
+        // any other non-private tag should be kept
+        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    }
+
+    #[test]
+    fn test_remove_private_tag() {
+        let tag = Tag(0x0033, 0x0010);
+        let config = ConfigBuilder::new()
+            .remove_private_tags(true)
+            .tag_action(tag, Action::None)
+            .build();
+        let tag_action = config.get_action(&tag);
+        assert_eq!(tag_action, &Action::Remove);
+        assert_eq!(config.get_action(&Tag(0x0033, 0x1010)), &Action::Remove);
+        // any other non-private tag should be kept
+        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    }
+
+    #[test]
+    fn test_is_curve_tag() {
+        // curve tags
+        assert!(is_curve_tag(&Tag::from([0x5000, 0])));
+        assert!(is_curve_tag(&Tag::from([0x5010, 0x0011])));
+        assert!(is_curve_tag(&Tag::from([0x50FF, 0x0100])));
+
+        // non-curve tags
+        assert!(!is_curve_tag(&Tag::from([0x5100, 0])));
+        assert!(!is_curve_tag(&Tag::from([0x6000, 0])));
+    }
+
+    #[test]
+    fn test_keep_curve_tag() {
+        let tag = Tag(0x5010, 0x0011);
+        let config = ConfigBuilder::new()
+            .remove_curves(true)
+            .tag_action(tag, Action::Keep)
+            .build();
+
+        // explicitly kept curve tags should be kept
+        let tag_action = config.get_action(&tag);
+        assert_eq!(tag_action, &Action::Keep);
+        // any other curve tags should be removed
+        assert_eq!(config.get_action(&Tag(0x50FF, 0x0100)), &Action::Remove);
+        // any other non-curve tag should be kept
+        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    }
+
+    #[test]
+    fn test_remove_curve_tag() {
+        let tag = Tag(0x5010, 0x0011);
+        let config = ConfigBuilder::new()
+            .remove_curves(true)
+            .tag_action(tag, Action::None)
+            .build();
+        let tag_action = config.get_action(&tag);
+        assert_eq!(tag_action, &Action::Remove);
+        assert_eq!(config.get_action(&Tag(0x50FF, 0x0100)), &Action::Remove);
+        // any other non-curve tag should be kept
+        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    }
+
+    #[test]
+    fn test_is_overlay_tag() {
+        // overlay tags
+        assert!(is_overlay_tag(&Tag::from([0x6000, 0])));
+        assert!(is_overlay_tag(&Tag::from([0x6010, 0x0011])));
+        assert!(is_overlay_tag(&Tag::from([0x60FF, 0x0100])));
+
+        // non-overlay tags
+        assert!(!is_overlay_tag(&Tag::from([0x6100, 0])));
+        assert!(!is_overlay_tag(&Tag::from([0x5000, 0])));
+    }
+
+    #[test]
+    fn test_keep_overlay_tag() {
+        let tag = Tag(0x6010, 0x0011);
+        let config = ConfigBuilder::new()
+            .remove_overlays(true)
+            .tag_action(tag, Action::Keep)
+            .build();
+
+        // explicitly kept overlay tags should be kept
+        let tag_action = config.get_action(&tag);
+        assert_eq!(tag_action, &Action::Keep);
+        // any other overlay tags should be removed
+        assert_eq!(config.get_action(&Tag(0x60FF, 0x0100)), &Action::Remove);
+        // any other non-overlay tag should be kept
+        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    }
+
+    #[test]
+    fn test_remove_overlay_tag() {
+        let tag = Tag(0x6010, 0x0011);
+        let config = ConfigBuilder::new()
+            .remove_overlays(true)
+            .tag_action(tag, Action::None)
+            .build();
+        let tag_action = config.get_action(&tag);
+        assert_eq!(tag_action, &Action::Remove);
+        assert_eq!(config.get_action(&Tag(0x60FF, 0x0100)), &Action::Remove);
+        // any other non-overlay tag should be kept
+        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map() {
+        let tag_actions = vec![
+            (Tag(0x0010, 0x0010), Action::Empty),
+            (Tag(0x0010, 0x0020), Action::Remove),
+        ];
+
+        let mut map = TagActionMap::new();
+        for tag_action in tag_actions {
+            map.insert(tag_action.0, tag_action.1.clone());
+        }
+        let json = serde_json::to_string(&map).unwrap();
+
+        // Check that the JSON format has tag strings as keys
+        assert_eq!(
+            json,
+            r#"{"(0010,0010)":{"comment":"PatientName","action":"empty"},"(0010,0020)":{"comment":"PatientID","action":"remove"}}"#
+        );
+
+        // Test deserialization
+        let deserialized: TagActionMap = serde_json::from_str(&json).unwrap();
+
+        // Check tag lookup
+        let action1 = deserialized.get(&Tag(0x0010, 0x0010)).unwrap();
+        let action2 = deserialized.get(&Tag(0x0010, 0x0020)).unwrap();
+
+        assert_eq!(*action1, Action::Empty);
+        assert_eq!(*action2, Action::Remove);
+
+        // Check conversion back to tag actions
+        let recovered: Vec<(Tag, Action)> = deserialized
+            .iter()
+            .map(|(tag, action)| (*tag, action.clone()))
+            .collect();
+        assert_eq!(recovered.len(), 2);
+
+        // BTreeMap ordered by Tag, so we can verify the exact order
+        assert_eq!(recovered[0].0, Tag(0x0010, 0x0010));
+        assert_eq!(recovered[0].1, Action::Empty);
+        assert_eq!(recovered[1].0, Tag(0x0010, 0x0020));
+        assert_eq!(recovered[1].1, Action::Remove);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_insert() {
+        let mut map = TagActionMap::new();
+
+        // Insert some tag actions
+        map.insert(Tag(0x0010, 0x0010), Action::Empty);
+        map.insert(Tag(0x0010, 0x0020), Action::Remove);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
+
+        // Serialize and check format
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(
+            json,
+            r#"{"(0010,0010)":{"comment":"PatientName","action":"empty"},"(0010,0020)":{"comment":"PatientID","action":"remove"}}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_ordering() {
+        let mut map = TagActionMap::new();
+
+        // Add tags in non-sequential order
+        map.insert(Tag(0x0020, 0x0010), Action::Empty); // Group 0020 comes after 0010
+        map.insert(Tag(0x0010, 0x0020), Action::Remove); // Element 0020 comes after 0010
+        map.insert(Tag(0x0010, 0x0010), Action::Hash { length: None }); // Should be first
+
+        // Convert to tag actions - should be in order
+        let actions: Vec<(Tag, Action)> = map
+            .iter()
+            .map(|(tag, action)| (*tag, action.clone()))
+            .collect();
+
+        // Verify order is by group first, then element
+        assert_eq!(actions[0].0, Tag(0x0010, 0x0010));
+        assert_eq!(actions[1].0, Tag(0x0010, 0x0020));
+        assert_eq!(actions[2].0, Tag(0x0020, 0x0010));
+
+        // Serialize and check the string format
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(
+            json,
+            r#"{"(0010,0010)":{"comment":"PatientName","action":"hash"},"(0010,0020)":{"comment":"PatientID","action":"remove"},"(0020,0010)":{"comment":"StudyID","action":"empty"}}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_error_handling() {
+        // Test invalid hex digits
+        let json = r#"{"(ZZZZ,0010)":{"action":"empty"}}"#;
+        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_wildcard_group_pattern_matches_whole_repeating_group() {
+        let json = r#"{"(60xx,0010)":{"action":"remove"}}"#;
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+
+        assert_eq!(map.get(&Tag(0x6000, 0x0010)), Some(&Action::Remove));
+        assert_eq!(map.get(&Tag(0x60FE, 0x0010)), Some(&Action::Remove));
+        // different element, no match
+        assert_eq!(map.get(&Tag(0x6000, 0x0011)), None);
+        // different group, no match
+        assert_eq!(map.get(&Tag(0x5000, 0x0010)), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_wildcard_element_pattern() {
+        let json = r#"{"(0010,xx00)":{"action":"empty"}}"#;
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+
+        assert_eq!(map.get(&Tag(0x0010, 0x0100)), Some(&Action::Empty));
+        assert_eq!(map.get(&Tag(0x0010, 0xAB00)), Some(&Action::Empty));
+        assert_eq!(map.get(&Tag(0x0010, 0x0101)), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_exact_entry_takes_precedence_over_pattern() {
+        let json = r#"{
+            "(60xx,0010)": {"action": "remove"},
+            "(0010,xx00)": {"action": "empty"}
+        }"#;
+        let mut map: TagActionMap = serde_json::from_str(json).unwrap();
+        map.insert(Tag(0x6000, 0x0010), Action::Keep);
+
+        assert_eq!(map.get(&Tag(0x6000, 0x0010)), Some(&Action::Keep));
+        // other tags in the same repeating group still fall through to the pattern
+        assert_eq!(map.get(&Tag(0x6001, 0x0010)), Some(&Action::Remove));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_most_specific_pattern_wins() {
+        let json = r#"{
+            "(60xx,xxxx)": {"action": "remove"},
+            "(60xx,0010)": {"action": "keep"}
+        }"#;
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+
+        // the more specific (fewer wildcards) pattern wins over the broader one
+        assert_eq!(map.get(&Tag(0x6000, 0x0010)), Some(&Action::Keep));
+        assert_eq!(map.get(&Tag(0x6000, 0x0011)), Some(&Action::Remove));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_pattern_round_trips_through_json() {
+        let mut map = TagActionMap::new();
+        map.insert_pattern_with_comment(
+            TagKeyPattern {
+                group_mask: 0xFF00,
+                group_value: 0x6000,
+                element_mask: 0xFFFF,
+                element_value: 0x0010,
+            },
+            Action::Remove,
+            None,
+        );
+
+        let json = serde_json::to_string(&map).unwrap();
+        let restored: TagActionMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get(&Tag(0x6000, 0x0010)), Some(&Action::Remove));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_wildcard_pattern_allows_mixed_case_x() {
+        // each nibble is independently concrete or a wildcard, so mixing
+        // "X"/"x" with concrete digits within one group is valid
+        let json = r#"{"(60Xx,0010)":{"action":"remove"}}"#;
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+        assert_eq!(map.get(&Tag(0x6005, 0x0010)), Some(&Action::Remove));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_action_map_rejects_non_hex_non_wildcard_key() {
+        let json = r#"{"(60GG,0010)":{"action":"remove"}}"#;
+        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialization_with_optional_comment() {
+        let mut map = TagActionMap::new();
+
+        // Add some tags - one with a known comment, one unknown
+        map.insert(Tag(0x0010, 0x0010), Action::Empty); // Known: PatientName
+        map.insert(Tag(0x9999, 0x9999), Action::Remove); // Unknown
+
+        // Serialize to JSON
+        let json = serde_json::to_string(&map).unwrap();
+
+        // For the known tag, a comment should be present
+        assert!(json.contains("\"(0010,0010)\":{\"comment\":\"PatientName\",\"action\":\"empty\"}"));
+
+        // For the unknown tag, the comment should be omitted
+        assert!(json.contains("\"(9999,9999)\":{\"action\":\"remove\"}"));
+        assert!(!json.contains("\"(9999,9999)\":{\"comment\""));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialization_with_optional_comment() {
+        // Test with and without comment
+        let json = r#"{
+            "(0010,0010)":{"comment":"PatientName","action":"empty"},
+            "(0010,0020)":{"action":"remove"}
+        }"#;
+
+        // Deserialize
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+
+        // Both should deserialize correctly
+        assert_eq!(map.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
+        assert_eq!(map.get(&Tag(0x0010, 0x0020)), Some(&Action::Remove));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_roundtrip_with_optional_comment() {
+        let mut original = TagActionMap::new();
+
+        // Add a mix of known and unknown tags
+        original.insert(Tag(0x0010, 0x0010), Action::Empty); // Known
+        original.insert(Tag(0x0008, 0x0050), Action::HashUID); // Known
+        original.insert(Tag(0x9999, 0x9999), Action::Remove); // Unknown
+
+        // Serialize
+        let json = serde_json::to_string(&original).unwrap();
+
+        // Known tags should have comments
+        assert!(json.contains("\"comment\":\"PatientName\""));
+        assert!(json.contains("\"comment\":\"AccessionNumber\""));
+
+        // Unknown tag should not have a comment
+        assert!(!json.contains("\"(9999,9999)\":{\"comment\""));
+
+        // Deserialize back
+        let deserialized: TagActionMap = serde_json::from_str(&json).unwrap();
+
+        // Verify all actions were preserved
+        assert_eq!(deserialized.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
+        assert_eq!(
+            deserialized.get(&Tag(0x0008, 0x0050)),
+            Some(&Action::HashUID)
+        );
+        assert_eq!(
+            deserialized.get(&Tag(0x9999, 0x9999)),
+            Some(&Action::Remove)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_user_comment_survives_roundtrip_instead_of_dictionary_alias() {
+        let mut original = TagActionMap::new();
+        original.insert_with_comment(
+            Tag(0x0010, 0x0010),
+            Action::Empty,
+            Some("required by our IRB protocol".to_string()),
+        );
+
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains("\"comment\":\"required by our IRB protocol\""));
+        assert!(!json.contains("\"comment\":\"PatientName\""));
+
+        let deserialized: TagActionMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.get_comment(&Tag(0x0010, 0x0010)),
+            Some("required by our IRB protocol")
+        );
+
+        // Re-serializing keeps the same user comment, not the dictionary alias.
+        let json_again = serde_json::to_string(&deserialized).unwrap();
+        assert_eq!(json, json_again);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_without_user_comment_falls_back_to_dictionary_alias() {
+        let mut map = TagActionMap::new();
+        map.insert(Tag(0x0010, 0x0010), Action::Empty);
+
+        assert_eq!(map.get_comment(&Tag(0x0010, 0x0010)), None);
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(json.contains("\"comment\":\"PatientName\""));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_malformed_json() {
+        // Action field of a wrong type
+        let json = r#"{"(0010,0010)":{"comment":"PatientName","action":123}}"#;
+        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+
+        // Should fail - action is required and must be valid
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_accepts_keyword_key() {
+        let json = r#"{"PatientID": {"action": "remove"}}"#;
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+        assert_eq!(map.get(&Tag(0x0010, 0x0020)), Some(&Action::Remove));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_accepts_mixed_keyword_and_hex_keys() {
+        let json = r#"{
+            "PatientID": {"action": "remove"},
+            "(0010,0010)": {"action": "empty"}
+        }"#;
+        let map: TagActionMap = serde_json::from_str(json).unwrap();
+        assert_eq!(map.get(&Tag(0x0010, 0x0020)), Some(&Action::Remove));
+        assert_eq!(map.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_rejects_unknown_keyword() {
+        let json = r#"{"NotARealKeyword": {"action": "remove"}}"#;
+        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("unknown tag keyword"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_hash_length_error() {
+        // Hash length should be at least 8
+        let json = r#"{"(0010,0010)":{"comment":"PatientName","action":"hash","length":5}}"#;
+        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+
+        // Should fail - hash length must be valid
+        assert!(result.is_err());
+        let error_message = result.unwrap_err().to_string().to_lowercase();
+        assert!(error_message.contains("validation error"));
+        assert!(error_message.contains("length"));
+    }
+
+    fn create_sample_tag_actions() -> TagActionMap {
+        let mut map = TagActionMap::new(); // Assuming you have a constructor
+        map.insert(Tag(0x0010, 0x0010), Action::Empty); // Patient Name
+        map.insert(Tag(0x0010, 0x0020), Action::Remove); // Patient ID
+        map.insert(Tag(0x0008, 0x0050), Action::Hash { length: None }); // Accession Number
+        map
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_serialization() {
+        // Create a sample config
+        let config = Config {
+            uid_root: UidRoot("1.2.826.0.1.3680043.10.188".to_string()),
+            tag_actions: create_sample_tag_actions(),
+            remove_private_tags: true,
+            remove_curves: false,
+            remove_overlays: true,
+            ..Default::default()
+        };
+
+        // Serialize to JSON
+        let json = serde_json::to_string_pretty(&config).unwrap();
+
+        // Basic checks on the JSON string
+        assert!(json.contains(r#""uid_root": "1.2.826.0.1.3680043.10.188"#));
+        assert!(json.contains(r#""remove_private_tags": true"#));
+        assert!(json.contains(r#""remove_curves": false"#));
+        assert!(json.contains(r#""remove_overlays": true"#));
+
+        // Check tag actions serialized correctly
+        assert!(json.contains(r#""(0010,0010)""#)); // Patient Name
+        assert!(json.contains(r#""action": "empty""#));
+        assert!(json.contains(r#""(0010,0020)""#)); // Patient ID
+        assert!(json.contains(r#""action": "remove""#));
+        assert!(json.contains(r#""(0008,0050)""#)); // Accession Number
+        assert!(json.contains(r#""action": "hash""#));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_deserialization() {
+        // JSON representation of config
+        let json = r#"{
+            "uid_root": "1.2.826.0.1.3680043.10.188",
+            "remove_private_tags": true,
+            "remove_curves": false,
+            "remove_overlays": true,
+            "tag_actions": {
+                "(0010,0010)": {"action": "empty"},
+                "(0010,0020)": {"action": "remove"},
+                "(0008,0050)": {"action": "hash"}
+            }
+        }"#;
+
+        // Deserialize to Config
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        // Check basic fields
+        assert_eq!(config.uid_root.0, "1.2.826.0.1.3680043.10.188");
+        assert!(config.remove_private_tags);
+        assert!(!config.remove_curves);
+        assert!(config.remove_overlays);
+
+        // Check tag actions
+        let patient_name = config.tag_actions.get(&Tag(0x0010, 0x0010)).unwrap();
+        match patient_name {
+            Action::Empty => { /* expected */ }
+            _ => panic!("Expected Empty action for Patient Name"),
+        }
+
+        let patient_id = config.tag_actions.get(&Tag(0x0010, 0x0020)).unwrap();
+        match patient_id {
+            Action::Remove => { /* expected */ }
+            _ => panic!("Expected Remove action for Patient ID"),
+        }
+
+        let accession = config.tag_actions.get(&Tag(0x0008, 0x0050)).unwrap();
+        match accession {
+            Action::Hash { length } => {
+                assert_eq!(*length, None);
+            }
+            _ => panic!("Expected Hash action for Accession Number"),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_roundtrip() {
+        // Create original config
+        let original_config = Config {
+            uid_root: UidRoot("1.2.826.0.1.3680043.10.188".to_string()),
+            tag_actions: create_sample_tag_actions(),
+            remove_private_tags: true,
+            remove_curves: false,
+            remove_overlays: true,
+            ..Default::default()
+        };
+
+        // Serialize to JSON and back
+        let json = serde_json::to_string(&original_config).unwrap();
+        let deserialized: Config = serde_json::from_str(&json).unwrap();
+
+        // Compare UID root
+        assert_eq!(original_config.uid_root.0, deserialized.uid_root.0);
+
+        // Compare boolean flags
+        assert_eq!(
+            original_config.remove_private_tags,
+            deserialized.remove_private_tags
+        );
+        assert_eq!(original_config.remove_curves, deserialized.remove_curves);
+        assert_eq!(
+            original_config.remove_overlays,
+            deserialized.remove_overlays
+        );
+
+        // Compare tag actions
+        let tags_to_check = [
+            Tag(0x0010, 0x0010), // Patient Name
+            Tag(0x0010, 0x0020), // Patient ID
+            Tag(0x0008, 0x0050), // Accession Number
+        ];
+
+        for tag in &tags_to_check {
+            let original_action = original_config.tag_actions.get(tag);
+            let deserialized_action = deserialized.tag_actions.get(tag);
+
+            assert_eq!(
+                original_action, deserialized_action,
+                "Action for tag ({}) didn't roundtrip correctly",
+                tag,
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_empty_tag_actions() {
+        // Create a config with empty tag actions
+        let empty_map = TagActionMap::new();
+        let config = Config {
+            uid_root: UidRoot("1.2.826.0.1.3680043.10.188".to_string()),
+            tag_actions: empty_map,
+            ..Default::default()
+        };
+
+        // Serialize and deserialize
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: Config = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.uid_root.0, "1.2.826.0.1.3680043.10.188");
+        assert!(!deserialized.remove_private_tags);
+        assert!(!deserialized.remove_curves);
+        assert!(!deserialized.remove_overlays);
+        assert_eq!(deserialized.tag_actions.len(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_partial_config_deserialization() {
+        let json = r#"{
+            "uid_root": "1.2.826.0.1.3680043.10.188",
+            "tag_actions": {
+                "(0010,0010)": {"action": "empty"}
+            }
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        let config = result.unwrap();
+
+        assert_eq!(config.uid_root.0, "1.2.826.0.1.3680043.10.188");
+        assert!(!config.remove_private_tags);
+        assert!(!config.remove_curves);
+        assert!(!config.remove_overlays);
+        assert_eq!(config.tag_actions.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_empty_uid_root_and_tag_actions() {
+        let json = r#"{
+            "uid_root": "",
+            "remove_private_tags": true,
+            "remove_curves": false,
+            "remove_overlays": true,
+            "tag_actions": {}
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        let config = result.unwrap();
+
+        assert_eq!(config.uid_root.0, "");
+        assert!(config.remove_private_tags);
+        assert!(!config.remove_curves);
+        assert!(config.remove_overlays);
+        assert_eq!(config.tag_actions.len(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_missing_uid_root() {
+        let json = r#"{
+            "remove_private_tags": true,
+            "remove_curves": false,
+            "remove_overlays": true,
+            "tag_actions": {}
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        let config = result.unwrap();
+
+        assert_eq!(config.uid_root.0, "");
+        assert!(config.remove_private_tags);
+        assert!(!config.remove_curves);
+        assert!(config.remove_overlays);
+        assert_eq!(config.tag_actions.len(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_default_remove_fields() {
+        let json = r#"{
+            "uid_root": "9999",
+            "tag_actions": {}
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        let config = result.unwrap();
+
+        assert_eq!(config.uid_root.0, "9999");
+        assert!(!config.remove_private_tags);
+        assert!(!config.remove_curves);
+        assert!(!config.remove_overlays);
+        assert_eq!(config.tag_actions.len(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_only_empty_tag_actions() {
+        let json = r#"{
+            "tag_actions": {}
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        let config = result.unwrap();
+
+        assert_eq!(config.uid_root.0, "");
+        assert!(!config.remove_private_tags);
+        assert!(!config.remove_curves);
+        assert!(!config.remove_overlays);
+        assert_eq!(config.tag_actions.len(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_malformed_config() {
+        // Invalid tag format
+        let json = r#"{
+            "uid_root": "1.2.826.0.1.3680043.10.188",
+            "remove_private_tags": true,
+            "remove_curves": false,
+            "remove_overlays": true,
+            "tag_actions": {
+                "invalid_tag_format": {"action": "empty"}
+            }
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+
+        // Invalid action
+        let json = r#"{
+            "uid_root": "1.2.826.0.1.3680043.10.188",
+            "remove_private_tags": true,
+            "remove_curves": false,
+            "remove_overlays": true,
+            "tag_actions": {
+                "(0010,0010)": {"action": "invalid_action"}
+            },
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        assert!(result.is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_malformed_config_rejects_out_of_range_date_shift_offset() {
+        let json = r#"{
+            "tag_actions": {
+                "(0010,0020)": {"action": "dateshift", "subject_tag": "(0010,0020)", "max_offset_days": 36500}
+            }
+        }"#;
+
+        let result: Result<Config, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+    }
 
     #[test]
-    fn test_config_builder() {
+    fn test_validate_accepts_a_valid_config() {
         let config = ConfigBuilder::new()
-            .tag_action(tags::PATIENT_NAME, Action::Empty)
+            .tag_action(tags::PATIENT_NAME, Action::Remove)
             .build();
-        let tag_action = config.get_action(&tags::PATIENT_NAME);
-        assert_eq!(tag_action, &Action::Empty);
+        assert!(config.validate().is_empty());
+    }
 
-        // tags without explicit action should be kept by default
-        let tag_action = config.get_action(&tags::PATIENT_ID);
-        assert_eq!(tag_action, &Action::Keep);
+    #[test]
+    fn test_validate_reports_invalid_uid_root() {
+        let mut config = ConfigBuilder::new().build();
+        config.uid_root = UidRoot("not-a-valid-uid-root".to_string());
+
+        let errors = config.validate();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "uid_root");
     }
 
     #[test]
-    fn test_uid_root_validation() {
-        // Valid cases
-        assert!(UidRoot::new("").is_ok());
-        assert!(UidRoot::new("1").is_ok());
-        assert!(UidRoot::new("1.2.3").is_ok());
-        assert!(UidRoot::new("123.456.").is_ok());
-        assert!(UidRoot::new(&"1".repeat(32)).is_ok());
+    fn test_validate_reports_every_invalid_tag_action() {
+        // `ConfigBuilder::tag_action` doesn't itself validate, so out-of-range
+        // or otherwise invalid actions can land in a `Config` without ever
+        // going through `TagActionMap`'s deserialization checks.
+        let mut config = ConfigBuilder::new()
+            .tag_action(
+                tags::STUDY_DATE,
+                Action::DateShift {
+                    subject_tag: tags::PATIENT_ID,
+                    max_offset_days: 36500,
+                },
+            )
+            .build();
+        config.uid_root = UidRoot("not-a-valid-uid-root".to_string());
 
-        // Invalid cases
-        assert!(UidRoot::new("0123").is_err()); // starts with 0
-        assert!(UidRoot::new("a.1.2").is_err()); // contains letter
-        assert!(UidRoot::new("1.2.3-4").is_err()); // contains invalid character
-        assert!(UidRoot::new(&"1".repeat(33)).is_err()); // too long
+        let errors = config.validate();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.path == "uid_root"));
+        assert!(errors
+            .iter()
+            .any(|e| e.path == format!("tag_actions.{}", tags::STUDY_DATE)));
     }
 
     #[test]
-    fn test_uid_root_from_str() {
-        // Valid cases
-        let uid_root: Result<UidRoot, _> = "1.2.736.120".parse();
-        assert!(uid_root.is_ok());
+    fn test_validate_rejects_pseudonymize_without_a_key() {
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_ID, Action::Pseudonymize)
+            .build();
 
-        let uid_root: Result<UidRoot, _> = "".parse();
-        assert!(uid_root.is_ok());
+        let errors = config.validate();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, format!("tag_actions.{}", tags::PATIENT_ID));
+    }
 
-        // Invalid cases
-        let uid_root: Result<UidRoot, _> = "0.1.2".parse();
-        assert!(uid_root.is_err());
+    #[test]
+    fn test_validate_accepts_pseudonymize_with_a_key() {
+        let config = ConfigBuilder::new()
+            .pseudonymization_key("institution-secret")
+            .tag_action(tags::PATIENT_ID, Action::Pseudonymize)
+            .build();
 
-        let uid_root: Result<UidRoot, _> = "invalid".parse();
-        assert!(uid_root.is_err());
+        assert!(config.validate().is_empty());
     }
 
     #[test]
-    fn test_uid_root_as_ref() {
-        // Test empty string
-        let uid_root = UidRoot::new("").unwrap();
-        assert_eq!(uid_root.as_ref(), "");
+    fn test_profiled_config_resolve_merges_onto_base() {
+        let base = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Remove)
+            .remove_private_tags(true)
+            .build();
 
-        // Test normal UID root
-        let uid_root = UidRoot::new("1.2.3").unwrap();
-        assert_eq!(uid_root.as_ref(), "1.2.3");
+        let profiled = ProfiledConfig::new(base).with_profile(
+            "research_export",
+            PartialConfig::new()
+                .remove_private_tags(false)
+                .tag_action(tags::PATIENT_NAME, Action::Keep),
+        );
 
-        // Test UID root with trailing dot
-        let uid_root = UidRoot::new("1.2.3.").unwrap();
-        assert_eq!(uid_root.as_ref(), "1.2.3.");
+        let config = profiled.resolve("research_export").unwrap();
+        assert_eq!(config.get_action(&tags::PATIENT_NAME), &Action::Keep);
+        assert!(!config.remove_private_tags);
 
-        // Test using as_ref in a function that expects &str
-        fn takes_str(_s: &str) {}
-        let uid_root = UidRoot::new("1.2.3").unwrap();
-        takes_str(uid_root.as_ref());
+        // base is untouched
+        assert_eq!(
+            profiled.base().get_action(&tags::PATIENT_NAME),
+            &Action::Remove
+        );
     }
 
     #[test]
-    fn test_is_private_tag() {
-        // private tags
-        assert!(is_private_tag(&Tag::from([1, 0])));
-        assert!(is_private_tag(&Tag::from([13, 12])));
-        assert!(is_private_tag(&Tag::from([33, 33])));
-
-        // non_private tags
-        assert!(!is_private_tag(&tags::ACCESSION_NUMBER));
-        assert!(!is_private_tag(&tags::PATIENT_ID));
-        assert!(!is_private_tag(&tags::PIXEL_DATA));
+    fn test_profiled_config_unknown_profile() {
+        let profiled = ProfiledConfig::new(Config::default());
+        let result = profiled.resolve("does_not_exist");
+        assert_eq!(
+            result,
+            Err(ConfigError::UnknownProfile("does_not_exist".to_string()))
+        );
     }
 
     #[test]
-    fn test_keep_private_tag() {
-        let tag = Tag(0x0033, 0x0010);
-        let config = ConfigBuilder::new()
-            .remove_private_tags(true)
-            .tag_action(tag, Action::Keep)
+    fn test_partial_config_absent_fields_fall_back_to_base() {
+        let base = ConfigBuilder::new()
+            .uid_root("1.2.3".parse().unwrap())
+            .remove_curves(true)
             .build();
 
-        // explicitly kept private tags should be kept
-        let tag_action = config.get_action(&tag);
-        assert_eq!(tag_action, &Action::Keep);
-        // any other private tag should be removed
-        assert_eq!(config.get_action(&Tag(0x0033, 0x1010)), &Action::Remove);
-        // any other non-private tag should be kept
-        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+        let profiled =
+            ProfiledConfig::new(base.clone()).with_profile("minimal", PartialConfig::new());
+
+        let config = profiled.resolve("minimal").unwrap();
+        assert_eq!(config, base);
     }
 
     #[test]
-    fn test_remove_private_tag() {
-        let tag = Tag(0x0033, 0x0010);
-        let config = ConfigBuilder::new()
-            .remove_private_tags(true)
-            .tag_action(tag, Action::None)
+    fn test_partial_config_overrides_hash_algorithm() {
+        let base = ConfigBuilder::new()
+            .hash_algorithm(HashAlgorithm::Blake3)
             .build();
-        let tag_action = config.get_action(&tag);
-        assert_eq!(tag_action, &Action::Remove);
-        assert_eq!(config.get_action(&Tag(0x0033, 0x1010)), &Action::Remove);
-        // any other non-private tag should be kept
-        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+
+        let config = Config::merge(
+            &base,
+            &PartialConfig::new().hash_algorithm(HashAlgorithm::Sha256),
+        )
+        .unwrap();
+
+        assert_eq!(config.get_hash_algorithm(), HashAlgorithm::Sha256);
+        assert_eq!(
+            config.get_hash_fn().call("203087"),
+            sha256_hash_fn("203087")
+        );
     }
 
     #[test]
-    fn test_is_curve_tag() {
-        // curve tags
-        assert!(is_curve_tag(&Tag::from([0x5000, 0])));
-        assert!(is_curve_tag(&Tag::from([0x5010, 0x0011])));
-        assert!(is_curve_tag(&Tag::from([0x50FF, 0x0100])));
+    fn test_merge_rejects_result_that_fails_validation() {
+        let base = Config::default();
 
-        // non-curve tags
-        assert!(!is_curve_tag(&Tag::from([0x5100, 0])));
-        assert!(!is_curve_tag(&Tag::from([0x6000, 0])));
+        let result = Config::merge(
+            &base,
+            &PartialConfig::new().tag_action(tags::PATIENT_NAME, Action::Pseudonymize),
+        );
+
+        assert!(matches!(result, Err(ConfigError::InvalidMergedConfig(_))));
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_keep_curve_tag() {
-        let tag = Tag(0x5010, 0x0011);
-        let config = ConfigBuilder::new()
-            .remove_curves(true)
-            .tag_action(tag, Action::Keep)
-            .build();
-
-        // explicitly kept curve tags should be kept
-        let tag_action = config.get_action(&tag);
-        assert_eq!(tag_action, &Action::Keep);
-        // any other curve tags should be removed
-        assert_eq!(config.get_action(&Tag(0x50FF, 0x0100)), &Action::Remove);
-        // any other non-curve tag should be kept
-        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+    fn test_from_reader_with_format_reads_yaml() {
+        let input = "uid_root: \"1.2.3\"\n";
+        let config = Config::from_reader_with_format(input.as_bytes(), ConfigFormat::Yaml).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_remove_curve_tag() {
-        let tag = Tag(0x5010, 0x0011);
+    fn test_to_string_with_format_round_trips_through_ron() {
         let config = ConfigBuilder::new()
-            .remove_curves(true)
-            .tag_action(tag, Action::None)
+            .uid_root("1.2.3".parse().unwrap())
+            .tag_action(tags::PATIENT_NAME, Action::Remove)
             .build();
-        let tag_action = config.get_action(&tag);
-        assert_eq!(tag_action, &Action::Remove);
-        assert_eq!(config.get_action(&Tag(0x50FF, 0x0100)), &Action::Remove);
-        // any other non-curve tag should be kept
-        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+
+        let serialized = config.to_string_with_format(ConfigFormat::Ron).unwrap();
+        let round_tripped =
+            Config::from_reader_with_format(serialized.as_bytes(), ConfigFormat::Ron).unwrap();
+
+        assert_eq!(round_tripped, config);
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_is_overlay_tag() {
-        // overlay tags
-        assert!(is_overlay_tag(&Tag::from([0x6000, 0])));
-        assert!(is_overlay_tag(&Tag::from([0x6010, 0x0011])));
-        assert!(is_overlay_tag(&Tag::from([0x60FF, 0x0100])));
+    fn test_from_path_detects_format_from_extension() {
+        let path = std::env::temp_dir().join(format!(
+            "dicom_anonymization_config_from_path_test_{:?}.yaml",
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "uid_root: \"1.2.3\"\n").unwrap();
 
-        // non-overlay tags
-        assert!(!is_overlay_tag(&Tag::from([0x6100, 0])));
-        assert!(!is_overlay_tag(&Tag::from([0x5000, 0])));
+        let config = Config::from_path(&path).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+
+        std::fs::remove_file(&path).unwrap();
     }
 
     #[test]
-    fn test_keep_overlay_tag() {
-        let tag = Tag(0x6010, 0x0011);
+    fn test_resolve_environment_applies_override_onto_base() {
         let config = ConfigBuilder::new()
-            .remove_overlays(true)
-            .tag_action(tag, Action::Keep)
-            .build();
+            .tag_action(tags::PATIENT_NAME, Action::Keep)
+            .build()
+            .with_environment(
+                "external_sharing",
+                PartialConfig::new().tag_action(tags::PATIENT_NAME, Action::Remove),
+            );
 
-        // explicitly kept overlay tags should be kept
-        let tag_action = config.get_action(&tag);
-        assert_eq!(tag_action, &Action::Keep);
-        // any other overlay tags should be removed
-        assert_eq!(config.get_action(&Tag(0x60FF, 0x0100)), &Action::Remove);
-        // any other non-overlay tag should be kept
-        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+        let resolved = config.resolve("external_sharing").unwrap();
+        assert_eq!(resolved.get_action(&tags::PATIENT_NAME), &Action::Remove);
+        // the base itself is untouched
+        assert_eq!(config.get_action(&tags::PATIENT_NAME), &Action::Keep);
     }
 
     #[test]
-    fn test_remove_overlay_tag() {
-        let tag = Tag(0x6010, 0x0011);
+    fn test_resolve_environment_inherits_unspecified_fields_from_base() {
         let config = ConfigBuilder::new()
-            .remove_overlays(true)
-            .tag_action(tag, Action::None)
-            .build();
-        let tag_action = config.get_action(&tag);
-        assert_eq!(tag_action, &Action::Remove);
-        assert_eq!(config.get_action(&Tag(0x60FF, 0x0100)), &Action::Remove);
-        // any other non-overlay tag should be kept
-        assert_eq!(config.get_action(&tags::PATIENT_ID), &Action::Keep);
+            .remove_private_tags(true)
+            .build()
+            .with_environment("research", PartialConfig::new().remove_curves(true));
+
+        let resolved = config.resolve("research").unwrap();
+        assert!(resolved.remove_private_tags);
+        assert!(resolved.remove_curves);
     }
 
     #[test]
-    fn test_tag_action_map() {
-        let tag_actions = vec![
-            (Tag(0x0010, 0x0010), Action::Empty),
-            (Tag(0x0010, 0x0020), Action::Remove),
-        ];
+    fn test_resolve_unknown_environment_errors() {
+        let config = ConfigBuilder::new().build();
 
-        let mut map = TagActionMap::new();
-        for tag_action in tag_actions {
-            map.insert(tag_action.0, tag_action.1.clone());
-        }
-        let json = serde_json::to_string(&map).unwrap();
+        let result = config.resolve("does_not_exist");
 
-        // Check that the JSON format has tag strings as keys
         assert_eq!(
-            json,
-            r#"{"(0010,0010)":{"comment":"PatientName","action":"empty"},"(0010,0020)":{"comment":"PatientID","action":"remove"}}"#
+            result,
+            Err(ConfigError::UnknownEnvironment(
+                "does_not_exist".to_string()
+            ))
         );
+    }
 
-        // Test deserialization
-        let deserialized: TagActionMap = serde_json::from_str(&json).unwrap();
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_without_environments_key_deserializes_as_before() {
+        let json = r#"{"uid_root": "1.2.3"}"#;
 
-        // Check tag lookup
-        let action1 = deserialized.get(&Tag(0x0010, 0x0010)).unwrap();
-        let action2 = deserialized.get(&Tag(0x0010, 0x0020)).unwrap();
+        let config: Config = serde_json::from_str(json).unwrap();
 
-        assert_eq!(*action1, Action::Empty);
-        assert_eq!(*action2, Action::Remove);
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+        assert!(config.environments.is_empty());
+    }
 
-        // Check conversion back to tag actions
-        let recovered: Vec<(Tag, Action)> = deserialized
-            .0
-            .iter()
-            .map(|(tag, action)| (*tag, action.clone()))
-            .collect();
-        assert_eq!(recovered.len(), 2);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_environments_field_is_omitted_from_serialized_output_when_empty() {
+        let config = ConfigBuilder::new().build();
+
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert!(!json.contains("environments"));
+    }
+
+    #[test]
+    fn test_tag_rule_group_range_matches() {
+        let config = ConfigBuilder::new()
+            .tag_rule(
+                TagRule::GroupRange {
+                    start: 0x5000,
+                    end: 0x50FF,
+                },
+                Action::Remove,
+            )
+            .build();
+
+        assert_eq!(config.get_action(&Tag(0x5010, 0x0011)), &Action::Remove);
+        // outside the range falls back to Keep (no removal policy enabled)
+        assert_eq!(config.get_action(&Tag(0x5100, 0x0011)), &Action::Keep);
+    }
+
+    #[test]
+    fn test_tag_rule_group_mask_matches() {
+        let config = ConfigBuilder::new()
+            .tag_rule(
+                TagRule::GroupMask {
+                    mask: 0xFF00,
+                    value: 0x6000,
+                },
+                Action::Empty,
+            )
+            .build();
+
+        assert_eq!(config.get_action(&Tag(0x60A0, 0x0000)), &Action::Empty);
+        assert_eq!(config.get_action(&Tag(0x6100, 0x0000)), &Action::Keep);
+    }
+
+    #[test]
+    fn test_tag_rule_group_mask_element_matches_one_element_across_every_group() {
+        let config = ConfigBuilder::new()
+            .tag_rule(
+                TagRule::GroupMaskElement {
+                    mask: 0xFF00,
+                    value: 0x6000,
+                    element: 0x3000,
+                },
+                Action::Remove,
+            )
+            .build();
 
-        // BTreeMap ordered by Tag, so we can verify the exact order
-        assert_eq!(recovered[0].0, Tag(0x0010, 0x0010));
-        assert_eq!(recovered[0].1, Action::Empty);
-        assert_eq!(recovered[1].0, Tag(0x0010, 0x0020));
-        assert_eq!(recovered[1].1, Action::Remove);
+        // Overlay Data in two different overlay groups
+        assert_eq!(config.get_action(&Tag(0x6000, 0x3000)), &Action::Remove);
+        assert_eq!(config.get_action(&Tag(0x60A0, 0x3000)), &Action::Remove);
+        // a different element in the same overlay group isn't affected
+        assert_eq!(config.get_action(&Tag(0x6000, 0x0022)), &Action::Keep);
+        // outside the group mask isn't affected
+        assert_eq!(config.get_action(&Tag(0x6100, 0x3000)), &Action::Keep);
     }
 
     #[test]
-    fn test_tag_action_map_insert() {
-        let mut map = TagActionMap::new();
+    fn test_group_mask_action_is_shorthand_for_group_mask_element_rule() {
+        let config = ConfigBuilder::new()
+            .group_mask_action(0x6000, 0xFF00, 0x3000, Action::Remove)
+            .build();
 
-        // Insert some tag actions
-        map.insert(Tag(0x0010, 0x0010), Action::Empty);
-        map.insert(Tag(0x0010, 0x0020), Action::Remove);
+        assert_eq!(config.get_action(&Tag(0x6000, 0x3000)), &Action::Remove);
+        assert_eq!(config.get_action(&Tag(0x6000, 0x0022)), &Action::Keep);
+    }
 
-        assert_eq!(map.len(), 2);
-        assert_eq!(map.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
+    #[test]
+    fn test_tag_rule_from_pattern_group_only_wildcard() {
+        let rule = TagRule::from_pattern("(50xx,xxxx)").unwrap();
+        assert_eq!(
+            rule,
+            TagRule::GroupMask {
+                mask: 0xFF00,
+                value: 0x5000,
+            }
+        );
+    }
 
-        // Serialize and check format
-        let json = serde_json::to_string(&map).unwrap();
+    #[test]
+    fn test_tag_rule_from_pattern_group_and_element() {
+        let rule = TagRule::from_pattern("(60xx,3000)").unwrap();
         assert_eq!(
-            json,
-            r#"{"(0010,0010)":{"comment":"PatientName","action":"empty"},"(0010,0020)":{"comment":"PatientID","action":"remove"}}"#
+            rule,
+            TagRule::GroupMaskElement {
+                mask: 0xFF00,
+                value: 0x6000,
+                element: 0x3000,
+            }
         );
     }
 
     #[test]
-    fn test_tag_ordering() {
-        let mut map = TagActionMap::new();
+    fn test_tag_rule_from_pattern_rejects_partially_wildcarded_element() {
+        assert!(TagRule::from_pattern("(60xx,30xx)").is_err());
+    }
 
-        // Add tags in non-sequential order
-        map.insert(Tag(0x0020, 0x0010), Action::Empty); // Group 0020 comes after 0010
-        map.insert(Tag(0x0010, 0x0020), Action::Remove); // Element 0020 comes after 0010
-        map.insert(Tag(0x0010, 0x0010), Action::Hash { length: None }); // Should be first
+    #[test]
+    fn test_tag_rule_from_pattern_rejects_malformed_input() {
+        assert!(TagRule::from_pattern("50xx,xxxx").is_err());
+        assert!(TagRule::from_pattern("(50x,xxxx)").is_err());
+        assert!(TagRule::from_pattern("(50xg,xxxx)").is_err());
+    }
 
-        // Convert to tag actions - should be in order
-        let actions: Vec<(Tag, Action)> = map
-            .0
-            .iter()
-            .map(|(tag, action)| (*tag, action.clone()))
-            .collect();
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tag_rule_pattern_round_trips_through_json() {
+        let rule = TagRule::GroupMaskElement {
+            mask: 0xFF00,
+            value: 0x6000,
+            element: 0x3000,
+        };
 
-        // Verify order is by group first, then element
-        assert_eq!(actions[0].0, Tag(0x0010, 0x0010));
-        assert_eq!(actions[1].0, Tag(0x0010, 0x0020));
-        assert_eq!(actions[2].0, Tag(0x0020, 0x0010));
+        let json = serde_json::to_string(&rule).unwrap();
+        assert_eq!(json, r#""(60xx,3000)""#);
 
-        // Serialize and check the string format
-        let json = serde_json::to_string(&map).unwrap();
-        assert_eq!(
-            json,
-            r#"{"(0010,0010)":{"comment":"PatientName","action":"hash"},"(0010,0020)":{"comment":"PatientID","action":"remove"},"(0020,0010)":{"comment":"StudyID","action":"empty"}}"#
-        );
+        let deserialized: TagRule = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, rule);
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_error_handling() {
-        // Test invalid hex digits
-        let json = r#"{"(ZZZZ,0010)":{"action":"empty"}}"#;
-        let result: Result<TagActionMap, _> = serde_json::from_str(json);
-        assert!(result.is_err());
+    fn test_tag_rule_group_range_still_serializes_as_tagged_form() {
+        let rule = TagRule::GroupRange {
+            start: 0x5000,
+            end: 0x50FF,
+        };
+
+        let json = serde_json::to_string(&rule).unwrap();
+        let deserialized: TagRule = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, rule);
     }
 
     #[test]
-    fn test_serialization_with_optional_comment() {
-        let mut map = TagActionMap::new();
+    fn test_tag_pattern_builder_adds_matching_rule() {
+        let config = ConfigBuilder::new()
+            .tag_pattern("(50xx,xxxx)", Action::Remove)
+            .unwrap()
+            .build();
 
-        // Add some tags - one with a known comment, one unknown
-        map.insert(Tag(0x0010, 0x0010), Action::Empty); // Known: PatientName
-        map.insert(Tag(0x9999, 0x9999), Action::Remove); // Unknown
+        assert_eq!(config.get_action(&Tag(0x5010, 0x0011)), &Action::Remove);
+    }
 
-        // Serialize to JSON
-        let json = serde_json::to_string(&map).unwrap();
+    #[test]
+    fn test_tag_pattern_builder_rejects_invalid_pattern() {
+        let result = ConfigBuilder::new().tag_pattern("not a pattern", Action::Remove);
+        assert!(result.is_err());
+    }
 
-        // For the known tag, a comment should be present
-        assert!(json.contains("\"(0010,0010)\":{\"comment\":\"PatientName\",\"action\":\"empty\"}"));
+    #[test]
+    fn test_validate_flags_non_nibble_aligned_group_mask() {
+        let config = ConfigBuilder::new()
+            .tag_rule(
+                TagRule::GroupMask {
+                    mask: 0xFF08,
+                    value: 0x6000,
+                },
+                Action::Remove,
+            )
+            .build();
 
-        // For the unknown tag, the comment should be omitted
-        assert!(json.contains("\"(9999,9999)\":{\"action\":\"remove\"}"));
-        assert!(!json.contains("\"(9999,9999)\":{\"comment\""));
+        let errors = config.validate();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "tag_rules.0");
     }
 
     #[test]
-    fn test_deserialization_with_optional_comment() {
-        // Test with and without comment
-        let json = r#"{
-            "(0010,0010)":{"comment":"PatientName","action":"empty"},
-            "(0010,0020)":{"action":"remove"}
-        }"#;
-
-        // Deserialize
-        let map: TagActionMap = serde_json::from_str(json).unwrap();
+    fn test_tag_rule_private_element_matches_any_private_block() {
+        let config = ConfigBuilder::new()
+            .tag_rule(TagRule::PrivateElement { element: 0x0010 }, Action::Empty);
+        let config = config.build();
 
-        // Both should deserialize correctly
-        assert_eq!(map.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
-        assert_eq!(map.get(&Tag(0x0010, 0x0020)), Some(&Action::Remove));
+        assert_eq!(config.get_action(&Tag(0x0009, 0x0010)), &Action::Empty);
+        assert_eq!(config.get_action(&Tag(0x0033, 0x0010)), &Action::Empty);
+        // a non-matching element in a private group isn't affected
+        assert_eq!(config.get_action(&Tag(0x0009, 0x0011)), &Action::Keep);
     }
 
     #[test]
-    fn test_roundtrip_with_optional_comment() {
-        let mut original = TagActionMap::new();
+    fn test_tag_rule_exact_action_takes_priority_over_rule() {
+        let config = ConfigBuilder::new()
+            .tag_rule(
+                TagRule::GroupRange {
+                    start: 0x5000,
+                    end: 0x50FF,
+                },
+                Action::Remove,
+            )
+            .tag_action(Tag(0x5010, 0x0011), Action::Keep)
+            .build();
 
-        // Add a mix of known and unknown tags
-        original.insert(Tag(0x0010, 0x0010), Action::Empty); // Known
-        original.insert(Tag(0x0008, 0x0050), Action::HashUID); // Known
-        original.insert(Tag(0x9999, 0x9999), Action::Remove); // Unknown
+        assert_eq!(config.get_action(&Tag(0x5010, 0x0011)), &Action::Keep);
+    }
 
-        // Serialize
-        let json = serde_json::to_string(&original).unwrap();
+    #[test]
+    fn test_tag_rule_most_recently_added_wins() {
+        let config = ConfigBuilder::new()
+            .tag_rule(
+                TagRule::GroupRange {
+                    start: 0x5000,
+                    end: 0x50FF,
+                },
+                Action::Remove,
+            )
+            .tag_rule(
+                TagRule::GroupRange {
+                    start: 0x5010,
+                    end: 0x5010,
+                },
+                Action::Keep,
+            )
+            .build();
 
-        // Known tags should have comments
-        assert!(json.contains("\"comment\":\"PatientName\""));
-        assert!(json.contains("\"comment\":\"AccessionNumber\""));
+        assert_eq!(config.get_action(&Tag(0x5010, 0x0000)), &Action::Keep);
+        assert_eq!(config.get_action(&Tag(0x5020, 0x0000)), &Action::Remove);
+    }
 
-        // Unknown tag should not have a comment
-        assert!(!json.contains("\"(9999,9999)\":{\"comment\""));
+    #[test]
+    fn test_vr_action_fallback_used_when_no_tag_action_or_rule_matches() {
+        let config = ConfigBuilder::new()
+            .vr_action(VR::PN, Action::Empty)
+            .build();
+        assert_eq!(
+            config.get_action_for_vr(&tags::PATIENT_NAME, Some(VR::PN)),
+            &Action::Empty
+        );
+    }
 
-        // Deserialize back
-        let deserialized: TagActionMap = serde_json::from_str(&json).unwrap();
+    #[test]
+    fn test_vr_action_fallback_skipped_without_vr() {
+        let config = ConfigBuilder::new()
+            .vr_action(VR::PN, Action::Empty)
+            .build();
+        assert_eq!(config.get_action(&tags::PATIENT_NAME), &Action::Keep);
+    }
 
-        // Verify all actions were preserved
-        assert_eq!(deserialized.get(&Tag(0x0010, 0x0010)), Some(&Action::Empty));
+    #[test]
+    fn test_vr_action_fallback_does_not_match_different_vr() {
+        let config = ConfigBuilder::new()
+            .vr_action(VR::PN, Action::Empty)
+            .build();
         assert_eq!(
-            deserialized.get(&Tag(0x0008, 0x0050)),
-            Some(&Action::HashUID)
+            config.get_action_for_vr(&tags::STUDY_DATE, Some(VR::DA)),
+            &Action::Keep
         );
+    }
+
+    #[test]
+    fn test_tag_action_takes_priority_over_vr_action() {
+        let config = ConfigBuilder::new()
+            .vr_action(VR::PN, Action::Empty)
+            .tag_action(tags::PATIENT_NAME, Action::Keep)
+            .build();
         assert_eq!(
-            deserialized.get(&Tag(0x9999, 0x9999)),
-            Some(&Action::Remove)
+            config.get_action_for_vr(&tags::PATIENT_NAME, Some(VR::PN)),
+            &Action::Keep
         );
     }
 
     #[test]
-    fn test_malformed_json() {
-        // Action field of a wrong type
-        let json = r#"{"(0010,0010)":{"comment":"PatientName","action":123}}"#;
-        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+    fn test_tag_rule_takes_priority_over_vr_action() {
+        let config = ConfigBuilder::new()
+            .vr_action(VR::PN, Action::Empty)
+            .tag_rule(
+                TagRule::GroupRange {
+                    start: 0x0010,
+                    end: 0x0010,
+                },
+                Action::Remove,
+            )
+            .build();
+        assert_eq!(
+            config.get_action_for_vr(&tags::PATIENT_NAME, Some(VR::PN)),
+            &Action::Remove
+        );
+    }
 
-        // Should fail - action is required and must be valid
-        assert!(result.is_err());
+    #[test]
+    fn test_private_tag_default_used_when_vr_unknown() {
+        let config = ConfigBuilder::new()
+            .private_tag_default(Action::Empty)
+            .build();
+
+        let private_tag = Tag(0x0009, 0x0010);
+        assert_eq!(config.get_action_for_vr(&private_tag, None), &Action::Empty);
     }
 
     #[test]
-    fn test_hash_length_error() {
-        // Hash length should be at least 8
-        let json = r#"{"(0010,0010)":{"comment":"PatientName","action":"hash","length":5}}"#;
-        let result: Result<TagActionMap, _> = serde_json::from_str(json);
+    fn test_private_tag_default_skipped_for_non_private_tag() {
+        let config = ConfigBuilder::new()
+            .private_tag_default(Action::Empty)
+            .build();
 
-        // Should fail - hash length must be valid
-        assert!(result.is_err());
-        let error_message = result.unwrap_err().to_string().to_lowercase();
-        assert!(error_message.contains("validation error"));
-        assert!(error_message.contains("length"));
+        // a standard (even-group) tag with no resolvable VR falls back to
+        // the usual should-be-removed/Keep default, not `private_tag_default`
+        assert_eq!(
+            config.get_action_for_vr(&tags::PATIENT_NAME, None),
+            &Action::Keep
+        );
     }
 
-    fn create_sample_tag_actions() -> TagActionMap {
-        let mut map = TagActionMap::new(); // Assuming you have a constructor
-        map.insert(Tag(0x0010, 0x0010), Action::Empty); // Patient Name
-        map.insert(Tag(0x0010, 0x0020), Action::Remove); // Patient ID
-        map.insert(Tag(0x0008, 0x0050), Action::Hash { length: None }); // Accession Number
-        map
+    #[test]
+    fn test_vr_action_takes_priority_over_private_tag_default() {
+        let config = ConfigBuilder::new()
+            .vr_action(VR::LO, Action::Remove)
+            .private_tag_default(Action::Empty)
+            .build();
+
+        let private_tag = Tag(0x0009, 0x0010);
+        assert_eq!(
+            config.get_action_for_vr(&private_tag, Some(VR::LO)),
+            &Action::Remove
+        );
     }
 
     #[test]
-    fn test_config_serialization() {
-        // Create a sample config
-        let config = Config {
-            uid_root: UidRoot("1.2.826.0.1.3680043.10.188".to_string()),
-            tag_actions: create_sample_tag_actions(),
-            remove_private_tags: true,
-            remove_curves: false,
-            remove_overlays: true,
-            ..Default::default()
-        };
+    fn test_private_tag_default_falls_back_to_remove_private_tags_when_unset() {
+        let config = ConfigBuilder::new().remove_private_tags(true).build();
 
-        // Serialize to JSON
-        let json = serde_json::to_string_pretty(&config).unwrap();
+        let private_tag = Tag(0x0009, 0x0010);
+        assert_eq!(
+            config.get_action_for_vr(&private_tag, None),
+            &Action::Remove
+        );
+    }
 
-        // Basic checks on the JSON string
-        assert!(json.contains(r#""uid_root": "1.2.826.0.1.3680043.10.188"#));
-        assert!(json.contains(r#""remove_private_tags": true"#));
-        assert!(json.contains(r#""remove_curves": false"#));
-        assert!(json.contains(r#""remove_overlays": true"#));
+    #[test]
+    fn test_output_transfer_syntax_unset_by_default() {
+        let config = ConfigBuilder::new().build();
+        assert_eq!(config.get_output_transfer_syntax(), None);
+    }
 
-        // Check tag actions serialized correctly
-        assert!(json.contains(r#""(0010,0010)""#)); // Patient Name
-        assert!(json.contains(r#""action": "empty""#));
-        assert!(json.contains(r#""(0010,0020)""#)); // Patient ID
-        assert!(json.contains(r#""action": "remove""#));
-        assert!(json.contains(r#""(0008,0050)""#)); // Accession Number
-        assert!(json.contains(r#""action": "hash""#));
+    #[test]
+    fn test_output_transfer_syntax_returns_configured_value() {
+        let config = ConfigBuilder::new()
+            .output_transfer_syntax(TransferSyntax::ExplicitVrLittleEndian)
+            .build();
+        assert_eq!(
+            config.get_output_transfer_syntax(),
+            Some(&TransferSyntax::ExplicitVrLittleEndian)
+        );
     }
 
     #[test]
-    fn test_config_deserialization() {
-        // JSON representation of config
-        let json = r#"{
-            "uid_root": "1.2.826.0.1.3680043.10.188",
-            "remove_private_tags": true,
-            "remove_curves": false,
-            "remove_overlays": true,
-            "tag_actions": {
-                "(0010,0010)": {"action": "empty"},
-                "(0010,0020)": {"action": "remove"},
-                "(0008,0050)": {"action": "hash"}
-            }
-        }"#;
+    fn test_private_creator_action_builds_policy_lazily() {
+        let config = ConfigBuilder::new()
+            .private_creator_action("SIEMENS CSA HEADER", 0x10, Action::Keep)
+            .build();
+
+        let policy = config.get_private_creator_policy().unwrap();
+        assert_eq!(policy.get_action("SIEMENS CSA HEADER", 0x10), Action::Keep);
+        assert_eq!(
+            policy.get_action("SIEMENS CSA HEADER", 0x11),
+            Action::Remove
+        );
+    }
+
+    #[test]
+    fn test_private_creator_allow_keeps_element_others_removed() {
+        let config = ConfigBuilder::new()
+            .private_creator_allow("SIEMENS CSA HEADER", 0x10)
+            .build();
 
-        // Deserialize to Config
-        let config: Config = serde_json::from_str(json).unwrap();
+        let policy = config.get_private_creator_policy().unwrap();
+        assert_eq!(policy.get_action("SIEMENS CSA HEADER", 0x10), Action::Keep);
+        assert_eq!(policy.get_action("OTHER CREATOR", 0x10), Action::Remove);
+    }
 
-        // Check basic fields
-        assert_eq!(config.uid_root.0, "1.2.826.0.1.3680043.10.188");
-        assert!(config.remove_private_tags);
-        assert!(!config.remove_curves);
-        assert!(config.remove_overlays);
+    #[test]
+    fn test_private_creator_action_and_allow_accumulate_on_same_builder() {
+        let config = ConfigBuilder::new()
+            .private_creator_action("ACME CORP", 0x10, Action::Empty)
+            .private_creator_allow("ACME CORP", 0x11)
+            .build();
 
-        // Check tag actions
-        let patient_name = config.tag_actions.get(&Tag(0x0010, 0x0010)).unwrap();
-        match patient_name {
-            Action::Empty => { /* expected */ }
-            _ => panic!("Expected Empty action for Patient Name"),
-        }
+        let policy = config.get_private_creator_policy().unwrap();
+        assert_eq!(policy.get_action("ACME CORP", 0x10), Action::Empty);
+        assert_eq!(policy.get_action("ACME CORP", 0x11), Action::Keep);
+        assert_eq!(policy.get_action("ACME CORP", 0x12), Action::Remove);
+    }
 
-        let patient_id = config.tag_actions.get(&Tag(0x0010, 0x0020)).unwrap();
-        match patient_id {
-            Action::Remove => { /* expected */ }
-            _ => panic!("Expected Remove action for Patient ID"),
-        }
+    #[test]
+    fn test_basic_profile_matches_default() {
+        assert_eq!(ConfigBuilder::basic_profile(), ConfigBuilder::default());
+    }
 
-        let accession = config.tag_actions.get(&Tag(0x0008, 0x0050)).unwrap();
-        match accession {
-            Action::Hash { length } => {
-                assert_eq!(*length, None);
+    #[test]
+    fn test_retain_uids_keeps_uid_tags() {
+        let config = ConfigBuilder::basic_profile().retain_uids(true).build();
+        assert_eq!(config.get_action(&tags::STUDY_INSTANCE_UID), &Action::Keep);
+        assert_eq!(config.get_action(&tags::SOP_INSTANCE_UID), &Action::Keep);
+        // unrelated tags are unaffected
+        assert_ne!(config.get_action(&tags::PATIENT_NAME), &Action::Keep);
+    }
+
+    #[test]
+    fn test_retain_longitudinal_temporal_full_keeps_dates() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_longitudinal_temporal(TemporalMode::Full)
+            .build();
+        assert_eq!(config.get_action(&tags::STUDY_DATE), &Action::Keep);
+    }
+
+    #[test]
+    fn test_retain_longitudinal_temporal_modified_replaces_hash_date_with_date_shift() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_longitudinal_temporal(TemporalMode::Modified)
+            .build();
+        assert_eq!(
+            config.get_action(&tags::STUDY_DATE),
+            &Action::DateShift {
+                subject_tag: tags::PATIENT_ID,
+                max_offset_days: DEFAULT_DATE_SHIFT_MAX_OFFSET_DAYS,
             }
-            _ => panic!("Expected Hash action for Accession Number"),
-        }
+        );
     }
 
     #[test]
-    fn test_config_roundtrip() {
-        // Create original config
-        let original_config = Config {
-            uid_root: UidRoot("1.2.826.0.1.3680043.10.188".to_string()),
-            tag_actions: create_sample_tag_actions(),
-            remove_private_tags: true,
-            remove_curves: false,
-            remove_overlays: true,
-            ..Default::default()
-        };
+    fn test_retain_patient_characteristics_keeps_matching_tags() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_patient_characteristics(true)
+            .build();
+        assert_eq!(config.get_action(&tags::PATIENT_AGE), &Action::Keep);
+        assert_eq!(config.get_action(&tags::PATIENT_SEX), &Action::Keep);
+    }
 
-        // Serialize to JSON and back
-        let json = serde_json::to_string(&original_config).unwrap();
-        let deserialized: Config = serde_json::from_str(&json).unwrap();
+    #[test]
+    fn test_retain_device_identity_keeps_matching_tags() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_device_identity(true)
+            .build();
+        assert_eq!(config.get_action(&tags::MANUFACTURER), &Action::Keep);
+        assert_eq!(config.get_action(&tags::STATION_NAME), &Action::Keep);
+    }
 
-        // Compare UID root
-        assert_eq!(original_config.uid_root.0, deserialized.uid_root.0);
+    #[test]
+    fn test_clean_descriptors_replaces_matching_tags() {
+        let config = ConfigBuilder::basic_profile()
+            .clean_descriptors(true)
+            .build();
+        assert_eq!(
+            config.get_action(&tags::STUDY_DESCRIPTION),
+            &Action::Replace {
+                value: "ANONYMIZED".into()
+            }
+        );
+    }
 
-        // Compare boolean flags
+    #[test]
+    fn test_retain_institution_identity_keeps_matching_tags() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_institution_identity(true)
+            .build();
+        assert_eq!(config.get_action(&tags::INSTITUTION_NAME), &Action::Keep);
+        assert_eq!(config.get_action(&tags::INSTITUTION_ADDRESS), &Action::Keep);
+    }
+
+    #[test]
+    fn test_basic_profile_populates_deidentification_method_code_sequence() {
+        let config = ConfigBuilder::basic_profile().build();
         assert_eq!(
-            original_config.remove_private_tags,
-            deserialized.remove_private_tags
+            config.get_action(&tags::DEIDENTIFICATION_METHOD_CODE_SEQUENCE),
+            &Action::Keep
         );
-        assert_eq!(original_config.remove_curves, deserialized.remove_curves);
+        assert_eq!(config.get_profile_codes(), &["113100"]);
+    }
+
+    #[test]
+    fn test_combined_profile_options_produce_sorted_deduped_code_sequence() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_uids(true)
+            .retain_patient_characteristics(true)
+            .build();
         assert_eq!(
-            original_config.remove_overlays,
-            deserialized.remove_overlays
+            config.get_action(&tags::DEIDENTIFICATION_METHOD_CODE_SEQUENCE),
+            &Action::Keep
         );
+        assert_eq!(config.get_profile_codes(), &["113100", "113108", "113110"]);
+    }
 
-        // Compare tag actions
-        let tags_to_check = [
-            Tag(0x0010, 0x0010), // Patient Name
-            Tag(0x0010, 0x0020), // Patient ID
-            Tag(0x0008, 0x0050), // Accession Number
-        ];
+    #[test]
+    fn test_retain_institution_identity_does_not_affect_code_sequence() {
+        let config = ConfigBuilder::basic_profile()
+            .retain_institution_identity(true)
+            .build();
+        assert_eq!(
+            config.get_action(&tags::DEIDENTIFICATION_METHOD_CODE_SEQUENCE),
+            &Action::Keep
+        );
+        assert_eq!(config.get_profile_codes(), &["113100"]);
+    }
 
-        for tag in &tags_to_check {
-            let original_action = original_config.tag_actions.get(tag);
-            let deserialized_action = deserialized.tag_actions.get(tag);
+    #[test]
+    fn test_tag_hash_fn_overrides_default_hash_fn() {
+        let config = ConfigBuilder::new()
+            .keyed_hash_fn("institution-secret")
+            .tag_hash_fn(tags::PATIENT_ID, HashFn::keyed("patient-id-pepper"))
+            .build();
 
-            assert_eq!(
-                original_action, deserialized_action,
-                "Action for tag ({}) didn't roundtrip correctly",
-                tag,
-            );
-        }
+        let default_result = config.get_hash_fn().call("203087");
+        let overridden_result = config.get_hash_fn_for(&tags::PATIENT_ID).call("203087");
+        assert_ne!(default_result, overridden_result);
     }
 
     #[test]
-    fn test_empty_tag_actions() {
-        // Create a config with empty tag actions
-        let empty_map = TagActionMap::new();
-        let config = Config {
-            uid_root: UidRoot("1.2.826.0.1.3680043.10.188".to_string()),
-            tag_actions: empty_map,
-            ..Default::default()
-        };
-
-        // Serialize and deserialize
-        let json = serde_json::to_string(&config).unwrap();
-        let deserialized: Config = serde_json::from_str(&json).unwrap();
+    fn test_get_hash_fn_for_falls_back_to_default() {
+        let config = ConfigBuilder::new()
+            .keyed_hash_fn("institution-secret")
+            .build();
 
-        assert_eq!(deserialized.uid_root.0, "1.2.826.0.1.3680043.10.188");
-        assert!(!deserialized.remove_private_tags);
-        assert!(!deserialized.remove_curves);
-        assert!(!deserialized.remove_overlays);
-        assert_eq!(deserialized.tag_actions.len(), 0);
+        let default_result = config.get_hash_fn().call("203087");
+        let fallback_result = config.get_hash_fn_for(&tags::PATIENT_NAME).call("203087");
+        assert_eq!(default_result, fallback_result);
     }
 
     #[test]
-    fn test_partial_config_deserialization() {
-        let json = r#"{
-            "uid_root": "1.2.826.0.1.3680043.10.188",
-            "tag_actions": {
-                "(0010,0010)": {"action": "empty"}
-            }
-        }"#;
+    fn test_uid_mapper_defaults_to_unset() {
+        let config = ConfigBuilder::new().build();
+        assert!(config.get_uid_mapper().is_none());
+    }
 
-        let result: Result<Config, _> = serde_json::from_str(json);
-        let config = result.unwrap();
+    #[test]
+    fn test_uid_mapper_is_shared_through_the_builder() {
+        let mapper = std::sync::Arc::new(crate::uid_mapper::UidMapper::new(UidRoot::default()));
+        let config = ConfigBuilder::new().uid_mapper(mapper.clone()).build();
 
-        assert_eq!(config.uid_root.0, "1.2.826.0.1.3680043.10.188");
-        assert!(!config.remove_private_tags);
-        assert!(!config.remove_curves);
-        assert!(!config.remove_overlays);
-        assert_eq!(config.tag_actions.len(), 1);
+        let mapped = config.get_uid_mapper().unwrap().map("1.2.3.4.5");
+        assert_eq!(mapper.map("1.2.3.4.5"), mapped);
     }
 
     #[test]
-    fn test_empty_uid_root_and_tag_actions() {
-        let json = r#"{
-            "uid_root": "",
-            "remove_private_tags": true,
-            "remove_curves": false,
-            "remove_overlays": true,
-            "tag_actions": {}
-        }"#;
+    fn test_hash_uid_is_consistent_with_or_without_a_shared_mapper() {
+        // Action::HashUID + UidMapper replaces this crate's old RemapUid
+        // action, which always consulted a single shared mapper. This
+        // confirms RemapUid's guarantee - the same original UID always maps
+        // to the same replacement - still holds for HashUID whether or not a
+        // Config actually has a UidMapper configured.
+        use crate::actions::Action;
+        use crate::tags;
+        use dicom_core::value::Value;
+        use dicom_core::VR;
+        use dicom_object::mem::InMemElement;
+        use dicom_object::FileDicomObject;
+
+        let obj = FileDicomObject::new_empty_with_meta(crate::test_utils::make_file_meta());
+        let elem = InMemElement::new(tags::SOP_INSTANCE_UID, VR::UI, Value::from("1.2.3.4.5"));
+        let action_struct = Action::HashUID.get_action_struct();
+
+        let no_mapper_config = ConfigBuilder::new().build();
+        let first = action_struct
+            .process(&no_mapper_config, &obj, &elem)
+            .unwrap()
+            .unwrap();
+        let second = action_struct
+            .process(&no_mapper_config, &obj, &elem)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.into_owned(), second.into_owned());
 
-        let result: Result<Config, _> = serde_json::from_str(json);
-        let config = result.unwrap();
+        let mapper = std::sync::Arc::new(crate::uid_mapper::UidMapper::new(UidRoot::default()));
+        let shared_mapper_config = ConfigBuilder::new().uid_mapper(mapper).build();
+        let with_mapper = action_struct
+            .process(&shared_mapper_config, &obj, &elem)
+            .unwrap()
+            .unwrap();
 
-        assert_eq!(config.uid_root.0, "");
-        assert!(config.remove_private_tags);
-        assert!(!config.remove_curves);
-        assert!(config.remove_overlays);
-        assert_eq!(config.tag_actions.len(), 0);
+        let without_mapper = action_struct
+            .process(&no_mapper_config, &obj, &elem)
+            .unwrap()
+            .unwrap();
+        assert_eq!(with_mapper.into_owned(), without_mapper.into_owned());
     }
 
     #[test]
-    fn test_missing_uid_root() {
-        let json = r#"{
-            "remove_private_tags": true,
-            "remove_curves": false,
-            "remove_overlays": true,
-            "tag_actions": {}
-        }"#;
-
-        let result: Result<Config, _> = serde_json::from_str(json);
-        let config = result.unwrap();
+    fn test_profile_codes_defaults_to_empty() {
+        let config = ConfigBuilder::new().build();
+        assert!(config.get_profile_codes().is_empty());
+    }
 
-        assert_eq!(config.uid_root.0, "");
-        assert!(config.remove_private_tags);
-        assert!(!config.remove_curves);
-        assert!(config.remove_overlays);
-        assert_eq!(config.tag_actions.len(), 0);
+    #[test]
+    fn test_profile_codes_are_recorded_by_basic_profile() {
+        let config = ConfigBuilder::basic_profile().retain_uids(true).build();
+        assert_eq!(config.get_profile_codes(), &["113100", "113110"]);
     }
 
     #[test]
-    fn test_default_remove_fields() {
-        let json = r#"{
-            "uid_root": "9999",
-            "tag_actions": {}
-        }"#;
+    fn test_keyed_hash_fn_is_deterministic() {
+        let config = ConfigBuilder::new()
+            .keyed_hash_fn("institution-secret")
+            .build();
+        assert_eq!(
+            config.get_hash_fn().call("203087"),
+            config.get_hash_fn().call("203087")
+        );
+    }
 
-        let result: Result<Config, _> = serde_json::from_str(json);
-        let config = result.unwrap();
+    #[test]
+    fn test_keyed_hash_fn_with_algorithm_selects_matching_hash_fn() {
+        let config = ConfigBuilder::new()
+            .keyed_hash_fn_with_algorithm("institution-secret", HashAlgorithm::Sha256)
+            .build();
 
-        assert_eq!(config.uid_root.0, "9999");
-        assert!(!config.remove_private_tags);
-        assert!(!config.remove_curves);
-        assert!(!config.remove_overlays);
-        assert_eq!(config.tag_actions.len(), 0);
+        assert_eq!(config.get_hash_algorithm(), HashAlgorithm::Sha256);
+        assert_eq!(
+            config.get_hash_fn().call("203087"),
+            HashFn::keyed_with("institution-secret", KeyedDigest::Sha256).call("203087")
+        );
     }
 
     #[test]
-    fn test_only_empty_tag_actions() {
-        let json = r#"{
-            "tag_actions": {}
-        }"#;
+    fn test_keyed_hash_fn_with_algorithm_differs_from_unkeyed() {
+        let config = ConfigBuilder::new()
+            .keyed_hash_fn_with_algorithm("institution-secret", HashAlgorithm::Sha256)
+            .build();
 
-        let result: Result<Config, _> = serde_json::from_str(json);
-        let config = result.unwrap();
+        assert_ne!(
+            config.get_hash_fn().call("203087"),
+            sha256_hash_fn("203087")
+        );
+    }
 
-        assert_eq!(config.uid_root.0, "");
-        assert!(!config.remove_private_tags);
-        assert!(!config.remove_curves);
-        assert!(!config.remove_overlays);
-        assert_eq!(config.tag_actions.len(), 0);
+    #[test]
+    fn test_default_hash_algorithm_is_blake3() {
+        let config = Config::default();
+        assert_eq!(config.get_hash_algorithm(), HashAlgorithm::Blake3);
+        assert_eq!(
+            config.get_hash_fn().call("203087"),
+            blake3_hash_fn("203087")
+        );
     }
 
     #[test]
-    fn test_malformed_config() {
-        // Invalid tag format
-        let json = r#"{
-            "uid_root": "1.2.826.0.1.3680043.10.188",
-            "remove_private_tags": true,
-            "remove_curves": false,
-            "remove_overlays": true,
-            "tag_actions": {
-                "invalid_tag_format": {"action": "empty"}
-            }
-        }"#;
+    fn test_hash_algorithm_builder_selects_matching_hash_fn() {
+        let config = ConfigBuilder::new()
+            .hash_algorithm(HashAlgorithm::Sha256)
+            .build();
 
-        let result: Result<Config, _> = serde_json::from_str(json);
-        assert!(result.is_err());
+        assert_eq!(config.get_hash_algorithm(), HashAlgorithm::Sha256);
+        assert_eq!(
+            config.get_hash_fn().call("203087"),
+            sha256_hash_fn("203087")
+        );
+    }
 
-        // Invalid action
-        let json = r#"{
-            "uid_root": "1.2.826.0.1.3680043.10.188",
-            "remove_private_tags": true,
-            "remove_curves": false,
-            "remove_overlays": true,
-            "tag_actions": {
-                "(0010,0010)": {"action": "invalid_action"}
-            },
-        }"#;
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_hash_algorithm_round_trips_through_json() {
+        let config = ConfigBuilder::new()
+            .hash_algorithm(HashAlgorithm::Sha512)
+            .build();
 
-        let result: Result<Config, _> = serde_json::from_str(json);
-        assert!(result.is_err());
+        let json = serde_json::to_string(&config).unwrap();
+        let restored: Config = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.get_hash_algorithm(), HashAlgorithm::Sha512);
+        assert_eq!(
+            restored.get_hash_fn().call("203087"),
+            sha512_hash_fn("203087")
+        );
     }
 }
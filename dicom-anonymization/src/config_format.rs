@@ -0,0 +1,279 @@
+// Every loader/writer in this module round-trips a `Config` through `serde`,
+// so (unlike `crate::config` itself) it has no meaningful behavior with the
+// `serde` cargo feature off; it relies on that feature being enabled, which
+// it is by default.
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::Config;
+
+/// The serialization format a [`Config`] document is authored in.
+///
+/// JSON remains the format every other loader in this crate (and
+/// [`Config`]'s own `Deserialize` impl) assumes by default; this module adds
+/// the others as an opt-in so a profile can be hand-authored in whichever
+/// format is most convenient to annotate and review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Ron,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Guesses the format from `path`'s extension, falling back to
+    /// [`ConfigFormat::Json`] when the extension is missing or unrecognized -
+    /// matching the format [`Config`]'s `Deserialize` impl assumes everywhere
+    /// else in this crate.
+    fn from_extension(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("ron") => ConfigFormat::Ron,
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                ConfigFormat::Yaml
+            }
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigFormatError {
+    #[error("failed to read config file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {format:?} config: {reason}")]
+    Parse {
+        format: ConfigFormat,
+        reason: String,
+    },
+
+    #[error("failed to read config: {0}")]
+    Read(std::io::Error),
+}
+
+/// A TOML document can't hold [`Config`] directly at its root: TOML requires
+/// every scalar field to be written before any table field, but
+/// `tag_actions` (and the other map fields) are tables that sit ahead of
+/// later scalar fields like `date_shift_key` in [`Config`]'s own field order.
+/// Nesting the whole document under a named `config` table sidesteps the
+/// restriction instead of reordering [`Config`]'s fields just for one format.
+#[derive(Serialize, Deserialize)]
+struct TomlDocument {
+    config: Config,
+}
+
+/// Parses `input` as a [`Config`] document written in `format`.
+///
+/// RON and YAML parse into the same shape JSON does, reusing [`Config`]'s own
+/// `Deserialize` impl (and, transitively, [`crate::config::TagActionMap`]'s
+/// tag-format and action validation) unchanged. TOML is the exception: the
+/// document must nest the config under a top-level `config` table, per
+/// [`TomlDocument`].
+pub fn load_config(input: &str, format: ConfigFormat) -> Result<Config, ConfigFormatError> {
+    match format {
+        ConfigFormat::Json => serde_json::from_str(input).map_err(|e| ConfigFormatError::Parse {
+            format,
+            reason: e.to_string(),
+        }),
+        ConfigFormat::Ron => ron::from_str(input).map_err(|e| ConfigFormatError::Parse {
+            format,
+            reason: e.to_string(),
+        }),
+        ConfigFormat::Yaml => serde_yaml::from_str(input).map_err(|e| ConfigFormatError::Parse {
+            format,
+            reason: e.to_string(),
+        }),
+        ConfigFormat::Toml => toml::from_str::<TomlDocument>(input)
+            .map(|doc| doc.config)
+            .map_err(|e| ConfigFormatError::Parse {
+                format,
+                reason: e.to_string(),
+            }),
+    }
+}
+
+/// Reads `path` and parses it as a [`Config`], guessing the format from the
+/// file extension (see [`ConfigFormat::from_extension`]).
+pub fn load_config_file(path: &Path) -> Result<Config, ConfigFormatError> {
+    let format = ConfigFormat::from_extension(path);
+    let input = fs::read_to_string(path).map_err(|source| ConfigFormatError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+
+    load_config(&input, format)
+}
+
+/// Serializes `config` as a document in `format`, the inverse of
+/// [`load_config`]. TOML wraps the config under a `config` table for the
+/// same reason [`load_config`] expects one back - see [`TomlDocument`].
+pub fn config_to_string(
+    config: &Config,
+    format: ConfigFormat,
+) -> Result<String, ConfigFormatError> {
+    match format {
+        ConfigFormat::Json => {
+            serde_json::to_string_pretty(config).map_err(|e| ConfigFormatError::Parse {
+                format,
+                reason: e.to_string(),
+            })
+        }
+        ConfigFormat::Ron => ron::ser::to_string_pretty(config, ron::ser::PrettyConfig::default())
+            .map_err(|e| ConfigFormatError::Parse {
+                format,
+                reason: e.to_string(),
+            }),
+        ConfigFormat::Yaml => serde_yaml::to_string(config).map_err(|e| ConfigFormatError::Parse {
+            format,
+            reason: e.to_string(),
+        }),
+        ConfigFormat::Toml => {
+            let doc = TomlDocument {
+                config: config.clone(),
+            };
+            toml::to_string_pretty(&doc).map_err(|e| ConfigFormatError::Parse {
+                format,
+                reason: e.to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::actions::Action;
+    use dicom_dictionary_std::tags;
+
+    #[test]
+    fn test_loads_plain_json() {
+        let config = load_config(r#"{"uid_root": "1.2.3"}"#, ConfigFormat::Json).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+    }
+
+    #[test]
+    fn test_loads_ron() {
+        let input = r#"(
+            uid_root: "1.2.3",
+            tag_actions: {
+                "(0010,0010)": (action: "remove"),
+            },
+        )"#;
+
+        let config = load_config(input, ConfigFormat::Ron).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+        assert_eq!(
+            config.get_tag_actions().get(&tags::PATIENT_NAME),
+            Some(&Action::Remove)
+        );
+    }
+
+    #[test]
+    fn test_loads_yaml() {
+        let input = "uid_root: \"1.2.3\"\ntag_actions:\n  \"(0010,0010)\":\n    action: remove\n";
+
+        let config = load_config(input, ConfigFormat::Yaml).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+        assert_eq!(
+            config.get_tag_actions().get(&tags::PATIENT_NAME),
+            Some(&Action::Remove)
+        );
+    }
+
+    #[test]
+    fn test_loads_toml_wrapped_under_config_table() {
+        let input = "[config]\nuid_root = \"1.2.3\"\n\n[config.tag_actions.\"(0010,0010)\"]\naction = \"remove\"\n";
+
+        let config = load_config(input, ConfigFormat::Toml).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+        assert_eq!(
+            config.get_tag_actions().get(&tags::PATIENT_NAME),
+            Some(&Action::Remove)
+        );
+    }
+
+    #[test]
+    fn test_toml_without_config_table_is_an_error() {
+        let input = "uid_root = \"1.2.3\"\n";
+        let result = load_config(input, ConfigFormat::Toml);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_extension_detects_each_format() {
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("profile.ron")),
+            ConfigFormat::Ron
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("profile.yaml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("profile.yml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("profile.toml")),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("profile.json")),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("profile")),
+            ConfigFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_load_config_file_reads_and_parses() {
+        let path = std::env::temp_dir().join(format!(
+            "dicom_anonymization_config_format_test_{:?}.yaml",
+            std::thread::current().id()
+        ));
+        fs::write(&path, "uid_root: \"1.2.3\"\n").unwrap();
+
+        let config = load_config_file(&path).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_tag_key_round_trips_through_every_format() {
+        let config = crate::config::ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Remove)
+            .build();
+
+        for format in [
+            ConfigFormat::Json,
+            ConfigFormat::Ron,
+            ConfigFormat::Yaml,
+            ConfigFormat::Toml,
+        ] {
+            let serialized = config_to_string(&config, format).unwrap();
+            assert!(
+                serialized.contains("(0010,0010)"),
+                "{format:?} output did not contain the tag key: {serialized}"
+            );
+
+            let round_tripped = load_config(&serialized, format).unwrap();
+            assert_eq!(
+                round_tripped.get_tag_actions().get(&tags::PATIENT_NAME),
+                Some(&Action::Remove),
+                "{format:?} failed to round-trip the tag key"
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use dicom_core::header::Header;
+use dicom_core::value::Value;
+use dicom_core::Tag;
+use dicom_dictionary_std::tags;
+use dicom_object::mem::InMemElement;
+use std::collections::HashMap;
+
+/// The Directory Record Sequence tag (0004,1220) under which a DICOMDIR's
+/// patient/study/series/instance records are nested.
+pub const DIRECTORY_RECORD_SEQUENCE: Tag = tags::DIRECTORY_RECORD_SEQUENCE;
+
+/// Tags within a Directory Record item that reference another SOP Instance,
+/// rather than describing the record itself, and so must be rewritten through
+/// the same [`UidMappingTable`] used to anonymize the instance they point at
+/// instead of through the record's own `tag_actions` policy.
+const REFERENCED_UID_TAGS: &[Tag] = &[tags::REFERENCED_SOP_INSTANCE_UID_IN_FILE];
+
+/// A shared, consistent original-UID-to-new-UID mapping.
+///
+/// A single [`UidMappingTable`] is meant to be threaded through both the
+/// per-instance anonymization pass (wherever `Action::HashUID` derives a new
+/// UID) and the DICOMDIR pass, so a `ReferencedSOPInstanceUIDInFile`
+/// (0004,1511) in the directory still resolves to the instance it used to
+/// reference after that instance's own `SOPInstanceUID` has been remapped.
+#[derive(Debug, Clone, Default)]
+pub struct UidMappingTable(HashMap<String, String>);
+
+impl UidMappingTable {
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Returns the UID already mapped to `original`, computing and
+    /// remembering one via `derive` the first time `original` is seen.
+    pub fn get_or_insert_with(
+        &mut self,
+        original: &str,
+        derive: impl FnOnce() -> String,
+    ) -> String {
+        self.0
+            .entry(original.to_string())
+            .or_insert_with(derive)
+            .clone()
+    }
+
+    /// Returns the UID already mapped to `original`, if any.
+    pub fn get(&self, original: &str) -> Option<&str> {
+        self.0.get(original).map(String::as_str)
+    }
+}
+
+/// Rewrites a Directory Record element that references another SOP Instance
+/// so it points at that instance's already-mapped UID.
+///
+/// Elements other than [`REFERENCED_UID_TAGS`] are returned unchanged here;
+/// callers should otherwise process a Directory Record item's elements
+/// through the usual `tag_actions` policy, exactly like a regular dataset.
+/// Since a directory record is ordinarily written out *after* the instances
+/// it references have already been anonymized, `uid_mapping` is expected to
+/// already hold an entry for `original`; if it doesn't, the element is left
+/// unchanged rather than inventing a mapping out of order.
+pub fn remap_referenced_uid(elem: &InMemElement, uid_mapping: &UidMappingTable) -> InMemElement {
+    if !REFERENCED_UID_TAGS.contains(&elem.tag()) {
+        return elem.clone();
+    }
+
+    let Ok(original) = elem.value().to_str() else {
+        return elem.clone();
+    };
+    let original = original.trim_end_matches('\0');
+
+    match uid_mapping.get(original) {
+        Some(mapped) => InMemElement::new(elem.tag(), elem.vr(), Value::from(mapped.to_string())),
+        None => elem.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+
+    #[test]
+    fn test_uid_mapping_table_is_consistent_for_same_original() {
+        let mut table = UidMappingTable::new();
+        let first = table.get_or_insert_with("1.2.3", || "9999.1".to_string());
+        let second = table.get_or_insert_with("1.2.3", || "9999.2".to_string());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_uid_mapping_table_differs_by_original() {
+        let mut table = UidMappingTable::new();
+        let a = table.get_or_insert_with("1.2.3", || "9999.1".to_string());
+        let b = table.get_or_insert_with("1.2.4", || "9999.2".to_string());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_uid_mapping_table_get_before_insert_is_none() {
+        let table = UidMappingTable::new();
+        assert_eq!(table.get("1.2.3"), None);
+    }
+
+    #[test]
+    fn test_remap_referenced_uid_rewrites_mapped_uid() {
+        let mut uid_mapping = UidMappingTable::new();
+        uid_mapping.get_or_insert_with("1.2.3", || "9999.1".to_string());
+
+        let elem = InMemElement::new(
+            tags::REFERENCED_SOP_INSTANCE_UID_IN_FILE,
+            VR::UI,
+            Value::from("1.2.3"),
+        );
+        let remapped = remap_referenced_uid(&elem, &uid_mapping);
+        assert_eq!(remapped.value(), &Value::from("9999.1"));
+    }
+
+    #[test]
+    fn test_remap_referenced_uid_leaves_unmapped_uid_unchanged() {
+        let uid_mapping = UidMappingTable::new();
+        let elem = InMemElement::new(
+            tags::REFERENCED_SOP_INSTANCE_UID_IN_FILE,
+            VR::UI,
+            Value::from("1.2.3"),
+        );
+        let remapped = remap_referenced_uid(&elem, &uid_mapping);
+        assert_eq!(remapped.value(), &Value::from("1.2.3"));
+    }
+
+    #[test]
+    fn test_remap_referenced_uid_leaves_unrelated_tag_unchanged() {
+        let uid_mapping = UidMappingTable::new();
+        let elem = InMemElement::new(tags::PATIENT_NAME, VR::PN, Value::from("Doe^John"));
+        let remapped = remap_referenced_uid(&elem, &uid_mapping);
+        assert_eq!(remapped.value(), &Value::from("Doe^John"));
+    }
+}
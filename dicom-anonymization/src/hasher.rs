@@ -0,0 +1,197 @@
+use std::fmt;
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+
+/// A hash function used by [`crate::config::Config`] for operations requiring
+/// hashing, such as generating new UIDs (`Action::HashUID`) and hashing
+/// identifier values (`Action::Hash`).
+///
+/// Wraps either a plain function like [`blake3_hash_fn`] or a keyed closure
+/// (see [`HashFn::keyed`]) behind one callable type, so [`crate::config::Config`]
+/// and [`crate::config::ConfigBuilder`] can treat both the same way.
+#[derive(Clone)]
+pub struct HashFn(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl HashFn {
+    /// Wraps BLAKE3's keyed hash mode: `key` is hashed down to a 32-byte key
+    /// that seeds every hash, so hashed identifiers are stable for callers who
+    /// share the key but not linkable across callers who don't, and can't be
+    /// recomputed by anyone who doesn't know it. This defeats the
+    /// dictionary/rainbow-table attacks possible against plain, unsalted
+    /// `blake3_hash_fn`, the same way the date-shift day offset and
+    /// [`crate::actions::pseudonymize`]'s keystream key their own BLAKE3
+    /// usage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::hasher::HashFn;
+    ///
+    /// let hash_fn = HashFn::keyed("institution-secret");
+    /// assert_eq!(hash_fn.call("identifier"), hash_fn.call("identifier"));
+    /// ```
+    pub fn keyed(key: impl Into<String>) -> Self {
+        let key = key.into();
+        let derived_key = *blake3::hash(key.as_bytes()).as_bytes();
+        HashFn(Arc::new(move |input: &str| {
+            blake3::keyed_hash(&derived_key, input.as_bytes())
+                .to_hex()
+                .to_string()
+        }))
+    }
+
+    /// Builds a keyed [`HashFn`] over one of [`KeyedDigest`]'s digests using a
+    /// real HMAC construction (via the `hmac` crate), for sites whose approved
+    /// algorithm isn't BLAKE3 but who still need a keyed hasher; see
+    /// [`crate::config::HashAlgorithm::keyed_hash_fn`] for a version selected
+    /// by [`crate::config::HashAlgorithm`] instead of a raw [`KeyedDigest`].
+    ///
+    /// This exists because naive secret-prefix keying (`hash_fn(key + input)`)
+    /// is vulnerable to length-extension attacks against Merkle-Damgard hashes
+    /// like SHA-256/SHA-512 - HMAC is the standard, safe way to key them.
+    pub fn keyed_with(key: impl Into<String>, digest: KeyedDigest) -> Self {
+        let key = key.into();
+        HashFn(Arc::new(move |input: &str| match digest {
+            KeyedDigest::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+                    .expect("HMAC accepts a key of any length");
+                mac.update(input.as_bytes());
+                hex::encode(mac.finalize().into_bytes())
+            }
+            KeyedDigest::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(key.as_bytes())
+                    .expect("HMAC accepts a key of any length");
+                mac.update(input.as_bytes());
+                hex::encode(mac.finalize().into_bytes())
+            }
+        }))
+    }
+
+    pub(crate) fn call(&self, input: &str) -> String {
+        (self.0)(input)
+    }
+}
+
+impl<F> From<F> for HashFn
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    fn from(f: F) -> Self {
+        HashFn(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for HashFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HashFn(..)")
+    }
+}
+
+/// Digest algorithms [`HashFn::keyed_with`] can build a real HMAC over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyedDigest {
+    Sha256,
+    Sha512,
+}
+
+impl PartialEq for HashFn {
+    // The wrapped closure is runtime behavior, not structural configuration, so
+    // equality never distinguishes two otherwise-identical `Config`s by it.
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Hashes `input` with BLAKE3 and returns the digest as a lowercase hex string.
+pub fn blake3_hash_fn(input: &str) -> String {
+    blake3::hash(input.as_bytes()).to_hex().to_string()
+}
+
+/// Hashes `input` with SHA-256 and returns the digest as a lowercase hex
+/// string, for sites that need to match an existing pipeline's digest choice
+/// rather than BLAKE3 (see [`crate::config::HashAlgorithm`]).
+pub fn sha256_hash_fn(input: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(input.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Hashes `input` with SHA-512 and returns the digest as a lowercase hex
+/// string; see [`sha256_hash_fn`].
+pub fn sha512_hash_fn(input: &str) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(input.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blake3_hash_fn_is_deterministic() {
+        assert_eq!(blake3_hash_fn("abc"), blake3_hash_fn("abc"));
+    }
+
+    #[test]
+    fn test_blake3_hash_fn_differs_by_input() {
+        assert_ne!(blake3_hash_fn("abc"), blake3_hash_fn("def"));
+    }
+
+    #[test]
+    fn test_keyed_hash_fn_differs_by_key() {
+        let a = HashFn::keyed("key-a");
+        let b = HashFn::keyed("key-b");
+        assert_ne!(a.call("identifier"), b.call("identifier"));
+    }
+
+    #[test]
+    fn test_keyed_hash_fn_differs_from_unkeyed() {
+        let keyed = HashFn::keyed("key-a");
+        assert_ne!(keyed.call("identifier"), blake3_hash_fn("identifier"));
+    }
+
+    #[test]
+    fn test_keyed_with_differs_by_key() {
+        let a = HashFn::keyed_with("key-a", KeyedDigest::Sha256);
+        let b = HashFn::keyed_with("key-b", KeyedDigest::Sha256);
+        assert_ne!(a.call("identifier"), b.call("identifier"));
+    }
+
+    #[test]
+    fn test_keyed_with_differs_from_unkeyed() {
+        let keyed = HashFn::keyed_with("key-a", KeyedDigest::Sha256);
+        assert_ne!(keyed.call("identifier"), sha256_hash_fn("identifier"));
+    }
+
+    #[test]
+    fn test_keyed_with_differs_by_digest() {
+        let sha256 = HashFn::keyed_with("key-a", KeyedDigest::Sha256);
+        let sha512 = HashFn::keyed_with("key-a", KeyedDigest::Sha512);
+        assert_ne!(sha256.call("identifier"), sha512.call("identifier"));
+    }
+
+    #[test]
+    fn test_hash_fn_from_plain_function() {
+        let hash_fn: HashFn = blake3_hash_fn.into();
+        assert_eq!(hash_fn.call("abc"), blake3_hash_fn("abc"));
+    }
+
+    #[test]
+    fn test_sha256_hash_fn_is_deterministic() {
+        assert_eq!(sha256_hash_fn("abc"), sha256_hash_fn("abc"));
+    }
+
+    #[test]
+    fn test_sha512_hash_fn_is_deterministic() {
+        assert_eq!(sha512_hash_fn("abc"), sha512_hash_fn("abc"));
+    }
+
+    #[test]
+    fn test_sha256_and_sha512_differ_from_blake3() {
+        assert_ne!(sha256_hash_fn("abc"), blake3_hash_fn("abc"));
+        assert_ne!(sha512_hash_fn("abc"), blake3_hash_fn("abc"));
+    }
+}
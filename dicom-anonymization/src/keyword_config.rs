@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use dicom_core::dictionary::TagRange;
+use dicom_core::{DataDictionary, Tag};
+use dicom_dictionary_std::StandardDataDictionary;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::Action;
+use crate::config::{ConfigError, TagActionMap};
+
+/// Resolves `keyword` (a standard DICOM keyword, e.g. `"PatientID"`) to the
+/// [`Tag`] the built-in data dictionary associates with it.
+///
+/// Returns `None` for keywords the dictionary doesn't know, and for keywords
+/// that name a repeating tag range (e.g. overlay group tags), since those
+/// don't resolve to a single [`Tag`].
+fn resolve_tag_by_keyword(keyword: &str) -> Option<Tag> {
+    let entry = StandardDataDictionary.by_name(keyword)?;
+    match entry.tag {
+        TagRange::Single(tag) => Some(tag),
+        _ => None,
+    }
+}
+
+/// Looks up the standard keyword that [`resolve_tag_by_keyword`] would
+/// resolve back to `tag`, if the dictionary has one.
+fn resolve_keyword_by_tag(tag: &Tag) -> Option<&'static str> {
+    let entry = StandardDataDictionary.by_tag(*tag)?;
+    Some(entry.alias)
+}
+
+/// Returns `tag`'s standard keyword, or its `(gggg,eeee)` hex form if the
+/// dictionary has no keyword for it, so every [`Tag`] round-trips through a
+/// [`KeywordConfig`] document even when it isn't a named standard tag.
+fn keyword_or_hex(tag: &Tag) -> String {
+    resolve_keyword_by_tag(tag)
+        .map(String::from)
+        .unwrap_or_else(|| format!("{tag}"))
+}
+
+/// Mirrors [`Action`] field-for-field, but references other tags by their
+/// standard keyword (e.g. `"PatientID"`) instead of a raw [`Tag`], and uses
+/// serde's default externally-tagged representation: a unit variant like
+/// [`Self::Remove`] serializes as the bare string `"Remove"`, and a variant
+/// with fields like [`Self::HashDate`] serializes as
+/// `{"HashDate": {"other_tag": "PatientID"}}`. That makes a [`KeywordConfig`]
+/// document read as plain, hand-editable JSON/YAML rather than the tag
+/// encoding [`Action`] itself uses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KeywordAction {
+    Empty,
+    Hash {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        length: Option<usize>,
+    },
+    HashDate {
+        other_tag: String,
+    },
+    DateShift {
+        subject_tag: String,
+        max_offset_days: u32,
+    },
+    HashUID,
+    Keep,
+    None,
+    Remove,
+    Replace {
+        value: String,
+    },
+}
+
+impl KeywordAction {
+    /// Resolves this action's keyword-valued tag references, producing the
+    /// equivalent [`Action`].
+    fn into_action(self) -> Result<Action, ConfigError> {
+        Ok(match self {
+            KeywordAction::Empty => Action::Empty,
+            KeywordAction::Hash { length } => Action::Hash { length },
+            KeywordAction::HashDate { other_tag } => Action::HashDate {
+                other_tag: resolve_tag_by_keyword(&other_tag)
+                    .ok_or(ConfigError::UnknownTagKeyword(other_tag))?,
+            },
+            KeywordAction::DateShift {
+                subject_tag,
+                max_offset_days,
+            } => Action::DateShift {
+                subject_tag: resolve_tag_by_keyword(&subject_tag)
+                    .ok_or(ConfigError::UnknownTagKeyword(subject_tag))?,
+                max_offset_days,
+            },
+            KeywordAction::HashUID => Action::HashUID,
+            KeywordAction::Keep => Action::Keep,
+            KeywordAction::None => Action::None,
+            KeywordAction::Remove => Action::Remove,
+            KeywordAction::Replace { value } => Action::Replace { value },
+        })
+    }
+
+    fn from_action(action: &Action) -> Self {
+        match action {
+            Action::Empty => KeywordAction::Empty,
+            Action::Hash { length } => KeywordAction::Hash { length: *length },
+            Action::HashDate { other_tag } => KeywordAction::HashDate {
+                other_tag: keyword_or_hex(other_tag),
+            },
+            Action::DateShift {
+                subject_tag,
+                max_offset_days,
+            } => KeywordAction::DateShift {
+                subject_tag: keyword_or_hex(subject_tag),
+                max_offset_days: *max_offset_days,
+            },
+            Action::HashUID => KeywordAction::HashUID,
+            Action::Keep => KeywordAction::Keep,
+            Action::None => KeywordAction::None,
+            Action::Remove => KeywordAction::Remove,
+            Action::Replace { value } => KeywordAction::Replace {
+                value: value.clone(),
+            },
+        }
+    }
+}
+
+/// A de-identification policy expressed as a document mapping standard
+/// DICOM keywords (e.g. `"AcquisitionComments"`) to a [`KeywordAction`],
+/// rather than the numeric `(gggg,eeee)` tags [`TagActionMap`] uses.
+///
+/// This is the editable, shippable form of a [`TagActionMap`]: load one from
+/// a JSON/YAML file and resolve it with [`Self::into_tag_action_map`], then
+/// pass the result to [`crate::config::ConfigBuilder::tag_action_map`]. Going
+/// the other way, [`Self::from_tag_action_map`] turns an existing
+/// [`TagActionMap`] - such as a built-in default profile's - back into this
+/// document form, so it can be serialized out, edited, and reloaded.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct KeywordConfig(BTreeMap<String, KeywordAction>);
+
+impl KeywordConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_action(mut self, keyword: impl Into<String>, action: KeywordAction) -> Self {
+        self.0.insert(keyword.into(), action);
+        self
+    }
+
+    /// Resolves every keyword (and keyword-valued tag reference) in this
+    /// document against the built-in data dictionary, producing the
+    /// equivalent [`TagActionMap`].
+    ///
+    /// Returns [`ConfigError::UnknownTagKeyword`] naming the first keyword
+    /// the dictionary doesn't recognize.
+    pub fn into_tag_action_map(self) -> Result<TagActionMap, ConfigError> {
+        let mut map = TagActionMap::new();
+        for (keyword, action) in self.0 {
+            let tag = resolve_tag_by_keyword(&keyword)
+                .ok_or_else(|| ConfigError::UnknownTagKeyword(keyword.clone()))?;
+            map.insert(tag, action.into_action()?);
+        }
+        Ok(map)
+    }
+
+    /// Produces a [`KeywordConfig`] document equivalent to `map`, suitable
+    /// for serializing out to an editable JSON/YAML profile.
+    pub fn from_tag_action_map(map: &TagActionMap) -> Self {
+        let mut document = KeywordConfig::new();
+        for (tag, action) in map.iter() {
+            document
+                .0
+                .insert(keyword_or_hex(tag), KeywordAction::from_action(action));
+        }
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dicom_dictionary_std::tags;
+
+    #[test]
+    fn test_resolve_tag_by_keyword_known() {
+        assert_eq!(resolve_tag_by_keyword("PatientID"), Some(tags::PATIENT_ID));
+    }
+
+    #[test]
+    fn test_resolve_tag_by_keyword_unknown() {
+        assert_eq!(resolve_tag_by_keyword("NotARealKeyword"), None);
+    }
+
+    #[test]
+    fn test_into_tag_action_map_resolves_simple_actions() {
+        let document = KeywordConfig::new()
+            .with_action("PatientName", KeywordAction::Empty)
+            .with_action("StudyInstanceUID", KeywordAction::HashUID);
+
+        let map = document.into_tag_action_map().unwrap();
+        assert_eq!(map.get(&tags::PATIENT_NAME), Some(&Action::Empty));
+        assert_eq!(map.get(&tags::STUDY_INSTANCE_UID), Some(&Action::HashUID));
+    }
+
+    #[test]
+    fn test_into_tag_action_map_resolves_nested_tag_reference() {
+        let document = KeywordConfig::new().with_action(
+            "StudyDate",
+            KeywordAction::HashDate {
+                other_tag: "PatientID".to_string(),
+            },
+        );
+
+        let map = document.into_tag_action_map().unwrap();
+        assert_eq!(
+            map.get(&tags::STUDY_DATE),
+            Some(&Action::HashDate {
+                other_tag: tags::PATIENT_ID
+            })
+        );
+    }
+
+    #[test]
+    fn test_into_tag_action_map_unknown_top_level_keyword() {
+        let document = KeywordConfig::new().with_action("NotARealKeyword", KeywordAction::Remove);
+        let err = document.into_tag_action_map().unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::UnknownTagKeyword("NotARealKeyword".to_string())
+        );
+    }
+
+    #[test]
+    fn test_into_tag_action_map_unknown_nested_keyword() {
+        let document = KeywordConfig::new().with_action(
+            "StudyDate",
+            KeywordAction::HashDate {
+                other_tag: "NotARealKeyword".to_string(),
+            },
+        );
+        let err = document.into_tag_action_map().unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::UnknownTagKeyword("NotARealKeyword".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_tag_action_map_uses_keyword_when_known() {
+        let mut map = TagActionMap::new();
+        map.insert(tags::PATIENT_NAME, Action::Empty);
+
+        let document = KeywordConfig::from_tag_action_map(&map);
+        assert_eq!(document.0.get("PatientName"), Some(&KeywordAction::Empty));
+    }
+
+    #[test]
+    fn test_from_tag_action_map_falls_back_to_hex_for_private_tag() {
+        let tag = Tag(0x0009, 0x0010);
+        let mut map = TagActionMap::new();
+        map.insert(tag, Action::Remove);
+
+        let document = KeywordConfig::from_tag_action_map(&map);
+        assert_eq!(
+            document.0.get(&format!("{tag}")),
+            Some(&KeywordAction::Remove)
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_tag_action_map() {
+        let mut map = TagActionMap::new();
+        map.insert(tags::PATIENT_NAME, Action::Empty);
+        map.insert(
+            tags::STUDY_DATE,
+            Action::HashDate {
+                other_tag: tags::PATIENT_ID,
+            },
+        );
+
+        let document = KeywordConfig::from_tag_action_map(&map);
+        let round_tripped = document.into_tag_action_map().unwrap();
+        assert_eq!(round_tripped, map);
+    }
+
+    #[test]
+    fn test_serialize_unit_variant_is_bare_string() {
+        let json = serde_json::to_string(&KeywordAction::Remove).unwrap();
+        assert_eq!(json, r#""Remove""#);
+    }
+
+    #[test]
+    fn test_serialize_hash_date_is_externally_tagged() {
+        let action = KeywordAction::HashDate {
+            other_tag: "PatientID".to_string(),
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"HashDate":{"other_tag":"PatientID"}}"#);
+    }
+}
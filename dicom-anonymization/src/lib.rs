@@ -0,0 +1,23 @@
+pub mod actions;
+pub mod anonymizer;
+pub mod batch;
+pub mod config;
+pub mod config_format;
+pub mod dicomdir;
+pub mod hasher;
+pub mod keyword_config;
+pub mod metrics;
+pub mod orthanc;
+pub mod private_creator;
+pub mod processor;
+pub mod relaxed_config;
+pub mod remote_config;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod transfer_syntax;
+pub mod uid_mapper;
+pub mod workload;
+
+pub use anonymizer::{AnonymizationError, AnonymizationResult, Anonymizer};
+pub use dicom_core::Tag;
+pub use dicom_dictionary_std::tags;
@@ -0,0 +1,309 @@
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
+use log::warn;
+
+use crate::actions::AuditRecord;
+
+/// A single field value on a [`MetricPoint`], mapped to its InfluxDB line
+/// protocol suffix on write (`i` for integers, `u` for unsigned, none for
+/// floats, quoted for strings).
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricValue {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+}
+
+impl MetricValue {
+    fn to_line_protocol(&self) -> String {
+        match self {
+            MetricValue::Int(v) => format!("{v}i"),
+            MetricValue::UInt(v) => format!("{v}u"),
+            MetricValue::Float(v) => format!("{v}"),
+            MetricValue::Bool(v) => v.to_string(),
+            MetricValue::String(v) => format!("\"{}\"", v.replace('"', "\\\"")),
+        }
+    }
+}
+
+/// One InfluxDB line protocol point: `measurement,tag=val field=val,field=val timestamp`.
+///
+/// Built with a [`ConfigBuilder`](crate::config::ConfigBuilder)-style fluent
+/// API, then handed to a [`MetricsSink`].
+#[derive(Debug, Clone)]
+pub struct MetricPoint {
+    measurement: String,
+    tags: Vec<(String, String)>,
+    fields: Vec<(String, MetricValue)>,
+    timestamp: SystemTime,
+}
+
+impl MetricPoint {
+    pub fn new(measurement: impl Into<String>) -> Self {
+        Self {
+            measurement: measurement.into(),
+            tags: Vec::new(),
+            fields: Vec::new(),
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_field(mut self, key: impl Into<String>, value: MetricValue) -> Self {
+        self.fields.push((key.into(), value));
+        self
+    }
+
+    /// Renders this point as one InfluxDB line protocol line, or `None` if
+    /// every field was dropped - either there were none to begin with, or
+    /// all of them were non-finite floats, which InfluxDB rejects outright.
+    fn to_line_protocol(&self) -> Option<String> {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|(_, value)| !matches!(value, MetricValue::Float(f) if !f.is_finite()))
+            .map(|(key, value)| format!("{key}={}", value.to_line_protocol()))
+            .collect();
+
+        if fields.is_empty() {
+            return None;
+        }
+
+        let tags: String = self
+            .tags
+            .iter()
+            .map(|(key, value)| format!(",{key}={value}"))
+            .collect();
+
+        let timestamp_nanos = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+
+        Some(format!(
+            "{}{tags} {} {timestamp_nanos}",
+            self.measurement,
+            fields.join(",")
+        ))
+    }
+}
+
+/// Builds the per-run `anonymization` metric point described in the
+/// `Processor`/batch path's audit trail: counts of elements processed,
+/// removed, and date-shifted, plus byte sizes and wall-clock duration.
+///
+/// Reuses [`AuditRecord`] (see [`crate::processor::DefaultProcessor::process_object_with_report`])
+/// as the source of truth for per-element outcomes, rather than threading a
+/// second, parallel counting pass through the processor.
+pub fn anonymization_metric_point(
+    report: &[AuditRecord],
+    duration: Duration,
+    bytes_in: usize,
+    bytes_out: usize,
+) -> MetricPoint {
+    let elements_removed = report.iter().filter(|record| !record.kept).count() as u64;
+    let dates_shifted = report
+        .iter()
+        .filter(|record| record.action == "DateShift")
+        .count() as u64;
+
+    MetricPoint::new("anonymization")
+        .with_field("elements_processed", MetricValue::UInt(report.len() as u64))
+        .with_field("elements_removed", MetricValue::UInt(elements_removed))
+        .with_field("dates_shifted", MetricValue::UInt(dates_shifted))
+        .with_field("bytes_in", MetricValue::UInt(bytes_in as u64))
+        .with_field("bytes_out", MetricValue::UInt(bytes_out as u64))
+        .with_field(
+            "duration_ms",
+            MetricValue::Float(duration.as_secs_f64() * 1_000.0),
+        )
+}
+
+/// Somewhere to send [`MetricPoint`]s. Implement this for a real backend;
+/// [`NoopMetricsSink`] is the default so metrics collection stays entirely
+/// opt-in and the core anonymization path stays dependency-free.
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, point: MetricPoint);
+}
+
+/// The default [`MetricsSink`]: discards every point. Metrics collection is
+/// opt-in, so code paths that don't care about telemetry pay nothing for it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetricsSink;
+
+impl MetricsSink for NoopMetricsSink {
+    fn record(&self, _point: MetricPoint) {}
+}
+
+/// A [`MetricsSink`] that batches points and pushes them to a time-series
+/// database speaking InfluxDB line protocol over HTTP.
+///
+/// Points are handed to a `channel_capacity`-bounded channel and flushed by a
+/// background thread once a batch reaches `batch_size` or `flush_interval`
+/// elapses, whichever comes first - so recording a point never blocks the
+/// hot anonymization path on network I/O. If the channel is still full after
+/// waiting `drop_deadline` for a slot, the point is dropped with a logged
+/// warning rather than stalling anonymization.
+pub struct LineProtocolSink {
+    sender: Sender<MetricPoint>,
+    drop_deadline: Duration,
+}
+
+impl LineProtocolSink {
+    pub fn new(
+        url: impl Into<String>,
+        channel_capacity: usize,
+        batch_size: usize,
+        flush_interval: Duration,
+        drop_deadline: Duration,
+    ) -> Self {
+        let (sender, receiver) = bounded::<MetricPoint>(channel_capacity);
+        let url = url.into();
+
+        thread::spawn(move || {
+            let mut batch = Vec::with_capacity(batch_size);
+            loop {
+                match receiver.recv_timeout(flush_interval) {
+                    Ok(point) => {
+                        batch.push(point);
+                        if batch.len() >= batch_size {
+                            flush_batch(&url, &batch);
+                            batch.clear();
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        if !batch.is_empty() {
+                            flush_batch(&url, &batch);
+                            batch.clear();
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        if !batch.is_empty() {
+                            flush_batch(&url, &batch);
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            sender,
+            drop_deadline,
+        }
+    }
+}
+
+impl MetricsSink for LineProtocolSink {
+    fn record(&self, point: MetricPoint) {
+        if self.sender.send_timeout(point, self.drop_deadline).is_err() {
+            warn!("metrics channel stayed full past the drop deadline; dropping metric point");
+        }
+    }
+}
+
+fn flush_batch(url: &str, batch: &[MetricPoint]) {
+    let body: String = batch
+        .iter()
+        .filter_map(MetricPoint::to_line_protocol)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if body.is_empty() {
+        return;
+    }
+
+    if let Err(err) = ureq::post(url).send_string(&body) {
+        warn!(
+            "failed to flush {} metric point(s) to {url}: {err}",
+            batch.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::VR;
+
+    use crate::actions::Action;
+
+    #[test]
+    fn test_to_line_protocol_renders_tags_and_fields() {
+        let point = MetricPoint::new("anonymization")
+            .with_tag("host", "worker-1")
+            .with_field("elements_processed", MetricValue::UInt(42))
+            .with_field("duration_ms", MetricValue::Float(12.5));
+
+        let line = point.to_line_protocol().unwrap();
+        let (prefix, timestamp) = line.rsplit_once(' ').unwrap();
+        assert_eq!(
+            prefix,
+            "anonymization,host=worker-1 elements_processed=42u,duration_ms=12.5"
+        );
+        assert!(timestamp.parse::<u128>().is_ok());
+    }
+
+    #[test]
+    fn test_to_line_protocol_drops_non_finite_float_fields() {
+        let point = MetricPoint::new("anonymization")
+            .with_field("elements_processed", MetricValue::UInt(1))
+            .with_field("bad_ratio", MetricValue::Float(f64::NAN));
+
+        let line = point.to_line_protocol().unwrap();
+        assert!(line.starts_with("anonymization elements_processed=1u "));
+        assert!(!line.contains("bad_ratio"));
+    }
+
+    #[test]
+    fn test_to_line_protocol_is_none_when_every_field_is_dropped() {
+        let point = MetricPoint::new("anonymization")
+            .with_field("bad_ratio", MetricValue::Float(f64::INFINITY));
+
+        assert_eq!(point.to_line_protocol(), None);
+    }
+
+    #[test]
+    fn test_noop_sink_accepts_points_without_panicking() {
+        let sink = NoopMetricsSink;
+        sink.record(MetricPoint::new("anonymization"));
+    }
+
+    #[test]
+    fn test_anonymization_metric_point_counts_removed_and_shifted_elements() {
+        use dicom_dictionary_std::tags;
+
+        let report = vec![
+            AuditRecord::kept(
+                tags::STUDY_DATE,
+                VR::DA,
+                &Action::DateShift {
+                    subject_tag: tags::PATIENT_ID,
+                    max_offset_days: 30,
+                },
+            ),
+            AuditRecord::removed(tags::PATIENT_NAME, VR::PN, &Action::Remove),
+            AuditRecord::kept(tags::ACCESSION_NUMBER, VR::SH, &Action::Keep),
+        ];
+
+        let point = anonymization_metric_point(&report, Duration::from_millis(5), 1000, 800);
+        let line = point.to_line_protocol().unwrap();
+
+        assert!(line.contains("elements_processed=3u"));
+        assert!(line.contains("elements_removed=1u"));
+        assert!(line.contains("dates_shifted=1u"));
+        assert!(line.contains("bytes_in=1000u"));
+        assert!(line.contains("bytes_out=800u"));
+        assert!(line.contains("duration_ms=5"));
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::processor::{self, Processor};
+
+/// An Orthanc instance resource, as returned by `GET /instances/{id}` (and,
+/// expanded, by `GET /series/{id}/instances`).
+///
+/// Only the fields this module actually consults are modeled; Orthanc's
+/// responses carry several more that callers who need them can add here.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Instance {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "ParentSeries")]
+    pub parent_series: String,
+    #[serde(rename = "MainDicomTags")]
+    pub main_dicom_tags: HashMap<String, String>,
+}
+
+/// An Orthanc series resource, as returned by `GET /series/{id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "ParentStudy")]
+    pub parent_study: String,
+    #[serde(rename = "MainDicomTags")]
+    pub main_dicom_tags: HashMap<String, String>,
+    #[serde(rename = "Instances")]
+    pub instances: Vec<String>,
+}
+
+/// An Orthanc study resource, as returned by `GET /studies/{id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Study {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "MainDicomTags")]
+    pub main_dicom_tags: HashMap<String, String>,
+    #[serde(rename = "Series")]
+    pub series: Vec<String>,
+}
+
+/// The body Orthanc returns from a successful `POST /instances` upload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct UploadResult {
+    #[serde(rename = "ID")]
+    id: String,
+}
+
+#[derive(Error, Debug)]
+pub enum OrthancError {
+    #[error("Orthanc request failed: {0}")]
+    Request(String),
+
+    #[error("failed to decode Orthanc response: {0}")]
+    Decode(String),
+
+    #[error("failed to decode DICOM instance: {0}")]
+    InvalidDicom(String),
+
+    #[error("anonymization error: {0}")]
+    Anonymization(#[from] processor::Error),
+}
+
+impl From<ureq::Error> for OrthancError {
+    fn from(err: ureq::Error) -> Self {
+        OrthancError::Request(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for OrthancError {
+    fn from(err: std::io::Error) -> Self {
+        OrthancError::Decode(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for OrthancError {
+    fn from(err: serde_json::Error) -> Self {
+        OrthancError::Decode(err.to_string())
+    }
+}
+
+/// A thin, synchronous client for the subset of the Orthanc REST API this
+/// module needs to anonymize instances in place: listing instances under a
+/// series or study, downloading an instance's raw DICOM file, and uploading
+/// or deleting instances.
+///
+/// Built on [`ureq`] rather than an async HTTP client, matching the rest of
+/// this crate - the CLI (`src/bin/main.rs`) already gets its concurrency from
+/// [`rayon`] rather than an async runtime, so a blocking client avoids
+/// pulling in a second concurrency model.
+#[derive(Debug, Clone)]
+pub struct OrthancClient {
+    base_url: String,
+    credentials: Option<(String, String)>,
+}
+
+impl OrthancClient {
+    /// Creates a client for the Orthanc server at `base_url` (e.g.
+    /// `http://localhost:8042`), with no authentication.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            credentials: None,
+        }
+    }
+
+    /// Registers HTTP Basic credentials to send with every request.
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let request = ureq::request(method, &format!("{}{}", self.base_url, path));
+        match &self.credentials {
+            Some((username, password)) => request.auth(username, password),
+            None => request,
+        }
+    }
+
+    /// `GET /instances/{instance_id}` - fetches one instance's metadata.
+    pub fn get_instance(&self, instance_id: &str) -> Result<Instance, OrthancError> {
+        let response = self
+            .request("GET", &format!("/instances/{instance_id}"))
+            .call()?;
+        Ok(response.into_json()?)
+    }
+
+    /// `GET /series/{series_id}/instances` - lists every instance belonging
+    /// to `series_id`, expanded to full [`Instance`] resources.
+    pub fn list_series_instances(&self, series_id: &str) -> Result<Vec<Instance>, OrthancError> {
+        let response = self
+            .request("GET", &format!("/series/{series_id}/instances"))
+            .call()?;
+        Ok(response.into_json()?)
+    }
+
+    /// `GET /studies/{study_id}/instances` - lists every instance belonging
+    /// to `study_id`, across all of its series, expanded to full [`Instance`]
+    /// resources.
+    pub fn list_study_instances(&self, study_id: &str) -> Result<Vec<Instance>, OrthancError> {
+        let response = self
+            .request("GET", &format!("/studies/{study_id}/instances"))
+            .call()?;
+        Ok(response.into_json()?)
+    }
+
+    /// `GET /instances/{instance_id}/file` - downloads the raw DICOM bytes
+    /// for one instance.
+    pub fn download_instance_file(&self, instance_id: &str) -> Result<Vec<u8>, OrthancError> {
+        let response = self
+            .request("GET", &format!("/instances/{instance_id}/file"))
+            .call()?;
+
+        let mut bytes = Vec::new();
+        response.into_reader().read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// `POST /instances` - uploads a raw DICOM file, returning the ID Orthanc
+    /// assigned the newly-created instance.
+    pub fn upload_instance(&self, dicom_bytes: &[u8]) -> Result<String, OrthancError> {
+        let response = self.request("POST", "/instances").send_bytes(dicom_bytes)?;
+        let result: UploadResult = response.into_json()?;
+        Ok(result.id)
+    }
+
+    /// `DELETE /instances/{instance_id}` - permanently deletes an instance.
+    pub fn delete_instance(&self, instance_id: &str) -> Result<(), OrthancError> {
+        self.request("DELETE", &format!("/instances/{instance_id}"))
+            .call()?;
+        Ok(())
+    }
+}
+
+/// Downloads every instance under `series_id`, anonymizes it with
+/// `processor`, and re-uploads the result to the same Orthanc server.
+///
+/// When `delete_originals` is set, each original instance is deleted once its
+/// anonymized replacement has been uploaded successfully. Returns the
+/// Orthanc-assigned ID of every anonymized instance, in the order processed;
+/// a download, anonymization, or upload failure partway through stops and
+/// returns the error, leaving already-processed instances (and their
+/// originals) as they were left.
+pub fn anonymize_series_in_place(
+    client: &OrthancClient,
+    processor: &impl Processor,
+    series_id: &str,
+    delete_originals: bool,
+) -> Result<Vec<String>, OrthancError> {
+    let instances = client.list_series_instances(series_id)?;
+    let mut new_ids = Vec::with_capacity(instances.len());
+
+    for instance in instances {
+        let new_id =
+            anonymize_instance_in_place(client, processor, &instance.id, delete_originals)?;
+        new_ids.push(new_id);
+    }
+
+    Ok(new_ids)
+}
+
+/// Downloads `instance_id`, anonymizes it with `processor`, re-uploads the
+/// result, and (if `delete_original`) deletes `instance_id` afterwards.
+/// Returns the Orthanc-assigned ID of the anonymized instance.
+pub fn anonymize_instance_in_place(
+    client: &OrthancClient,
+    processor: &impl Processor,
+    instance_id: &str,
+    delete_original: bool,
+) -> Result<String, OrthancError> {
+    let dicom_bytes = client.download_instance_file(instance_id)?;
+
+    let obj = dicom_object::from_reader(Cursor::new(dicom_bytes))
+        .map_err(|e| OrthancError::InvalidDicom(e.to_string()))?;
+    let anonymized = processor.process_object(&obj)?;
+
+    let mut out = Vec::new();
+    anonymized
+        .write_all(&mut out)
+        .map_err(|e| OrthancError::InvalidDicom(e.to_string()))?;
+
+    let new_id = client.upload_instance(&out)?;
+
+    if delete_original {
+        client.delete_instance(instance_id)?;
+    }
+
+    Ok(new_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_instance() {
+        let json = r#"{
+            "ID": "instance-id",
+            "ParentSeries": "series-id",
+            "MainDicomTags": {
+                "SOPInstanceUID": "1.2.3.4"
+            }
+        }"#;
+
+        let instance: Instance = serde_json::from_str(json).unwrap();
+        assert_eq!(instance.id, "instance-id");
+        assert_eq!(instance.parent_series, "series-id");
+        assert_eq!(
+            instance.main_dicom_tags.get("SOPInstanceUID"),
+            Some(&"1.2.3.4".to_string())
+        );
+    }
+
+    #[test]
+    fn test_deserialize_series() {
+        let json = r#"{
+            "ID": "series-id",
+            "ParentStudy": "study-id",
+            "MainDicomTags": {
+                "SeriesInstanceUID": "1.2.3"
+            },
+            "Instances": ["instance-a", "instance-b"]
+        }"#;
+
+        let series: Series = serde_json::from_str(json).unwrap();
+        assert_eq!(series.id, "series-id");
+        assert_eq!(series.parent_study, "study-id");
+        assert_eq!(series.instances, vec!["instance-a", "instance-b"]);
+    }
+
+    #[test]
+    fn test_deserialize_study() {
+        let json = r#"{
+            "ID": "study-id",
+            "MainDicomTags": {
+                "StudyInstanceUID": "1.2"
+            },
+            "Series": ["series-a", "series-b"]
+        }"#;
+
+        let study: Study = serde_json::from_str(json).unwrap();
+        assert_eq!(study.id, "study-id");
+        assert_eq!(study.series, vec!["series-a", "series-b"]);
+    }
+
+    #[test]
+    fn test_deserialize_upload_result() {
+        let json = r#"{"ID": "new-instance-id", "Status": "Success"}"#;
+        let result: UploadResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.id, "new-instance-id");
+    }
+}
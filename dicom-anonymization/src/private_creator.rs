@@ -0,0 +1,201 @@
+use dicom_object::DefaultDicomObject;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::Action;
+use crate::Tag;
+
+/// Matches private data elements by the creator string that reserved their
+/// block, plus the element byte within that block, rather than by an
+/// absolute tag.
+///
+/// Private blocks are reassigned a group-relative block number by whichever
+/// application writes the file first, so the same creator's "Reason" element
+/// might live at `(0009,1010)` in one file and `(0009,1310)` in another. A
+/// rule scoped to `(creator, element_byte)` survives that reshuffling, unlike
+/// a [`crate::config::TagActionMap`] entry keyed on the absolute tag.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivateCreatorRule {
+    creator: String,
+    element_byte: u8,
+}
+
+impl PrivateCreatorRule {
+    pub fn new(creator: impl Into<String>, element_byte: u8) -> Self {
+        Self {
+            creator: creator.into(),
+            element_byte,
+        }
+    }
+
+    fn matches(&self, creator: &str, element_byte: u8) -> bool {
+        self.creator == creator && self.element_byte == element_byte
+    }
+}
+
+/// A [`PrivateCreatorRule`] paired with the [`Action`] to take when it matches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivateCreatorRuleAction {
+    rule: PrivateCreatorRule,
+    action: Action,
+}
+
+/// Policy governing private (odd-group) data elements by creator block
+/// rather than by absolute tag.
+///
+/// Unrecognized private elements - those whose creator isn't covered by any
+/// rule in `rules` or `allowlist` - are removed by default, since private
+/// elements are not standardized and may carry unpredictable PHI. Add to
+/// `allowlist` for creator/element_byte combinations known to be harmless,
+/// and to `rules` for anything that needs a different action.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PrivateCreatorPolicy {
+    #[serde(default)]
+    rules: Vec<PrivateCreatorRuleAction>,
+    #[serde(default)]
+    allowlist: Vec<PrivateCreatorRule>,
+}
+
+impl PrivateCreatorPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule applying `action` whenever a private element's creator and
+    /// element byte match `rule`. Later-added rules take priority over
+    /// earlier ones matching the same `(creator, element_byte)`.
+    pub fn with_rule(mut self, rule: PrivateCreatorRule, action: Action) -> Self {
+        self.rules.push(PrivateCreatorRuleAction { rule, action });
+        self
+    }
+
+    /// Marks `rule` as known-harmless, so matching elements are kept instead
+    /// of falling back to the default Remove.
+    pub fn with_allowed(mut self, rule: PrivateCreatorRule) -> Self {
+        self.allowlist.push(rule);
+        self
+    }
+
+    /// Returns the action to take for a private element whose reservation
+    /// block belongs to `creator`, with the given `element_byte`.
+    ///
+    /// Checks `rules` (most recently added first), then `allowlist`, and
+    /// falls back to [`Action::Remove`] if nothing matches.
+    pub fn get_action(&self, creator: &str, element_byte: u8) -> Action {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule_action| rule_action.rule.matches(creator, element_byte))
+            .map(|rule_action| rule_action.action.clone())
+            .or_else(|| {
+                self.allowlist
+                    .iter()
+                    .any(|rule| rule.matches(creator, element_byte))
+                    .then_some(Action::Keep)
+            })
+            .unwrap_or(Action::Remove)
+    }
+}
+
+/// Looks up the creator string that reserved the private block `tag` belongs
+/// to, by reading the Private Creator Data Element at `(group, block)` where
+/// `block` is `tag`'s element number's high byte.
+///
+/// Returns `None` if `tag` isn't a private tag, its block number is outside
+/// the reserved `0x10..=0xFF` range, or the dataset has no matching Private
+/// Creator Data Element (e.g. it was already removed, or the file is
+/// malformed).
+pub fn resolve_private_creator(obj: &DefaultDicomObject, tag: &Tag) -> Option<String> {
+    if tag.group() % 2 == 0 {
+        return None;
+    }
+
+    let block = tag.element() >> 8;
+    if !(0x10..=0xFF).contains(&block) {
+        return None;
+    }
+
+    let creator_tag = Tag(tag.group(), block);
+    let creator = obj.element(creator_tag).ok()?.value().to_str().ok()?;
+    Some(creator.trim_end_matches(['\0', ' ']).to_string())
+}
+
+/// Returns the element byte (the low byte of the element number) that
+/// identifies `tag` within its private reservation block.
+pub fn private_element_byte(tag: &Tag) -> u8 {
+    (tag.element() & 0xFF) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::value::Value;
+    use dicom_core::VR;
+    use dicom_object::mem::InMemElement;
+    use dicom_object::FileDicomObject;
+
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_policy_defaults_to_remove() {
+        let policy = PrivateCreatorPolicy::new();
+        assert_eq!(policy.get_action("ACME CORP", 0x10), Action::Remove);
+    }
+
+    #[test]
+    fn test_policy_allowlist_keeps_matching_element() {
+        let policy =
+            PrivateCreatorPolicy::new().with_allowed(PrivateCreatorRule::new("ACME CORP", 0x10));
+        assert_eq!(policy.get_action("ACME CORP", 0x10), Action::Keep);
+        // a different element byte under the same creator is still unmatched
+        assert_eq!(policy.get_action("ACME CORP", 0x11), Action::Remove);
+    }
+
+    #[test]
+    fn test_policy_rule_overrides_default() {
+        let policy = PrivateCreatorPolicy::new()
+            .with_rule(PrivateCreatorRule::new("ACME CORP", 0x10), Action::Empty);
+        assert_eq!(policy.get_action("ACME CORP", 0x10), Action::Empty);
+    }
+
+    #[test]
+    fn test_policy_most_recently_added_rule_wins() {
+        let policy = PrivateCreatorPolicy::new()
+            .with_rule(PrivateCreatorRule::new("ACME CORP", 0x10), Action::Empty)
+            .with_rule(PrivateCreatorRule::new("ACME CORP", 0x10), Action::Keep);
+        assert_eq!(policy.get_action("ACME CORP", 0x10), Action::Keep);
+    }
+
+    #[test]
+    fn test_resolve_private_creator_finds_matching_block() {
+        let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        obj.put(InMemElement::new(
+            Tag(0x0009, 0x0010),
+            VR::LO,
+            Value::from("ACME CORP"),
+        ));
+        let data_elem_tag = Tag(0x0009, 0x1010);
+        assert_eq!(
+            resolve_private_creator(&obj, &data_elem_tag),
+            Some("ACME CORP".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_private_creator_none_for_standard_tag() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        assert_eq!(resolve_private_creator(&obj, &Tag(0x0008, 0x0020)), None);
+    }
+
+    #[test]
+    fn test_resolve_private_creator_none_when_creator_missing() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        assert_eq!(resolve_private_creator(&obj, &Tag(0x0009, 0x1010)), None);
+    }
+
+    #[test]
+    fn test_private_element_byte() {
+        assert_eq!(private_element_byte(&Tag(0x0009, 0x1010)), 0x10);
+        assert_eq!(private_element_byte(&Tag(0x0009, 0x10ab)), 0xab);
+    }
+}
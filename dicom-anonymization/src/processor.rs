@@ -0,0 +1,973 @@
+use dicom_core::header::Header;
+use dicom_core::value::{CastValueError, DataSetSequence, Value};
+use dicom_core::{Length, Tag, VR};
+use dicom_dictionary_std::tags;
+use dicom_object::mem::InMemElement;
+use dicom_object::{AccessError, DefaultDicomObject, InMemDicomObject};
+use log::warn;
+use std::borrow::Cow;
+use thiserror::Error;
+
+use crate::actions::errors::ActionError;
+use crate::actions::utils::resolve_element_vr;
+use crate::actions::{Action, AuditRecord};
+use crate::config::{is_private_tag, Config, DEIDENTIFIER};
+use crate::private_creator::{private_element_byte, resolve_private_creator};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("Value error: {}", .0.to_lowercase())]
+    ValueError(String),
+
+    #[error("Element error: {}", .0.to_lowercase())]
+    ElementError(String),
+
+    #[error("Anonymization error: {}", .0.to_lowercase())]
+    AnonymizationError(String),
+}
+
+impl From<CastValueError> for Error {
+    fn from(err: CastValueError) -> Self {
+        Error::ValueError(format!("{err}"))
+    }
+}
+
+impl From<AccessError> for Error {
+    fn from(err: AccessError) -> Self {
+        Error::ElementError(format!("{err}"))
+    }
+}
+
+impl From<ActionError> for Error {
+    fn from(err: ActionError) -> Self {
+        Error::AnonymizationError(format!("{err}"))
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The PS3.16 CID 7050 code meaning for `code`, or a placeholder for a code
+/// this crate doesn't itself apply (e.g. one a caller recorded directly via
+/// [`crate::config::ConfigBuilder::tag_action`]).
+fn code_meaning(code: &str) -> &'static str {
+    match code {
+        "113100" => "Basic Application Confidentiality Profile",
+        "113101" => "Clean Pixel Data Option",
+        "113102" => "Clean Recognizable Visual Features Option",
+        "113103" => "Clean Graphics Option",
+        "113104" => "Clean Structured Content Option",
+        "113105" => "Clean Descriptors Option",
+        "113106" => "Retain Longitudinal Temporal Information Full Dates Option",
+        "113107" => "Retain Longitudinal Temporal Information Modified Dates Option",
+        "113108" => "Retain Patient Characteristics Option",
+        "113109" => "Retain Device Identity Option",
+        "113110" => "Retain UIDs Option",
+        "113111" => "Retain Safe Private Option",
+        _ => "Unknown De-identification Method",
+    }
+}
+
+/// Builds one PS3.3 Code Sequence Macro item (`CodeValue`/`CodingSchemeDesignator`/
+/// `CodeMeaning`) for `code`, all of which are coded against the `"DCM"` scheme
+/// in PS3.16 CID 7050.
+fn code_sequence_item(code: &str) -> InMemDicomObject {
+    InMemDicomObject::from_element_iter([
+        InMemElement::new(tags::CODE_VALUE, VR::SH, Value::from(code)),
+        InMemElement::new(tags::CODING_SCHEME_DESIGNATOR, VR::SH, Value::from("DCM")),
+        InMemElement::new(tags::CODE_MEANING, VR::LO, Value::from(code_meaning(code))),
+    ])
+}
+
+pub trait Processor {
+    fn process_element<'a>(
+        &'a self,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>>;
+
+    /// Runs once, after every element of an object has gone through
+    /// [`Self::process_element`], with a chance to add elements to it -
+    /// something `process_element` can't do, since it only ever transforms
+    /// or drops the single element it's handed.
+    ///
+    /// The default implementation does nothing; [`DefaultProcessor`]
+    /// overrides this to insert the PS3.15 de-identification bookkeeping
+    /// attributes (`PatientIdentityRemoved`, `DeidentificationMethod`,
+    /// `DeidentificationMethodCodeSequence`) that conformant anonymizers are
+    /// expected to write.
+    fn finalize(&self, _obj: &mut DefaultDicomObject) {}
+
+    /// Applies [`Self::process_element`] to every element of `obj` in turn,
+    /// then [`Self::finalize`], returning a new object with each element
+    /// kept, transformed, or removed according to the result.
+    ///
+    /// A `Processor` only needs to implement `process_element`; this default
+    /// implementation is what lets a whole DICOM object be anonymized in one
+    /// call (e.g. [`crate::orthanc`], which has no per-element entry point of
+    /// its own to drive).
+    fn process_object(&self, obj: &DefaultDicomObject) -> Result<DefaultDicomObject> {
+        let mut result = obj.clone();
+
+        for elem in obj.iter() {
+            match self.process_element(obj, elem)? {
+                Some(processed) => {
+                    result.put(processed.into_owned());
+                }
+                None => {
+                    result.remove_element(elem.tag());
+                }
+            }
+        }
+
+        self.finalize(&mut result);
+
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefaultProcessor {
+    config: Config,
+}
+
+impl DefaultProcessor {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Returns the action the configured [`crate::private_creator::PrivateCreatorPolicy`]
+    /// assigns to `tag`, if one is set and `tag`'s creator can be resolved from `obj`.
+    ///
+    /// Returns `None` (deferring to [`Config::get_action`]) for non-private tags,
+    /// when no policy is configured, or when the creator can't be resolved.
+    fn private_creator_action(&self, obj: &DefaultDicomObject, tag: &Tag) -> Option<Action> {
+        if !is_private_tag(tag) {
+            return None;
+        }
+
+        let policy = self.config.get_private_creator_policy()?;
+        let creator = resolve_private_creator(obj, tag)?;
+        Some(policy.get_action(&creator, private_element_byte(tag)))
+    }
+
+    /// Resolves the [`Action`] that applies to `tag`, by the same
+    /// private-creator-then-VR precedence [`Self::process_element`] uses.
+    fn resolve_action(&self, obj: &DefaultDicomObject, tag: &Tag, vr: dicom_core::VR) -> Action {
+        self.private_creator_action(obj, tag)
+            .unwrap_or_else(|| self.config.get_action_for_vr(tag, Some(vr)).clone())
+    }
+
+    /// Does the actual work behind [`Processor::process_element`], additionally
+    /// returning the message of any [`ActionError::InvalidHashDateTag`] that
+    /// was downgraded to a no-op, so [`Self::process_object_with_report`] can
+    /// surface it on the matching [`AuditRecord`] instead of only logging it.
+    fn process_element_inner<'a>(
+        &'a self,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<(Option<Cow<'a, InMemElement>>, Option<String>)> {
+        let tag = elem.tag();
+        let vr = resolve_element_vr(obj, &tag, elem.vr());
+        let action = self.resolve_action(obj, &tag, vr);
+        let action_struct = action.get_action_struct();
+
+        match action_struct.process(&self.config, obj, elem) {
+            Ok(None) => Ok((None, None)),
+            Ok(Some(v)) => Ok((Some(Cow::Owned(v.into_owned())), None)),
+            Err(ActionError::InvalidHashDateTag(e)) => {
+                // log a warning for this error, but return the element as is
+                warn!("{}", e);
+                Ok((Some(Cow::Borrowed(elem)), Some(e.to_string())))
+            }
+            Err(e) => Err(Error::from(e)),
+        }
+    }
+
+    /// Inserts `tag` into `obj` with `default`'s value, unless `obj` already
+    /// has an element for `tag` (kept, transformed, or otherwise already
+    /// present) or the configured action for `tag` is [`Action::Remove`] (an
+    /// explicit opt-out this shouldn't override). Writes the configured
+    /// [`Action::Replace`] value instead of `default` when one is set, so a
+    /// custom value for one of these tags (see the `DeidentificationMethod`
+    /// example on [`crate::config::ConfigBuilder::tag_action`]) is still
+    /// respected. Does nothing if neither a default nor a `Replace` value is
+    /// available.
+    fn fill_in_tag(&self, obj: &mut DefaultDicomObject, tag: Tag, vr: VR, default: Option<String>) {
+        if obj.element(tag).is_ok() {
+            return;
+        }
+
+        let value = match self.config.get_action(&tag) {
+            Action::Remove => return,
+            Action::Replace { value } => Some(value.clone()),
+            _ => default,
+        };
+
+        if let Some(value) = value {
+            obj.put(InMemElement::new(tag, vr, Value::from(value)));
+        }
+    }
+
+    /// Fills `DeidentificationMethodCodeSequence` (tag `(0012,0064)`, VR `SQ`)
+    /// with one PS3.3 Code Sequence Macro item per applied profile code,
+    /// unless `obj` already has an element for the tag or the configured
+    /// action for it is [`Action::Remove`].
+    ///
+    /// Unlike [`Self::fill_in_tag`], this never writes a flat string into the
+    /// tag: a `Value::Sequence` is the only conformant representation for an
+    /// `SQ` element, so a toolkit that iterates this tag's items sees real
+    /// items instead of choking on a comma-joined `LO`. An explicit
+    /// [`Action::Replace`] for the tag is still honored as a literal override
+    /// (see the `DeidentificationMethod` example on
+    /// [`crate::config::ConfigBuilder::tag_action`]), since that's a
+    /// deliberate opt-out of the Code Sequence Macro shape, not the default
+    /// autofill path.
+    fn fill_in_code_sequence_tag(&self, obj: &mut DefaultDicomObject) {
+        let tag = tags::DEIDENTIFICATION_METHOD_CODE_SEQUENCE;
+
+        if obj.element(tag).is_ok() {
+            return;
+        }
+
+        match self.config.get_action(&tag) {
+            Action::Remove => return,
+            Action::Replace { value } => {
+                obj.put(InMemElement::new(tag, VR::LO, Value::from(value.clone())));
+                return;
+            }
+            _ => {}
+        }
+
+        let profile_codes = self.config.get_profile_codes();
+        if profile_codes.is_empty() {
+            return;
+        }
+
+        let items = profile_codes
+            .iter()
+            .map(|code| code_sequence_item(code))
+            .collect::<Vec<_>>();
+        obj.put(InMemElement::new(
+            tag,
+            VR::SQ,
+            Value::Sequence(DataSetSequence::new(items, Length(0))),
+        ));
+    }
+
+    /// Like [`Processor::process_object`], but also returns an
+    /// [`AuditRecord`] per element describing the action applied and
+    /// whether the element was kept (possibly transformed) or removed -
+    /// without the report itself ever holding an original or transformed
+    /// value, so it's safe to hand to a compliance reviewer on its own.
+    pub fn process_object_with_report(
+        &self,
+        obj: &DefaultDicomObject,
+    ) -> Result<(DefaultDicomObject, Vec<AuditRecord>)> {
+        let mut result = obj.clone();
+        let mut records = Vec::new();
+
+        for elem in obj.iter() {
+            let tag = elem.tag();
+            let vr = resolve_element_vr(obj, &tag, elem.vr());
+            let action = self.resolve_action(obj, &tag, vr);
+            let original_length = elem.value().length().0;
+
+            match self.process_element_inner(obj, elem)? {
+                (Some(processed), downgraded_warning) => {
+                    result.put(processed.into_owned());
+                    records.push(AuditRecord::kept(
+                        tag,
+                        vr,
+                        &action,
+                        original_length,
+                        downgraded_warning,
+                    ));
+                }
+                (None, _) => {
+                    result.remove_element(tag);
+                    records.push(AuditRecord::removed(tag, vr, &action, original_length));
+                }
+            }
+        }
+
+        self.finalize(&mut result);
+
+        Ok((result, records))
+    }
+
+    /// Like [`Processor::process_object`], but also reports every value this
+    /// pass actually changed to `recorder`, so an authorized custodian can
+    /// keep a crosswalk back to the source data.
+    ///
+    /// Unlike [`Self::process_object_with_report`]'s [`AuditRecord`]s, which
+    /// never carry a value, `recorder` sees both the original and the
+    /// anonymized string - this is meant for an opt-in, tightly controlled
+    /// sink (see [`MappingRecorder`]'s own docs), not general-purpose
+    /// auditing. Only elements whose value actually changed are reported;
+    /// an element kept, removed, or rewritten to an identical value is not.
+    pub fn process_object_with_mapping(
+        &self,
+        obj: &DefaultDicomObject,
+        recorder: &dyn MappingRecorder,
+    ) -> Result<DefaultDicomObject> {
+        let mut result = obj.clone();
+
+        for elem in obj.iter() {
+            let tag = elem.tag();
+
+            match self.process_element(obj, elem)? {
+                Some(processed) => {
+                    if let (Ok(original), Ok(anonymized)) =
+                        (elem.value().to_str(), processed.value().to_str())
+                    {
+                        if original != anonymized {
+                            recorder.record(tag, &original, &anonymized);
+                        }
+                    }
+                    result.put(processed.into_owned());
+                }
+                None => {
+                    result.remove_element(tag);
+                }
+            }
+        }
+
+        self.finalize(&mut result);
+
+        Ok(result)
+    }
+}
+
+/// A sink for original/anonymized value pairs, invoked by
+/// [`DefaultProcessor::process_object_with_mapping`] once per element whose
+/// value was actually changed.
+///
+/// This is a deliberately narrow, opt-in escape hatch from the
+/// de-identification this crate otherwise performs: an implementor holds
+/// enough information to re-identify a subject, so it exists for an
+/// authorized data custodian's own crosswalk, not for routine use - the CLI
+/// only wires one up behind an explicit `--mapping-file` flag. Implement
+/// this trait to collect the same crosswalk from an embedder (e.g. the WASM
+/// bindings) without going through the CLI at all.
+pub trait MappingRecorder {
+    fn record(&self, tag: Tag, original_value: &str, anonymized_value: &str);
+}
+
+impl Processor for DefaultProcessor {
+    /// Process a DICOM data element according to the configured anonymization rules
+    ///
+    /// Takes a DICOM object and one of its elements, applies the appropriate anonymization
+    /// action based on the configuration, and returns the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `obj` - Reference to the DICOM object containing the element
+    /// * `elem` - Reference to the element to be processed
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing:
+    /// * `Some(Cow<InMemElement>)` - The processed element
+    /// * `None` - If the element should be removed
+    /// * `Err` - If there was an error processing the element
+    fn process_element<'a>(
+        &'a self,
+        obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>> {
+        self.process_element_inner(obj, elem).map(|(elem, _)| elem)
+    }
+
+    /// Inserts whichever of `PatientIdentityRemoved`, `DeidentificationMethod`,
+    /// and `DeidentificationMethodCodeSequence` are still missing from `obj`
+    /// once every element has been processed.
+    ///
+    /// Nothing here overwrites a tag the configured `tag_actions` already
+    /// produced a value (or no value at all) for - including `Action::Remove`,
+    /// the default [`crate::config::ConfigBuilder::basic_profile`] uses for
+    /// `PatientIdentityRemoved`, specifically because whether identity removal
+    /// actually happened can be overridden by the user and so can't be known
+    /// for sure. This only fills a genuine gap left by a source object that
+    /// never carried the tag to begin with.
+    fn finalize(&self, obj: &mut DefaultDicomObject) {
+        self.fill_in_tag(
+            obj,
+            tags::PATIENT_IDENTITY_REMOVED,
+            VR::CS,
+            Some("YES".to_string()),
+        );
+        self.fill_in_tag(
+            obj,
+            tags::DEIDENTIFICATION_METHOD,
+            VR::LO,
+            Some(DEIDENTIFIER.to_string()),
+        );
+
+        self.fill_in_code_sequence_tag(obj);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DoNothingProcessor;
+
+impl DoNothingProcessor {
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DoNothingProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Processor for DoNothingProcessor {
+    fn process_element<'a>(
+        &'a self,
+        _obj: &DefaultDicomObject,
+        elem: &'a InMemElement,
+    ) -> Result<Option<Cow<'a, InMemElement>>> {
+        // just return it as is, without any changes
+        Ok(Some(Cow::Borrowed(elem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_core::header::HasLength;
+    use dicom_core::value::Value;
+    use dicom_core::{header, PrimitiveValue, VR};
+    use dicom_dictionary_std::tags;
+    use dicom_object::FileDicomObject;
+
+    use crate::actions::Action;
+    use crate::config::ConfigBuilder;
+    use crate::test_utils::make_file_meta;
+    use serde_json;
+
+    #[test]
+    fn test_process_element_hash_length() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("0123456789ABCDEF"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::ACCESSION_NUMBER, Action::Hash { length: None })
+            .build();
+
+        let elem = obj.element(tags::ACCESSION_NUMBER).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(processed.unwrap().value().length(), header::Length(16));
+    }
+
+    #[test]
+    fn test_process_element_hash_max_length() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("0123456789ABCDEF"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::ACCESSION_NUMBER, Action::Hash { length: Some(32) })
+            .build();
+
+        let elem = obj.element(tags::ACCESSION_NUMBER).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        // new value length should have been cut off at the max length for SH VR, which is 16
+        assert_eq!(processed.unwrap().value().length(), header::Length(16));
+    }
+
+    #[test]
+    fn test_process_element_hash_length_with_value() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("0123456789ABCDEF"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::ACCESSION_NUMBER, Action::Hash { length: Some(8) })
+            .build();
+
+        let elem = obj.element(tags::ACCESSION_NUMBER).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(processed.unwrap().value().length(), header::Length(8));
+    }
+
+    #[test]
+    fn test_process_element_hash_date_invalid_hash_date_tag_error() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::STUDY_DATE,
+            VR::DA,
+            Value::from("20010102"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(
+                tags::STUDY_DATE,
+                Action::HashDate {
+                    other_tag: tags::PATIENT_ID,
+                },
+            )
+            .build();
+
+        let elem = obj.element(tags::STUDY_DATE).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+
+        // element should be returned as is because the `PatientID` tag is not in the DICOM object
+        assert_eq!(&processed.unwrap().into_owned(), elem);
+    }
+
+    #[test]
+    fn test_process_element_replace() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(
+                tags::PATIENT_NAME,
+                Action::Replace {
+                    value: "Jane Doe".into(),
+                },
+            )
+            .build();
+
+        let elem = obj.element(tags::PATIENT_NAME).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(processed.unwrap().value(), &Value::from("Jane Doe"));
+    }
+
+    #[test]
+    fn test_process_element_keep() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Keep)
+            .build();
+
+        let elem = obj.element(tags::PATIENT_NAME).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(&processed.unwrap().into_owned(), elem);
+    }
+
+    #[test]
+    fn test_process_element_empty() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Empty)
+            .build();
+
+        let elem = obj.element(tags::PATIENT_NAME).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(
+            processed.unwrap().value(),
+            &Value::Primitive(PrimitiveValue::Empty)
+        );
+    }
+
+    #[test]
+    fn test_process_element_remove() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Remove)
+            .build();
+
+        let elem = obj.element(tags::PATIENT_NAME).unwrap();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(processed, None);
+    }
+
+    #[test]
+    fn test_process_object_applies_processor_to_every_element() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("12345"),
+        ));
+        obj.put(InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("ACC001"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(
+                tags::PATIENT_NAME,
+                Action::Replace {
+                    value: "Jane Doe".into(),
+                },
+            )
+            .tag_action(tags::PATIENT_ID, Action::Remove)
+            .tag_action(tags::ACCESSION_NUMBER, Action::Keep)
+            .build();
+
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_object(&obj).unwrap();
+
+        assert_eq!(
+            processed.element(tags::PATIENT_NAME).unwrap().value(),
+            &Value::from("Jane Doe")
+        );
+        assert!(processed.element(tags::PATIENT_ID).is_err());
+        assert_eq!(
+            processed.element(tags::ACCESSION_NUMBER).unwrap().value(),
+            &Value::from("ACC001")
+        );
+    }
+
+    #[test]
+    fn test_process_object_with_report_describes_every_element_without_leaking_values() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("12345"),
+        ));
+        obj.put(InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("ACC001"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(
+                tags::PATIENT_NAME,
+                Action::Replace {
+                    value: "Jane Doe".into(),
+                },
+            )
+            .tag_action(tags::PATIENT_ID, Action::Remove)
+            .tag_action(tags::ACCESSION_NUMBER, Action::Keep)
+            .build();
+
+        let processor = DefaultProcessor::new(config);
+        let (processed, report) = processor.process_object_with_report(&obj).unwrap();
+
+        assert_eq!(
+            processed.element(tags::PATIENT_NAME).unwrap().value(),
+            &Value::from("Jane Doe")
+        );
+        assert!(processed.element(tags::PATIENT_ID).is_err());
+
+        assert_eq!(report.len(), 3);
+
+        let name_record = report
+            .iter()
+            .find(|record| record.tag.0 == tags::PATIENT_NAME)
+            .unwrap();
+        assert!(name_record.kept);
+        assert_eq!(name_record.action, "Replace");
+        assert_eq!(name_record.transform.as_deref(), Some("replaced"));
+
+        let id_record = report
+            .iter()
+            .find(|record| record.tag.0 == tags::PATIENT_ID)
+            .unwrap();
+        assert!(!id_record.kept);
+        assert_eq!(id_record.action, "Remove");
+        assert_eq!(id_record.transform, None);
+
+        let accession_record = report
+            .iter()
+            .find(|record| record.tag.0 == tags::ACCESSION_NUMBER)
+            .unwrap();
+        assert!(accession_record.kept);
+        assert_eq!(accession_record.action, "Keep");
+        assert_eq!(accession_record.transform, None);
+
+        // no record carries a value field at all, let alone the original one
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains("John Doe"));
+        assert!(!json.contains("Jane Doe"));
+        assert!(!json.contains("12345"));
+
+        assert_eq!(name_record.original_length, "John Doe".len() as u32);
+        assert_eq!(id_record.original_length, "12345".len() as u32);
+        assert_eq!(name_record.downgraded_warning, None);
+    }
+
+    #[test]
+    fn test_process_object_with_report_surfaces_a_downgraded_hash_date_warning() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::STUDY_DATE,
+            VR::DA,
+            Value::from("20010102"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(
+                tags::STUDY_DATE,
+                Action::HashDate {
+                    other_tag: tags::PATIENT_ID,
+                },
+            )
+            .build();
+
+        let processor = DefaultProcessor::new(config);
+        let (_, report) = processor.process_object_with_report(&obj).unwrap();
+
+        let date_record = report
+            .iter()
+            .find(|record| record.tag.0 == tags::STUDY_DATE)
+            .unwrap();
+        assert!(date_record.kept);
+        assert!(date_record.downgraded_warning.is_some());
+    }
+
+    #[test]
+    fn test_process_object_with_mapping_reports_only_changed_values() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+        obj.put(InMemElement::new(
+            tags::PATIENT_ID,
+            VR::LO,
+            Value::from("12345"),
+        ));
+        obj.put(InMemElement::new(
+            tags::ACCESSION_NUMBER,
+            VR::SH,
+            Value::from("ACC001"),
+        ));
+
+        let config = ConfigBuilder::new()
+            .tag_action(
+                tags::PATIENT_NAME,
+                Action::Replace {
+                    value: "Jane Doe".into(),
+                },
+            )
+            .tag_action(tags::PATIENT_ID, Action::Remove)
+            .tag_action(tags::ACCESSION_NUMBER, Action::Keep)
+            .build();
+
+        #[derive(Default)]
+        struct RecordingMappingRecorder {
+            records: std::sync::Mutex<Vec<(Tag, String, String)>>,
+        }
+
+        impl MappingRecorder for RecordingMappingRecorder {
+            fn record(&self, tag: Tag, original_value: &str, anonymized_value: &str) {
+                self.records.lock().unwrap().push((
+                    tag,
+                    original_value.to_string(),
+                    anonymized_value.to_string(),
+                ));
+            }
+        }
+
+        let processor = DefaultProcessor::new(config);
+        let recorder = RecordingMappingRecorder::default();
+        let processed = processor
+            .process_object_with_mapping(&obj, &recorder)
+            .unwrap();
+
+        assert_eq!(
+            processed.element(tags::PATIENT_NAME).unwrap().value(),
+            &Value::from("Jane Doe")
+        );
+
+        let records = recorder.records.into_inner().unwrap();
+        assert_eq!(
+            records,
+            vec![(
+                tags::PATIENT_NAME,
+                "John Doe".to_string(),
+                "Jane Doe".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_process_object_inserts_deidentification_attributes_when_missing() {
+        let meta = make_file_meta();
+        let obj = FileDicomObject::new_empty_with_meta(meta);
+
+        let config = ConfigBuilder::basic_profile().retain_uids(true).build();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_object(&obj).unwrap();
+
+        assert_eq!(
+            processed
+                .element(tags::PATIENT_IDENTITY_REMOVED)
+                .unwrap()
+                .value()
+                .to_str()
+                .unwrap(),
+            "YES"
+        );
+        assert_eq!(
+            processed
+                .element(tags::DEIDENTIFICATION_METHOD)
+                .unwrap()
+                .value()
+                .to_str()
+                .unwrap(),
+            "CARECODERS"
+        );
+        let code_sequence_value = processed
+            .element(tags::DEIDENTIFICATION_METHOD_CODE_SEQUENCE)
+            .unwrap()
+            .value();
+        let items = match code_sequence_value {
+            Value::Sequence(seq) => seq.items(),
+            other => panic!("expected a Sequence value, got {other:?}"),
+        };
+        let codes: Vec<String> = items
+            .iter()
+            .map(|item| {
+                item.element(tags::CODE_VALUE)
+                    .unwrap()
+                    .value()
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(codes, vec!["113100".to_string(), "113110".to_string()]);
+        assert_eq!(
+            items[0]
+                .element(tags::CODING_SCHEME_DESIGNATOR)
+                .unwrap()
+                .value()
+                .to_str()
+                .unwrap(),
+            "DCM"
+        );
+    }
+
+    #[test]
+    fn test_process_object_does_not_overwrite_an_already_present_deidentification_attribute() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_IDENTITY_REMOVED,
+            VR::CS,
+            Value::from("NO"),
+        ));
+
+        let config = ConfigBuilder::new().build();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_object(&obj).unwrap();
+
+        assert_eq!(
+            processed
+                .element(tags::PATIENT_IDENTITY_REMOVED)
+                .unwrap()
+                .value()
+                .to_str()
+                .unwrap(),
+            "NO"
+        );
+    }
+
+    #[test]
+    fn test_process_object_respects_an_explicit_remove_action_for_a_deidentification_attribute() {
+        let meta = make_file_meta();
+        let obj = FileDicomObject::new_empty_with_meta(meta);
+
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_IDENTITY_REMOVED, Action::Remove)
+            .build();
+        let processor = DefaultProcessor::new(config);
+        let processed = processor.process_object(&obj).unwrap();
+
+        assert!(processed.element(tags::PATIENT_IDENTITY_REMOVED).is_err());
+    }
+
+    #[test]
+    fn test_do_nothing_processor() {
+        let meta = make_file_meta();
+        let mut obj = FileDicomObject::new_empty_with_meta(meta);
+
+        obj.put(InMemElement::new(
+            tags::PATIENT_NAME,
+            VR::PN,
+            Value::from("John Doe"),
+        ));
+
+        let elem = obj.element(tags::PATIENT_NAME).unwrap();
+        let processor = DoNothingProcessor::new();
+        let processed = processor.process_element(&obj, elem).unwrap();
+        assert_eq!(processed.unwrap().into_owned(), elem.clone());
+    }
+}
@@ -0,0 +1,81 @@
+use thiserror::Error;
+
+use crate::config::Config;
+
+#[derive(Error, Debug)]
+pub enum RelaxedConfigError {
+    #[error("failed to parse config: {0}")]
+    Parse(String),
+}
+
+/// Loads a [`Config`] from a relaxed JSON5/Hjson-style dialect instead of
+/// strict JSON: `//` and `/* */` comments, trailing commas, and unquoted
+/// object keys are all accepted.
+///
+/// This is an opt-in alternative to parsing `input` with `serde_json`
+/// directly - [`Config`]'s own `Deserialize` impl, and therefore strict JSON,
+/// remains the default everywhere else in this crate. It exists because
+/// anonymization profiles are large, hand-edited tag maps, and allowing
+/// comments and trailing commas makes them far less painful to annotate and
+/// review. Tag format and action validation are unaffected: both are
+/// enforced by [`crate::config::TagActionMap`]'s `Deserialize` impl, which
+/// this function goes through exactly the same as the strict JSON path.
+pub fn load_relaxed_config(input: &str) -> Result<Config, RelaxedConfigError> {
+    json5::from_str(input).map_err(|err| RelaxedConfigError::Parse(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::actions::Action;
+    use dicom_dictionary_std::tags;
+
+    #[test]
+    fn test_loads_plain_json() {
+        let config = load_relaxed_config(r#"{"uid_root": "1.2.3"}"#).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+    }
+
+    #[test]
+    fn test_accepts_comments_and_trailing_commas() {
+        let input = r#"{
+            // this profile redacts direct identifiers
+            uid_root: "1.2.3",
+            tag_actions: {
+                "(0010,0010)": { action: "remove" }, /* patient name */
+            },
+        }"#;
+
+        let config = load_relaxed_config(input).unwrap();
+        assert_eq!(config.get_uid_root().as_ref(), "1.2.3");
+        assert_eq!(
+            config.get_tag_actions().get(&tags::PATIENT_NAME),
+            Some(&Action::Remove)
+        );
+    }
+
+    #[test]
+    fn test_still_validates_tag_format() {
+        let input = r#"{
+            tag_actions: {
+                "not-a-tag": { action: "remove" },
+            },
+        }"#;
+
+        let result = load_relaxed_config(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_still_validates_action() {
+        let input = r#"{
+            tag_actions: {
+                "(0010,0010)": { action: "hash", length: 0 },
+            },
+        }"#;
+
+        let result = load_relaxed_config(input);
+        assert!(result.is_err());
+    }
+}
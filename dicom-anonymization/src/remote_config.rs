@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use log::warn;
+use thiserror::Error;
+
+use crate::config::Config;
+
+#[derive(Error, Debug)]
+pub enum RemoteConfigError {
+    #[error("failed to fetch remote config: {0}")]
+    Fetch(String),
+
+    #[error("malformed remote config: {0}")]
+    Malformed(String),
+}
+
+/// Loads a [`Config`] from a remote HTTP(S) endpoint and caches the last
+/// known-good copy to disk, so a fleet of anonymizer instances can share a
+/// centrally-managed profile that updates without a redeploy.
+///
+/// A fetch failure or a malformed payload from the endpoint never tears down
+/// an instance that's already running: [`Self::reload`] logs the problem and
+/// keeps serving the previous good config (initially, the one cached on disk
+/// from a prior run) instead of returning an error.
+pub struct RemoteConfigLoader {
+    url: String,
+    cache_path: PathBuf,
+    refresh_interval: Duration,
+    etag: Option<String>,
+    last_fetched: Option<Instant>,
+    current: Config,
+}
+
+impl RemoteConfigLoader {
+    /// Creates a loader for `url`, caching the fetched config to `cache_path`.
+    ///
+    /// The initial config is read from `cache_path` if it already holds one
+    /// from a previous run, falling back to [`Config::default`] otherwise, so
+    /// [`Self::current`] always has something to return even before the
+    /// first [`Self::reload`].
+    pub fn new(
+        url: impl Into<String>,
+        cache_path: impl Into<PathBuf>,
+        refresh_interval: Duration,
+    ) -> Self {
+        let cache_path = cache_path.into();
+        let current = Self::read_cache(&cache_path).unwrap_or_default();
+
+        Self {
+            url: url.into(),
+            cache_path,
+            refresh_interval,
+            etag: None,
+            last_fetched: None,
+            current,
+        }
+    }
+
+    /// Returns the most recently loaded config: the latest successful fetch,
+    /// the on-disk cache from a previous run, or [`Config::default`] if
+    /// neither is available yet.
+    pub fn current(&self) -> &Config {
+        &self.current
+    }
+
+    /// Returns whether `refresh_interval` has elapsed since the last call to
+    /// [`Self::reload`] (or `reload` has never been called), i.e. whether a
+    /// periodic caller is due to call it again.
+    pub fn needs_refresh(&self) -> bool {
+        match self.last_fetched {
+            Some(last) => last.elapsed() >= self.refresh_interval,
+            None => true,
+        }
+    }
+
+    /// Fetches the config from `url`, updating [`Self::current`] and the
+    /// on-disk cache on success.
+    ///
+    /// On a request failure or a payload that doesn't deserialize into a
+    /// [`Config`], this logs a warning naming the failure and leaves
+    /// [`Self::current`] exactly as it was, rather than returning an error -
+    /// callers that poll this periodically shouldn't have one bad fetch stop
+    /// them from ever trying again.
+    pub fn reload(&mut self) {
+        match self.fetch() {
+            Ok(Some(config)) => self.current = config,
+            // 304 Not Modified: the cached copy is still current.
+            Ok(None) => {}
+            Err(err) => warn!("{err}, keeping previous config"),
+        }
+        self.last_fetched = Some(Instant::now());
+    }
+
+    fn fetch(&mut self) -> Result<Option<Config>, RemoteConfigError> {
+        let mut request = ureq::get(&self.url);
+        if let Some(etag) = &self.etag {
+            request = request.set("If-None-Match", etag);
+        }
+
+        let response = request
+            .call()
+            .map_err(|e| RemoteConfigError::Fetch(e.to_string()))?;
+
+        if response.status() == 304 {
+            return Ok(None);
+        }
+
+        let etag = response.header("ETag").map(str::to_string);
+        let body = response
+            .into_string()
+            .map_err(|e| RemoteConfigError::Fetch(e.to_string()))?;
+
+        let config: Config =
+            serde_json::from_str(&body).map_err(|e| RemoteConfigError::Malformed(e.to_string()))?;
+
+        self.etag = etag;
+        self.write_cache(&body);
+
+        Ok(Some(config))
+    }
+
+    fn read_cache(path: &Path) -> Option<Config> {
+        let body = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&body).ok()
+    }
+
+    fn write_cache(&self, body: &str) {
+        if let Err(err) = fs::write(&self.cache_path, body) {
+            warn!(
+                "failed to write remote config cache to {}: {err}",
+                self.cache_path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_cache_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "dicom_anonymization_remote_config_test_{name}_{:?}",
+            std::thread::current().id()
+        ))
+    }
+
+    #[test]
+    fn test_new_falls_back_to_default_when_no_cache_exists() {
+        let cache_path = unique_cache_path("missing");
+        let _ = fs::remove_file(&cache_path);
+
+        let loader = RemoteConfigLoader::new(
+            "http://example.invalid",
+            cache_path,
+            Duration::from_secs(60),
+        );
+        assert_eq!(loader.current(), &Config::default());
+    }
+
+    #[test]
+    fn test_new_reads_existing_cache() {
+        let cache_path = unique_cache_path("existing");
+        fs::write(&cache_path, r#"{"uid_root": "1.2.3"}"#).unwrap();
+
+        let loader = RemoteConfigLoader::new(
+            "http://example.invalid",
+            &cache_path,
+            Duration::from_secs(60),
+        );
+        assert_eq!(loader.current().get_uid_root().as_ref(), "1.2.3");
+
+        fs::remove_file(&cache_path).unwrap();
+    }
+
+    #[test]
+    fn test_needs_refresh_before_first_reload() {
+        let cache_path = unique_cache_path("needs-refresh");
+        let _ = fs::remove_file(&cache_path);
+
+        let loader = RemoteConfigLoader::new(
+            "http://example.invalid",
+            cache_path,
+            Duration::from_secs(60),
+        );
+        assert!(loader.needs_refresh());
+    }
+
+    #[test]
+    fn test_reload_keeps_previous_config_on_fetch_failure() {
+        let cache_path = unique_cache_path("fetch-failure");
+        fs::write(&cache_path, r#"{"uid_root": "1.2.3"}"#).unwrap();
+
+        let mut loader = RemoteConfigLoader::new(
+            "http://127.0.0.1:1/unreachable",
+            &cache_path,
+            Duration::from_secs(60),
+        );
+        loader.reload();
+
+        // the unreachable URL never overwrote the config loaded from cache
+        assert_eq!(loader.current().get_uid_root().as_ref(), "1.2.3");
+        assert!(!loader.needs_refresh());
+
+        fs::remove_file(&cache_path).unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+use dicom_object::meta::{FileMetaTable, FileMetaTableBuilder};
+
+/// Builds a minimal, valid [`FileMetaTable`] for constructing a
+/// [`dicom_object::FileDicomObject`] in a test, under Explicit VR Little
+/// Endian (the transfer syntax most of this crate's action tests assume,
+/// since it's the one where an element always carries its own VR).
+pub(crate) fn make_file_meta() -> FileMetaTable {
+    FileMetaTableBuilder::new()
+        .media_storage_sop_class_uid("1.2.840.10008.5.1.4.1.1.7")
+        .media_storage_sop_instance_uid("1.2.3.4.5.6.7.8.9")
+        .transfer_syntax("1.2.840.10008.1.2.1")
+        .build()
+        .expect("a minimal, valid file meta table")
+}
@@ -0,0 +1,136 @@
+use dicom_object::DefaultDicomObject;
+use serde::{Deserialize, Serialize};
+
+/// The Implicit VR Little Endian transfer syntax UID.
+pub const IMPLICIT_VR_LITTLE_ENDIAN_UID: &str = "1.2.840.10008.1.2";
+/// The Explicit VR Little Endian transfer syntax UID.
+pub const EXPLICIT_VR_LITTLE_ENDIAN_UID: &str = "1.2.840.10008.1.2.1";
+/// The (retired) Explicit VR Big Endian transfer syntax UID.
+pub const EXPLICIT_VR_BIG_ENDIAN_UID: &str = "1.2.840.10008.1.2.2";
+
+/// The transfer syntax a DICOM object's dataset is encoded under.
+///
+/// [`TransferSyntax::Encapsulated`] covers every compressed transfer syntax
+/// (JPEG, JPEG 2000, RLE Lossless, and so on) that this crate doesn't decode;
+/// it carries the UID so callers can still identify which one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferSyntax {
+    ImplicitVrLittleEndian,
+    ExplicitVrLittleEndian,
+    ExplicitVrBigEndian,
+    Encapsulated(String),
+}
+
+impl TransferSyntax {
+    /// Returns the transfer syntax UID this variant stands for.
+    pub fn uid(&self) -> &str {
+        match self {
+            TransferSyntax::ImplicitVrLittleEndian => IMPLICIT_VR_LITTLE_ENDIAN_UID,
+            TransferSyntax::ExplicitVrLittleEndian => EXPLICIT_VR_LITTLE_ENDIAN_UID,
+            TransferSyntax::ExplicitVrBigEndian => EXPLICIT_VR_BIG_ENDIAN_UID,
+            TransferSyntax::Encapsulated(uid) => uid,
+        }
+    }
+
+    /// Returns whether elements under this transfer syntax carry their VR on
+    /// the wire. Implicit VR Little Endian is the only transfer syntax that
+    /// doesn't; a dictionary lookup (see [`crate::actions::utils::resolve_vr`])
+    /// is needed to know an element's VR under it.
+    pub fn is_explicit(&self) -> bool {
+        !matches!(self, TransferSyntax::ImplicitVrLittleEndian)
+    }
+
+    /// Returns whether pixel data under this transfer syntax is encapsulated
+    /// (compressed) rather than native. Actions that operate directly on
+    /// pixel samples (e.g. [`crate::actions::Rect`]-based blanking) only
+    /// support native pixel data.
+    pub fn is_encapsulated(&self) -> bool {
+        matches!(self, TransferSyntax::Encapsulated(_))
+    }
+}
+
+/// Detects the transfer syntax `obj`'s dataset was parsed under, from its File
+/// Meta Information (0002,0010) `TransferSyntaxUID`.
+///
+/// `TransferSyntaxUID` may carry a trailing padding byte (`\0` or space),
+/// since UI values are padded to an even length like any other string VR.
+/// Unrecognized UIDs - which in practice means a compressed transfer syntax
+/// this crate doesn't decode - resolve to [`TransferSyntax::Encapsulated`]
+/// carrying the UID as given, rather than failing: by the time `obj` exists,
+/// the underlying DICOM parser has already used this same UID to decode the
+/// dataset, so there's no heuristic left for this crate to apply itself.
+pub fn detect(obj: &DefaultDicomObject) -> TransferSyntax {
+    match obj.meta().transfer_syntax.trim_end_matches(['\0', ' ']) {
+        IMPLICIT_VR_LITTLE_ENDIAN_UID => TransferSyntax::ImplicitVrLittleEndian,
+        EXPLICIT_VR_LITTLE_ENDIAN_UID => TransferSyntax::ExplicitVrLittleEndian,
+        EXPLICIT_VR_BIG_ENDIAN_UID => TransferSyntax::ExplicitVrBigEndian,
+        other => TransferSyntax::Encapsulated(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dicom_object::FileDicomObject;
+
+    use crate::test_utils::make_file_meta;
+
+    #[test]
+    fn test_detect_implicit_vr_little_endian() {
+        let mut meta = make_file_meta();
+        meta.transfer_syntax = IMPLICIT_VR_LITTLE_ENDIAN_UID.to_string();
+        let obj = FileDicomObject::new_empty_with_meta(meta);
+        assert_eq!(detect(&obj), TransferSyntax::ImplicitVrLittleEndian);
+    }
+
+    #[test]
+    fn test_detect_explicit_vr_little_endian() {
+        let obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+        assert_eq!(detect(&obj), TransferSyntax::ExplicitVrLittleEndian);
+    }
+
+    #[test]
+    fn test_detect_explicit_vr_big_endian() {
+        let mut meta = make_file_meta();
+        meta.transfer_syntax = EXPLICIT_VR_BIG_ENDIAN_UID.to_string();
+        let obj = FileDicomObject::new_empty_with_meta(meta);
+        assert_eq!(detect(&obj), TransferSyntax::ExplicitVrBigEndian);
+    }
+
+    #[test]
+    fn test_detect_tolerates_trailing_padding() {
+        let mut meta = make_file_meta();
+        meta.transfer_syntax = format!("{IMPLICIT_VR_LITTLE_ENDIAN_UID}\0");
+        let obj = FileDicomObject::new_empty_with_meta(meta);
+        assert_eq!(detect(&obj), TransferSyntax::ImplicitVrLittleEndian);
+    }
+
+    #[test]
+    fn test_detect_unrecognized_uid_is_encapsulated() {
+        // JPEG Baseline
+        let mut meta = make_file_meta();
+        meta.transfer_syntax = "1.2.840.10008.1.2.4.50".to_string();
+        let obj = FileDicomObject::new_empty_with_meta(meta);
+        assert_eq!(
+            detect(&obj),
+            TransferSyntax::Encapsulated("1.2.840.10008.1.2.4.50".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_explicit() {
+        assert!(!TransferSyntax::ImplicitVrLittleEndian.is_explicit());
+        assert!(TransferSyntax::ExplicitVrLittleEndian.is_explicit());
+        assert!(TransferSyntax::ExplicitVrBigEndian.is_explicit());
+        assert!(TransferSyntax::Encapsulated("1.2.840.10008.1.2.4.50".to_string()).is_explicit());
+    }
+
+    #[test]
+    fn test_is_encapsulated() {
+        assert!(!TransferSyntax::ExplicitVrLittleEndian.is_encapsulated());
+        assert!(
+            TransferSyntax::Encapsulated("1.2.840.10008.1.2.4.50".to_string()).is_encapsulated()
+        );
+    }
+}
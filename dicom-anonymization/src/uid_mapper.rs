@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use num_bigint::BigInt;
+use num_traits::Num;
+
+use crate::config::UidRoot;
+
+const UID_MAX_LENGTH: usize = 64;
+
+/// Deterministically maps each original DICOM UID to a newly generated one
+/// rooted at a configured [`UidRoot`], caching every mapping so the same
+/// source UID always resolves to the same replacement - whether it's seen
+/// again later in the same object, in a different file processed later in
+/// the same run, or (once its state is restored via [`Self::restore`] from a
+/// prior [`Self::entries`] export) in an entirely separate run.
+///
+/// This is what [`Action::HashUID`](crate::actions::Action::HashUID) alone
+/// can't promise across a whole dataset: hashing each UID independently,
+/// with no shared cache, keeps Study/Series/SOPInstanceUID references (and
+/// anything else pointing at a UID - a Frame of Reference, a
+/// referenced-image sequence) consistent only within the call that produced
+/// them, not across the files that refer to each other.
+#[derive(Debug, Default)]
+pub struct UidMapper {
+    uid_root: UidRoot,
+    mapped: Mutex<HashMap<String, String>>,
+}
+
+impl UidMapper {
+    /// Creates an empty mapper that generates new UIDs rooted at `uid_root`.
+    pub fn new(uid_root: UidRoot) -> Self {
+        Self {
+            uid_root,
+            mapped: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the UID `original` maps to, generating and caching one the
+    /// first time `original` is seen and returning the same value on every
+    /// later call with that input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dicom_anonymization::config::UidRoot;
+    /// use dicom_anonymization::uid_mapper::UidMapper;
+    ///
+    /// let mapper = UidMapper::new("1.2.840.123".parse().unwrap());
+    /// let first = mapper.map("1.2.3.4.5");
+    /// let second = mapper.map("1.2.3.4.5");
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn map(&self, original: &str) -> String {
+        let mut mapped = self.mapped.lock().unwrap();
+
+        if let Some(existing) = mapped.get(original) {
+            return existing.clone();
+        }
+
+        let generated = Self::generate(&self.uid_root, original);
+        mapped.insert(original.to_string(), generated.clone());
+        generated
+    }
+
+    /// Generates a new UID for `original`, rooted at `uid_root`: a BLAKE3
+    /// hash of `original`, reinterpreted as a decimal integer (a DICOM UID
+    /// may only contain digits and dots, ruling out the hash's own hex
+    /// digest), truncated to fit within the 64-character UID length limit
+    /// alongside the root.
+    fn generate(uid_root: &UidRoot, original: &str) -> String {
+        let hash = blake3::hash(original.as_bytes());
+        let hash_as_number = BigInt::from_str_radix(hash.to_hex().as_str(), 16)
+            .expect("a hex digest is always valid base 16");
+
+        let prefix = uid_root.as_prefix();
+        let max_suffix_len = UID_MAX_LENGTH.saturating_sub(prefix.len());
+        let suffix = hash_as_number.to_string();
+        let suffix = &suffix[..suffix.len().min(max_suffix_len)];
+
+        format!("{prefix}{suffix}")
+    }
+
+    /// Returns every mapping recorded so far, as `(original, generated)`
+    /// pairs, suitable for persisting (e.g. as JSON via `serde_json`) and
+    /// later handing back to [`Self::restore`].
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.mapped
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(original, generated)| (original.clone(), generated.clone()))
+            .collect()
+    }
+
+    /// Seeds this mapper with mappings captured earlier by [`Self::entries`],
+    /// so UIDs already mapped in a prior run keep resolving to the same
+    /// replacement instead of getting a newly generated one. An `original`
+    /// already cached locally is left untouched rather than overwritten.
+    pub fn restore(&self, entries: impl IntoIterator<Item = (String, String)>) {
+        let mut mapped = self.mapped.lock().unwrap();
+        for (original, generated) in entries {
+            mapped.entry(original).or_insert(generated);
+        }
+    }
+}
+
+impl PartialEq for UidMapper {
+    // Two mappers are equal iff they're rooted the same, regardless of what
+    // they've cached so far - the cache is accumulated runtime state, not
+    // structural configuration, the same way `HashFn`'s wrapped closure isn't
+    // either.
+    fn eq(&self, other: &Self) -> bool {
+        self.uid_root == other.uid_root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_is_deterministic() {
+        let mapper = UidMapper::new(UidRoot::default());
+        assert_eq!(mapper.map("1.2.3.4.5"), mapper.map("1.2.3.4.5"));
+    }
+
+    #[test]
+    fn test_map_differs_by_input() {
+        let mapper = UidMapper::new(UidRoot::default());
+        assert_ne!(mapper.map("1.2.3.4.5"), mapper.map("1.2.3.4.6"));
+    }
+
+    #[test]
+    fn test_map_is_prefixed_with_the_uid_root() {
+        let mapper = UidMapper::new("2.16.840".parse().unwrap());
+        assert!(mapper.map("1.2.3.4.5").starts_with("2.16.840."));
+    }
+
+    #[test]
+    fn test_map_never_exceeds_the_uid_length_limit() {
+        let mapper = UidMapper::new("2.16.840".parse().unwrap());
+        assert!(mapper.map("1.2.3.4.5").len() <= UID_MAX_LENGTH);
+    }
+
+    #[test]
+    fn test_entries_round_trip_through_restore() {
+        let original_run = UidMapper::new(UidRoot::default());
+        let generated = original_run.map("1.2.3.4.5");
+
+        let later_run = UidMapper::new(UidRoot::default());
+        later_run.restore(original_run.entries());
+
+        assert_eq!(later_run.map("1.2.3.4.5"), generated);
+    }
+
+    #[test]
+    fn test_restore_does_not_overwrite_an_already_cached_mapping() {
+        let mapper = UidMapper::new(UidRoot::default());
+        let generated = mapper.map("1.2.3.4.5");
+
+        mapper.restore([("1.2.3.4.5".to_string(), "9.9.9.9.9".to_string())]);
+
+        assert_eq!(mapper.map("1.2.3.4.5"), generated);
+    }
+}
@@ -0,0 +1,293 @@
+use std::time::{Duration, Instant};
+
+use dicom_core::header::Header;
+use dicom_core::value::{PrimitiveValue, Value};
+use dicom_core::VR;
+use dicom_object::mem::InMemElement;
+use dicom_object::{DefaultDicomObject, FileDicomObject};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::processor::Processor;
+use crate::test_utils::make_file_meta;
+
+/// One VR the generator may assign to a synthesized element, weighted by how
+/// often it should appear relative to the other entries in
+/// [`WorkloadSpec::vr_weights`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrWeight {
+    pub vr: VR,
+    pub weight: u32,
+}
+
+impl VrWeight {
+    pub fn new(vr: VR, weight: u32) -> Self {
+        Self { vr, weight }
+    }
+}
+
+/// Describes a synthetic DICOM corpus to generate: how many objects, how
+/// many elements per object, the VR mix, and the byte-size range for any
+/// `OB` (pixel data-like) elements.
+///
+/// Generation is driven entirely by `seed`, so two runs with the same spec
+/// produce byte-identical objects - needed to diff benchmark results across
+/// commits rather than across both the commit *and* the random data.
+#[derive(Debug, Clone)]
+pub struct WorkloadSpec {
+    pub object_count: usize,
+    pub elements_per_object: usize,
+    pub vr_weights: Vec<VrWeight>,
+    pub pixel_data_size_range: (usize, usize),
+    pub seed: [u8; 32],
+}
+
+impl WorkloadSpec {
+    /// A workload spec skewed toward the VRs most common in real studies
+    /// (`SH`/`PN`/`DA`/`UI`, with a modest share of `OB` pixel-like data),
+    /// useful as a starting point for ad hoc benchmark runs.
+    pub fn representative(object_count: usize, elements_per_object: usize, seed: [u8; 32]) -> Self {
+        Self {
+            object_count,
+            elements_per_object,
+            vr_weights: vec![
+                VrWeight::new(VR::SH, 3),
+                VrWeight::new(VR::PN, 2),
+                VrWeight::new(VR::DA, 2),
+                VrWeight::new(VR::UI, 2),
+                VrWeight::new(VR::OB, 1),
+            ],
+            pixel_data_size_range: (1024, 64 * 1024),
+            seed,
+        }
+    }
+}
+
+/// Generates `spec.object_count` synthetic [`FileDicomObject`]s, each with
+/// `spec.elements_per_object` elements whose VRs are drawn from
+/// `spec.vr_weights`.
+pub fn generate_workload(spec: &WorkloadSpec) -> Vec<DefaultDicomObject> {
+    let mut rng = StdRng::from_seed(spec.seed);
+    let total_weight: u32 = spec.vr_weights.iter().map(|w| w.weight).sum();
+    assert!(total_weight > 0, "vr_weights must carry positive weight");
+
+    (0..spec.object_count)
+        .map(|_| generate_object(&mut rng, spec, total_weight))
+        .collect()
+}
+
+fn generate_object(rng: &mut StdRng, spec: &WorkloadSpec, total_weight: u32) -> DefaultDicomObject {
+    let mut obj = FileDicomObject::new_empty_with_meta(make_file_meta());
+
+    for element_number in 0..spec.elements_per_object {
+        let vr = pick_vr(rng, &spec.vr_weights, total_weight);
+        let tag = dicom_core::Tag(0x0009, (element_number % u16::MAX as usize) as u16);
+        let value = generate_value(rng, vr, spec.pixel_data_size_range);
+        obj.put(InMemElement::new(tag, vr, value));
+    }
+
+    obj
+}
+
+fn pick_vr(rng: &mut StdRng, vr_weights: &[VrWeight], total_weight: u32) -> VR {
+    let mut choice = rng.gen_range(0..total_weight);
+    for entry in vr_weights {
+        if choice < entry.weight {
+            return entry.vr;
+        }
+        choice -= entry.weight;
+    }
+    vr_weights.last().expect("vr_weights must be non-empty").vr
+}
+
+fn generate_value(
+    rng: &mut StdRng,
+    vr: VR,
+    pixel_data_size_range: (usize, usize),
+) -> Value<InMemElement> {
+    match vr {
+        VR::DA => {
+            let year = rng.gen_range(1960..2030);
+            let month = rng.gen_range(1..=12);
+            let day = rng.gen_range(1..=28);
+            Value::from(format!("{year:04}{month:02}{day:02}"))
+        }
+        VR::UI => Value::from(format!(
+            "1.2.840.{}.{}",
+            rng.gen_range(1..1_000_000u32),
+            rng.gen_range(1..1_000_000u32)
+        )),
+        VR::OB => {
+            let (min, max) = pixel_data_size_range;
+            let len = rng.gen_range(min..=max.max(min));
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            Value::Primitive(PrimitiveValue::U8(bytes.into()))
+        }
+        _ => {
+            let len = rng.gen_range(4..16);
+            let value: String = (0..len)
+                .map(|_| rng.sample(rand::distributions::Alphanumeric) as char)
+                .collect();
+            Value::from(value)
+        }
+    }
+}
+
+/// Per-item and aggregate latency/throughput numbers from running a
+/// [`Processor`] over a generated corpus, in a shape that diffs cleanly
+/// across commits when serialized to JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadReport {
+    pub item_count: usize,
+    pub min_latency_ms: f64,
+    pub mean_latency_ms: f64,
+    pub p50_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub throughput_bytes_per_sec: f64,
+}
+
+/// Runs `processor` over every object in `corpus`, returning a
+/// [`WorkloadReport`] summarizing per-object latency and aggregate
+/// throughput.
+///
+/// Object sizes are measured once up front by serializing each object (not
+/// included in the timed latency), so throughput reflects the same
+/// on-the-wire bytes a real pipeline would process.
+pub fn run_workload(processor: &impl Processor, corpus: &[DefaultDicomObject]) -> WorkloadReport {
+    let mut latencies = Vec::with_capacity(corpus.len());
+    let mut total_bytes: u64 = 0;
+
+    for obj in corpus {
+        let mut buf = Vec::new();
+        obj.write_all(&mut buf)
+            .expect("generated workload object failed to serialize");
+        total_bytes += buf.len() as u64;
+
+        let start = Instant::now();
+        processor
+            .process_object(obj)
+            .expect("processing a generated workload object failed");
+        latencies.push(start.elapsed());
+    }
+
+    summarize(&latencies, total_bytes)
+}
+
+fn summarize(latencies: &[Duration], total_bytes: u64) -> WorkloadReport {
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+
+    let to_ms = |d: Duration| d.as_secs_f64() * 1_000.0;
+    let percentile = |p: f64| -> f64 {
+        if sorted.is_empty() {
+            return 0.0;
+        }
+        let rank = ((p * (sorted.len() - 1) as f64).round() as usize).min(sorted.len() - 1);
+        to_ms(sorted[rank])
+    };
+
+    let total: Duration = sorted.iter().sum();
+    let mean_ms = if sorted.is_empty() {
+        0.0
+    } else {
+        to_ms(total) / sorted.len() as f64
+    };
+
+    let throughput_bytes_per_sec = if total.as_secs_f64() > 0.0 {
+        total_bytes as f64 / total.as_secs_f64()
+    } else {
+        0.0
+    };
+
+    WorkloadReport {
+        item_count: sorted.len(),
+        min_latency_ms: sorted.first().copied().map(to_ms).unwrap_or(0.0),
+        mean_latency_ms: mean_ms,
+        p50_latency_ms: percentile(0.50),
+        p95_latency_ms: percentile(0.95),
+        p99_latency_ms: percentile(0.99),
+        throughput_bytes_per_sec,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::actions::Action;
+    use crate::config::ConfigBuilder;
+    use crate::processor::DefaultProcessor;
+    use dicom_dictionary_std::tags;
+
+    #[test]
+    fn test_generate_workload_is_deterministic_given_the_same_seed() {
+        let spec = WorkloadSpec::representative(3, 10, [7u8; 32]);
+
+        let first: Vec<Vec<u8>> = generate_workload(&spec)
+            .iter()
+            .map(|obj| {
+                let mut buf = Vec::new();
+                obj.write_all(&mut buf).unwrap();
+                buf
+            })
+            .collect();
+        let second: Vec<Vec<u8>> = generate_workload(&spec)
+            .iter()
+            .map(|obj| {
+                let mut buf = Vec::new();
+                obj.write_all(&mut buf).unwrap();
+                buf
+            })
+            .collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_generate_workload_produces_the_requested_shape() {
+        let spec = WorkloadSpec::representative(2, 5, [1u8; 32]);
+        let corpus = generate_workload(&spec);
+
+        assert_eq!(corpus.len(), 2);
+        for obj in &corpus {
+            assert_eq!(obj.iter().count(), 5);
+        }
+    }
+
+    #[test]
+    fn test_run_workload_reports_every_item_and_nonzero_throughput() {
+        let config = ConfigBuilder::new()
+            .tag_action(tags::PATIENT_NAME, Action::Remove)
+            .build();
+        let processor = DefaultProcessor::new(config);
+        let spec = WorkloadSpec::representative(5, 20, [42u8; 32]);
+        let corpus = generate_workload(&spec);
+
+        let report = run_workload(&processor, &corpus);
+
+        assert_eq!(report.item_count, 5);
+        assert!(report.throughput_bytes_per_sec > 0.0);
+        assert!(report.min_latency_ms <= report.mean_latency_ms);
+        assert!(report.mean_latency_ms <= report.p99_latency_ms);
+        assert!(report.p50_latency_ms <= report.p95_latency_ms);
+        assert!(report.p95_latency_ms <= report.p99_latency_ms);
+    }
+
+    #[test]
+    fn test_report_serializes_to_json() {
+        let report = WorkloadReport {
+            item_count: 1,
+            min_latency_ms: 1.0,
+            mean_latency_ms: 1.0,
+            p50_latency_ms: 1.0,
+            p95_latency_ms: 1.0,
+            p99_latency_ms: 1.0,
+            throughput_bytes_per_sec: 100.0,
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"p99_latency_ms\":1.0"));
+    }
+}
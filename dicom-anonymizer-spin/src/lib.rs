@@ -16,11 +16,15 @@ use std::io::Cursor;
 struct CustomAnonymizationRequest {
     dicom_data: String,
     config: Option<serde_json::Value>,
+    #[serde(default)]
+    with_report: bool,
 }
 
 #[derive(Serialize)]
 struct CustomAnonymizationResponse {
     anonymized_data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audit_report: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -105,10 +109,11 @@ fn anonymize_custom(req: Request, _params: Params) -> Result<impl IntoResponse>
         None
     };
 
-    match perform_anonymization(&dicom_data, config.as_ref()) {
-        Ok(anonymized_data) => {
+    match perform_anonymization(&dicom_data, config.as_ref(), request.with_report) {
+        Ok((anonymized_data, audit_report)) => {
             let response = CustomAnonymizationResponse {
                 anonymized_data: BASE64.encode(&anonymized_data),
+                audit_report,
             };
             Ok(Response::builder()
                 .status(200)
@@ -120,10 +125,16 @@ fn anonymize_custom(req: Request, _params: Params) -> Result<impl IntoResponse>
     }
 }
 
+/// Anonymizes `dicom_data`, returning the re-encoded bytes and, if
+/// `with_report` is set, a JSON audit trail of every action applied -
+/// surfaced to callers via [`CustomAnonymizationResponse::audit_report`] for
+/// compliance records. Mirrors `Processor::process_object_with_report` in
+/// the `dicom_anonymization` crate's anonymization pipeline.
 fn perform_anonymization(
     dicom_data: &[u8],
     config: Option<&Config>,
-) -> Result<Vec<u8>, anyhow::Error> {
+    with_report: bool,
+) -> Result<(Vec<u8>, Option<serde_json::Value>), anyhow::Error> {
     let mut config_builder = ConfigBuilder::default();
 
     if let Some(cfg) = config {
@@ -135,16 +146,25 @@ fn perform_anonymization(
     let anonymizer = Anonymizer::new(processor);
 
     let cursor = Cursor::new(dicom_data);
-    let result = anonymizer
-        .anonymize(cursor)
-        .map_err(|e| anyhow::anyhow!("Anonymization failed: {}", e))?;
+
+    let (result, audit_report) = if with_report {
+        let (result, report) = anonymizer
+            .anonymize_with_report(cursor)
+            .map_err(|e| anyhow::anyhow!("Anonymization failed: {}", e))?;
+        (result, Some(serde_json::to_value(report)?))
+    } else {
+        let result = anonymizer
+            .anonymize(cursor)
+            .map_err(|e| anyhow::anyhow!("Anonymization failed: {}", e))?;
+        (result, None)
+    };
 
     let mut output = Vec::new();
     result
         .write(&mut output)
         .map_err(|e| anyhow::anyhow!("Failed to write DICOM: {}", e))?;
 
-    Ok(output)
+    Ok((output, audit_report))
 }
 
 fn handle_anonymization_error(e: anyhow::Error) -> Response {
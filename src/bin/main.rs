@@ -3,16 +3,18 @@ use clap::builder::TypedValueParser;
 use clap::Parser;
 use dicom_anonymization::actions::Action;
 use dicom_anonymization::config::UidRoot;
-use dicom_anonymization::processor::DefaultProcessor;
+use dicom_anonymization::processor::{DefaultProcessor, MappingRecorder};
 use dicom_anonymization::Anonymizer;
 use dicom_anonymization::{config::ConfigBuilder, AnonymizationError};
 use dicom_core::Tag;
 use dicom_dictionary_std::tags;
 use dicom_object::DefaultDicomObject;
 use env_logger::Builder;
-use log::{warn, Level, LevelFilter};
+use log::{info, warn, Level, LevelFilter};
 use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::{Condvar, Mutex};
 use std::{
     fs::File,
     io::{self, Read, Write},
@@ -21,6 +23,244 @@ use std::{
 };
 use walkdir::WalkDir;
 
+/// One row of the `--mapping-file` crosswalk: the file's own UID triple (so
+/// every row from the same file can be joined back together) plus the
+/// single tag this row reports.
+///
+/// Unlike [`dicom_anonymization::actions::AuditRecord`], a `MappingRow`
+/// exists specifically to carry a reversible original/anonymized value
+/// pair, so it can re-identify a subject - it's written only when
+/// `--mapping-file` is passed, never by default.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MappingRow {
+    original_study_instance_uid: String,
+    anonymized_study_instance_uid: String,
+    original_series_instance_uid: String,
+    anonymized_series_instance_uid: String,
+    original_sop_instance_uid: String,
+    anonymized_sop_instance_uid: String,
+    tag: Tag,
+    original_value: String,
+    anonymized_value: String,
+}
+
+impl MappingRow {
+    const CSV_HEADER: [&'static str; 9] = [
+        "original_study_instance_uid",
+        "anonymized_study_instance_uid",
+        "original_series_instance_uid",
+        "anonymized_series_instance_uid",
+        "original_sop_instance_uid",
+        "anonymized_sop_instance_uid",
+        "tag",
+        "original_value",
+        "anonymized_value",
+    ];
+
+    fn to_csv_fields(&self) -> [String; 9] {
+        [
+            self.original_study_instance_uid.clone(),
+            self.anonymized_study_instance_uid.clone(),
+            self.original_series_instance_uid.clone(),
+            self.anonymized_series_instance_uid.clone(),
+            self.original_sop_instance_uid.clone(),
+            self.anonymized_sop_instance_uid.clone(),
+            self.tag.to_string(),
+            self.original_value.clone(),
+            self.anonymized_value.clone(),
+        ]
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "original_study_instance_uid": self.original_study_instance_uid,
+            "anonymized_study_instance_uid": self.anonymized_study_instance_uid,
+            "original_series_instance_uid": self.original_series_instance_uid,
+            "anonymized_series_instance_uid": self.anonymized_series_instance_uid,
+            "original_sop_instance_uid": self.original_sop_instance_uid,
+            "anonymized_sop_instance_uid": self.anonymized_sop_instance_uid,
+            "tag": self.tag.to_string(),
+            "original_value": self.original_value,
+            "anonymized_value": self.anonymized_value,
+        })
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains(['"', ',', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Thread-safe, deduplicated collection point for every [`MappingRow`]
+/// produced while `--mapping-file` is set, shared across the `par_bridge`
+/// workers in [`main`].
+#[derive(Default)]
+struct MappingFile {
+    rows: Mutex<HashSet<MappingRow>>,
+}
+
+impl MappingFile {
+    fn extend(&self, rows: impl IntoIterator<Item = MappingRow>) {
+        self.rows.lock().unwrap().extend(rows);
+    }
+
+    /// Writes every collected row to `path` as JSON or CSV, guessed from
+    /// `path`'s extension the same way [`dicom_anonymization::config_format::ConfigFormat`]
+    /// guesses a config's format - defaulting to CSV when the extension
+    /// isn't recognized as JSON.
+    fn write(&self, path: &Path) -> Result<()> {
+        let rows = self.rows.lock().unwrap();
+        let is_json = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        let contents = if is_json {
+            let json_rows: Vec<_> = rows.iter().map(MappingRow::to_json).collect();
+            serde_json::to_string_pretty(&json_rows)?
+        } else {
+            let mut csv = MappingRow::CSV_HEADER.join(",");
+            csv.push('\n');
+            for row in rows.iter() {
+                let fields = row.to_csv_fields();
+                csv.push_str(
+                    &fields
+                        .iter()
+                        .map(|field| csv_field(field))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                );
+                csv.push('\n');
+            }
+            csv
+        };
+
+        std::fs::write(path, contents)
+            .with_context(|| format!("failed to write mapping file {}", path.display()))
+    }
+}
+
+/// Collects `(tag, original_value, anonymized_value)` for one file while
+/// [`DefaultProcessor::process_object_with_mapping`] runs, so [`anonymize`]
+/// can pair each one with that file's UID crosswalk afterwards.
+#[derive(Default)]
+struct TagMappingCollector {
+    records: Mutex<Vec<(Tag, String, String)>>,
+}
+
+/// One content hash's state in [`DedupState`]: either still being anonymized
+/// by whichever worker claimed it, or resolved to the output path that
+/// anonymization produced.
+enum DedupEntry {
+    Pending,
+    Done(PathBuf),
+}
+
+/// Shared, thread-safe record of every input content hash seen so far this
+/// run, mapped to the output path it was anonymized to, consulted by each
+/// `par_bridge` worker before `--dedup` lets a byte-identical duplicate
+/// input skip the anonymization pipeline.
+///
+/// Checking and claiming a hash is one atomic operation ([`Self::claim`]),
+/// not a separate lookup then insert - two workers racing on the same
+/// byte-identical input would otherwise both miss the lookup before either
+/// inserts, both anonymize, and both open the same deterministic output path
+/// for writing at once. The loser of a race instead blocks in `claim` until
+/// the winner resolves the hash.
+#[derive(Default)]
+struct DedupState {
+    seen: Mutex<HashMap<String, DedupEntry>>,
+    resolved: Condvar,
+}
+
+/// What [`DedupState::claim`] found for a content hash: either the caller is
+/// now responsible for anonymizing it (and must eventually call
+/// [`DedupClaim::resolve`]), or another worker already has, in which case
+/// `Done`'s path should be used directly instead.
+enum Claim<'a> {
+    Mine(DedupClaim<'a>),
+    Done(PathBuf),
+}
+
+/// Holds exclusive responsibility, within the run, for anonymizing the
+/// content hash it was issued for. Every other worker that claims the same
+/// hash blocks in [`DedupState::claim`] until [`Self::resolve`] runs - or,
+/// if this claim is dropped without resolving (the anonymization failed),
+/// until the claim is released so another worker can retry it instead of
+/// waiting forever.
+struct DedupClaim<'a> {
+    dedup: &'a DedupState,
+    content_hash: String,
+    resolved: bool,
+}
+
+impl DedupClaim<'_> {
+    /// Records the output path this claim's content hash resolved to, and
+    /// wakes every worker blocked on it in [`DedupState::claim`].
+    fn resolve(mut self, output_path: PathBuf) {
+        self.dedup
+            .seen
+            .lock()
+            .unwrap()
+            .insert(self.content_hash.clone(), DedupEntry::Done(output_path));
+        self.dedup.resolved.notify_all();
+        self.resolved = true;
+    }
+}
+
+impl Drop for DedupClaim<'_> {
+    fn drop(&mut self) {
+        if !self.resolved {
+            self.dedup.seen.lock().unwrap().remove(&self.content_hash);
+            self.dedup.resolved.notify_all();
+        }
+    }
+}
+
+impl DedupState {
+    /// Atomically checks `content_hash` against every hash already claimed
+    /// this run, and if it's new, claims it for the caller in the same
+    /// locked section - eliminating the lookup-then-insert race a separate
+    /// pair of methods would have.
+    ///
+    /// Blocks if another worker already claimed `content_hash` but hasn't
+    /// resolved it yet, returning `Claim::Done` with its output path once it
+    /// has (or re-claiming `content_hash` for the caller instead, if the
+    /// prior claimant's anonymization failed).
+    fn claim(&self, content_hash: &str) -> Claim<'_> {
+        let mut seen = self.seen.lock().unwrap();
+        loop {
+            match seen.get(content_hash) {
+                None => {
+                    seen.insert(content_hash.to_string(), DedupEntry::Pending);
+                    return Claim::Mine(DedupClaim {
+                        dedup: self,
+                        content_hash: content_hash.to_string(),
+                        resolved: false,
+                    });
+                }
+                Some(DedupEntry::Done(output_path)) => return Claim::Done(output_path.clone()),
+                Some(DedupEntry::Pending) => {
+                    seen = self.resolved.wait(seen).unwrap();
+                }
+            }
+        }
+    }
+}
+
+impl MappingRecorder for TagMappingCollector {
+    fn record(&self, tag: Tag, original_value: &str, anonymized_value: &str) {
+        self.records.lock().unwrap().push((
+            tag,
+            original_value.to_string(),
+            anonymized_value.to_string(),
+        ));
+    }
+}
+
 #[derive(Clone)]
 struct TagValueParser;
 
@@ -80,6 +320,31 @@ struct Args {
     /// Tags to exclude from anonymization, e.g. "00100020,00080050"
     #[arg(long, value_name = "TAGS", value_delimiter = ',', value_parser = TagValueParser)]
     exclude: Vec<Tag>,
+
+    /// Secret used to key hashing operations, so hashed values can't be
+    /// recomputed by anyone who doesn't know it (see --secret-file to read it
+    /// from a file instead of the command line)
+    #[arg(long, value_name = "SECRET", conflicts_with = "secret_file")]
+    secret: Option<String>,
+
+    /// File to read the hashing secret from, instead of passing it directly
+    /// with --secret
+    #[arg(long, value_name = "PATH", conflicts_with = "secret")]
+    secret_file: Option<PathBuf>,
+
+    /// Write a JSON or CSV (guessed from this path's extension) crosswalk of
+    /// every original value this run replaced, alongside the anonymized
+    /// value and the file's own UID triple - lets an authorized custodian
+    /// re-link a de-identified study back to its source. Omit this flag to
+    /// anonymize without keeping one
+    #[arg(long, value_name = "PATH")]
+    mapping_file: Option<PathBuf>,
+
+    /// In directory mode, skip re-anonymizing inputs whose content was
+    /// already seen earlier in this run, writing to the same output that
+    /// earlier occurrence produced instead
+    #[arg(long)]
+    dedup: bool,
 }
 
 struct DicomOutputFilePath {
@@ -132,8 +397,15 @@ impl DicomOutputFilePath {
     }
 }
 
-fn anonymize(anonymizer: &Anonymizer, input_path: &PathBuf, output_path: &PathBuf) -> Result<()> {
-    let input_src: Box<dyn Read> = if input_path == Path::new("-") {
+fn anonymize(
+    anonymizer: &Anonymizer,
+    processor: &DefaultProcessor,
+    mapping_file: Option<&MappingFile>,
+    dedup: Option<&DedupState>,
+    input_path: &PathBuf,
+    output_path: &PathBuf,
+) -> Result<PathBuf> {
+    let mut input_src: Box<dyn Read> = if input_path == Path::new("-") {
         Box::new(io::stdin().lock())
     } else {
         Box::new(
@@ -142,35 +414,102 @@ fn anonymize(anonymizer: &Anonymizer, input_path: &PathBuf, output_path: &PathBu
         )
     };
 
-    // Anonymize the input file
-    let anonymized_obj = anonymizer
-        .anonymize(input_src)
-        .with_context(|| format!("failed to anonymize {}", input_path.display()))?;
+    let mut bytes = Vec::new();
+    input_src
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("failed to read {}", input_path.display()))?;
+
+    // Content hash of the raw input, atomically checked against and claimed
+    // in every hash already seen this run so a byte-identical duplicate can
+    // skip straight to its already-produced output instead of running the
+    // anonymization pipeline again - anonymization is a deterministic
+    // function of the input bytes and `Config`, so a duplicate input is
+    // guaranteed to resolve to the exact same output path the first
+    // occurrence already wrote.
+    let content_hash = dedup.map(|_| blake3::hash(&bytes).to_hex().to_string());
+    let dedup_claim = match (dedup, &content_hash) {
+        (Some(dedup), Some(content_hash)) => match dedup.claim(content_hash) {
+            Claim::Done(existing_output_path) => {
+                info!(
+                    "skipping duplicate input {} (content already anonymized as {})",
+                    input_path.display(),
+                    existing_output_path.display()
+                );
+                return Ok(existing_output_path);
+            }
+            Claim::Mine(claim) => Some(claim),
+        },
+        _ => None,
+    };
+
+    // Anonymize the input file, recording a mapping row per changed value
+    // when --mapping-file is set
+    let anonymized = match mapping_file {
+        Some(mapping_file) => {
+            let original = dicom_object::from_reader(io::Cursor::new(&bytes))
+                .with_context(|| format!("failed to read {}", input_path.display()))?;
+
+            let collector = TagMappingCollector::default();
+            let anonymized = processor
+                .process_object_with_mapping(&original, &collector)
+                .with_context(|| format!("failed to anonymize {}", input_path.display()))?;
+
+            let original_uids = DicomOutputFilePath::from_dicom_object(&original)?;
+            let anonymized_uids = DicomOutputFilePath::from_dicom_object(&anonymized)?;
+
+            let rows = collector.records.into_inner().unwrap().into_iter().map(
+                |(tag, original_value, anonymized_value)| MappingRow {
+                    original_study_instance_uid: original_uids.study_instance_uid.clone(),
+                    anonymized_study_instance_uid: anonymized_uids.study_instance_uid.clone(),
+                    original_series_instance_uid: original_uids.series_instance_uid.clone(),
+                    anonymized_series_instance_uid: anonymized_uids.series_instance_uid.clone(),
+                    original_sop_instance_uid: original_uids.sop_instance_uid.clone(),
+                    anonymized_sop_instance_uid: anonymized_uids.sop_instance_uid.clone(),
+                    tag,
+                    original_value,
+                    anonymized_value,
+                },
+            );
+            mapping_file.extend(rows);
+
+            anonymized
+        }
+        None => {
+            anonymizer
+                .anonymize(io::Cursor::new(&bytes))
+                .with_context(|| format!("failed to anonymize {}", input_path.display()))?
+                .anonymized
+        }
+    };
+
+    let resolved_output_path = if output_path.is_dir() {
+        let file_path = DicomOutputFilePath::from_dicom_object(&anonymized)?;
+        output_path.join(file_path.to_path_buf())
+    } else {
+        output_path.clone()
+    };
 
     let output_target: Box<dyn Write> = if output_path == Path::new("-") {
         Box::new(io::stdout().lock())
     } else {
-        let output_file_path = if output_path.is_dir() {
-            let file_path = DicomOutputFilePath::from_dicom_object(&anonymized_obj.anonymized)?;
-            &output_path.join(file_path.to_path_buf())
-        } else {
-            output_path
-        };
-
         // Create intermediate output file directories if they don't exist yet
-        if let Some(parent_dir) = output_file_path.parent() {
+        if let Some(parent_dir) = resolved_output_path.parent() {
             std::fs::create_dir_all(parent_dir)?;
         }
 
         Box::new(
-            File::create(output_file_path)
-                .with_context(|| format!("failed to create {}", output_file_path.display()))?,
+            File::create(&resolved_output_path)
+                .with_context(|| format!("failed to create {}", resolved_output_path.display()))?,
         )
     };
     // Write the anonymized data to the output target
-    let _ = anonymized_obj.write(output_target);
+    let _ = anonymized.write(output_target);
 
-    Ok(())
+    if let Some(claim) = dedup_claim {
+        claim.resolve(resolved_output_path.clone());
+    }
+
+    Ok(resolved_output_path)
 }
 
 fn main() -> Result<()> {
@@ -183,6 +522,10 @@ fn main() -> Result<()> {
     let continue_on_read_error = args.r#continue;
     let verbose = args.verbose;
     let exclude_tags = args.exclude;
+    let secret = args.secret;
+    let secret_file = args.secret_file;
+    let mapping_file_path = args.mapping_file;
+    let dedup_enabled = args.dedup;
 
     let log_level = if verbose {
         LevelFilter::Info
@@ -220,13 +563,42 @@ fn main() -> Result<()> {
         config_builder = config_builder.tag_action(tag, Action::Keep);
     }
 
+    // secret to key hashing operations with, so pseudonyms can't be
+    // recomputed by anyone who doesn't know it
+    let secret = match secret_file {
+        Some(path) => Some(
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read secret file {}", path.display()))?
+                .trim_end_matches(['\n', '\r'])
+                .to_string(),
+        ),
+        None => secret,
+    };
+    if let Some(secret) = secret {
+        config_builder = config_builder.keyed_hash_fn(secret);
+    }
+
     let config = config_builder.build();
     let processor = DefaultProcessor::new(config);
-    let anonymizer = Anonymizer::new(processor);
+    let anonymizer = Anonymizer::new(processor.clone());
+    let mapping_file = mapping_file_path.as_ref().map(|_| MappingFile::default());
+    let dedup = dedup_enabled.then(DedupState::default);
 
     // Input is stdin or a file
     if input_path == Path::new("-") || input_path.is_file() {
-        anonymize(&anonymizer, &input_path, &output_path)?;
+        anonymize(
+            &anonymizer,
+            &processor,
+            mapping_file.as_ref(),
+            dedup.as_ref(),
+            &input_path,
+            &output_path,
+        )?;
+
+        if let (Some(mapping_file), Some(path)) = (&mapping_file, &mapping_file_path) {
+            mapping_file.write(path)?;
+        }
+
         return Ok(());
     }
 
@@ -255,7 +627,14 @@ fn main() -> Result<()> {
             })
             .par_bridge() // convert to a parallel iterator
             .try_for_each(|path_buf| {
-                let result = anonymize(&anonymizer, &path_buf, &output_path);
+                let result = anonymize(
+                    &anonymizer,
+                    &processor,
+                    mapping_file.as_ref(),
+                    dedup.as_ref(),
+                    &path_buf,
+                    &output_path,
+                );
                 match result {
                     Err(e) if continue_on_read_error => {
                         if let Some(&AnonymizationError::ReadError(_)) =
@@ -271,6 +650,10 @@ fn main() -> Result<()> {
                 }
             })?;
 
+        if let (Some(mapping_file), Some(path)) = (&mapping_file, &mapping_file_path) {
+            mapping_file.write(path)?;
+        }
+
         return Ok(());
     }
 